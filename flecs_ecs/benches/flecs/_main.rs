@@ -125,7 +125,8 @@ fn main() {}
 //     name = hooks_bench;
 //     config = ecs_default_criterion();
 //     targets =
-//     add_remove_hooks_components
+//     add_remove_hooks_components,
+//     move_hook_tables
 // );
 
 // criterion_group!(
@@ -472,6 +472,39 @@ macro_rules! bench_add_remove_hooks {
     }};
 }
 
+macro_rules! bench_move_hook {
+    ($group:expr,$name:literal,$hook:expr,$tables:expr) => {{
+        $group.bench_function($name, |bencher| {
+            let world = World::new();
+            if $hook {
+                world.component::<Position>().on_move(|_, pos| {
+                    black_box(pos);
+                });
+            }
+
+            // spread the entities over `$tables` tables that store `Position`
+            let tables: Vec<Entity> = (0..$tables).map(|_| world.entity().id()).collect();
+            let tag = world.entity().id();
+            let entities = create_entities(&world, ENTITY_COUNT as usize);
+            for (i, entity) in entities.iter().enumerate() {
+                entity.set(Position::default()).add_id(tables[i % $tables]);
+            }
+
+            bencher.iter_custom(|iters| {
+                let start = Instant::now();
+                for _ in 0..iters {
+                    for entity in &entities {
+                        entity.add_id(tag);
+                        entity.remove_id(tag);
+                    }
+                }
+                let elapsed = start.elapsed();
+                elapsed / (2 * ENTITY_COUNT) as u32
+            });
+        });
+    }};
+}
+
 pub(crate) use add_component_on_add_hook;
 pub(crate) use add_component_on_remove_hook;
 pub(crate) use add_component_range;
@@ -485,6 +518,7 @@ pub(crate) use bench_create_delete_entity;
 pub(crate) use bench_create_delete_entity_cmd;
 pub(crate) use bench_get_relationship_target;
 pub(crate) use bench_loop_entities;
+pub(crate) use bench_move_hook;
 // pub(crate) use ensure_mut_component_range;
 // pub(crate) use ensure_mut_component_range_cmd;
 pub(crate) use get_component_range;
@@ -10,3 +10,18 @@ pub fn add_remove_hooks_components(criterion: &mut Criterion) {
 
     group.finish();
 }
+
+/// The on move hook finds the entity of a moved value by scanning the tables that store the
+/// component, so the cost of a move grows with the number of tables.
+pub fn move_hook_tables(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("move_hook_tables");
+
+    bench_move_hook!(group, "no_hook_1", false, 1);
+    bench_move_hook!(group, "1", true, 1);
+    bench_move_hook!(group, "no_hook_64", false, 64);
+    bench_move_hook!(group, "16", true, 16);
+    bench_move_hook!(group, "64", true, 64);
+    bench_move_hook!(group, "256", true, 256);
+
+    group.finish();
+}
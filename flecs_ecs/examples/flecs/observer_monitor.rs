@@ -30,19 +30,21 @@ fn main() {
     world
         .observer::<flecs::Monitor, (&Position, &Velocity)>()
         .each_iter(|it, index, (_pos, _vel)| {
-            if it.event() == flecs::OnAdd::ID {
-                println!(
-                    " - Enter: {}: {}",
-                    it.event_id().to_str(),
-                    it.entity(index).name()
-                );
-            } else if it.event() == flecs::OnRemove::ID {
-                println!(
-                    " - Leave: {}: {}",
-                    it.event_id().to_str(),
-                    it.entity(index).name()
-                );
-            }
+            it.dispatch_event()
+                .on::<flecs::OnAdd>(|| {
+                    println!(
+                        " - Enter: {}: {}",
+                        it.event_id().to_str(),
+                        it.entity(index).name()
+                    );
+                })
+                .on::<flecs::OnRemove>(|| {
+                    println!(
+                        " - Leave: {}: {}",
+                        it.event_id().to_str(),
+                        it.entity(index).name()
+                    );
+                });
         });
 
     // Create entity
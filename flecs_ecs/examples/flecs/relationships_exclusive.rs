@@ -1,17 +1,15 @@
 use crate::z_ignore_test_common::*;
 
 use flecs_ecs::prelude::*;
-// Type for Platoon relationship
+// Type for Platoon relationship. Registering Platoon as exclusive relationship
+// ensures that an entity can only belong to a single Platoon.
 #[derive(Component)]
+#[flecs(exclusive)]
 struct Platoon;
 
 fn main() {
     let world = World::new();
 
-    // Register Platoon as exclusive relationship. This ensures that an entity
-    // can only belong to a single Platoon.
-    world.component::<Platoon>().add::<flecs::Exclusive>();
-
     // Create two platoons
     let platoon_1 = world.entity();
     let platoon_2 = world.entity();
@@ -1,16 +1,15 @@
 use crate::z_ignore_test_common::*;
 
 use flecs_ecs::prelude::*;
+// Register TradesWith as symmetric relationship. Symmetric relationships
+// go both ways, adding (R, B) to A will also add (R, A) to B.
 #[derive(Component)]
+#[flecs(symmetric)]
 struct TradesWith;
 
 fn main() {
     let world = World::new();
 
-    // Register TradesWith as symmetric relationship. Symmetric relationships
-    // go both ways, adding (R, B) to A will also add (R, A) to B.
-    world.component::<TradesWith>().add::<flecs::Symmetric>();
-
     // Create two players
     let player_1 = world.entity();
     let player_2 = world.entity();
@@ -1,14 +1,130 @@
 //! Optional addon for running the main application loop.
 
 use std::ffi::c_void;
+use std::os::raw::c_int;
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 use crate::core::*;
 use crate::sys;
 
+// flecs keeps the run action and the desc passed to `ecs_app_run` in globals, the lock is held
+// until the run action copied the desc.
+static APP_DESC_LOCK: Mutex<()> = Mutex::new(());
+
+type InitAction<'a> = Box<dyn FnMut(&World) + 'a>;
+type RunAction<'a> = Box<dyn FnMut(&AppRunner) -> i32 + 'a>;
+
+struct AppActions<'a> {
+    /// The context set with [`App::context`], the desc passed to flecs points to the actions.
+    ctx: *mut c_void,
+    on_init: Option<InitAction<'a>>,
+    run_action: Option<RunAction<'a>>,
+    desc_guard: Option<MutexGuard<'static, ()>>,
+}
+
 /// Application interface.
 pub struct App<'a> {
     world: WorldRef<'a>,
     desc: sys::ecs_app_desc_t,
+    actions: AppActions<'a>,
+}
+
+/// Error returned by [`App::run`] when the application exits with a non-zero exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppError {
+    /// The exit code of the application.
+    pub code: i32,
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "application exited with code {}", self.code)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// Drives the frames of an application from a custom run action, see [`App::set_run_action`].
+pub struct AppRunner<'a> {
+    world: WorldRef<'a>,
+    desc: sys::ecs_app_desc_t,
+}
+
+impl<'a> AppRunner<'a> {
+    /// The world of the application.
+    pub fn world(&self) -> WorldRef<'a> {
+        self.world
+    }
+
+    /// Run a single frame of the application.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the application should quit.
+    ///
+    /// # See also
+    ///
+    /// * C API: `ecs_app_run_frame`
+    #[doc(alias = "ecs_app_run_frame")]
+    pub fn frame(&self) -> bool {
+        unsafe { sys::ecs_app_run_frame(self.world.world_ptr_mut(), &self.desc) == 0 }
+    }
+
+    /// The number of frames set with [`App::set_frames`], 0 if the application runs until it quits.
+    pub fn frames(&self) -> i32 {
+        self.desc.frames
+    }
+
+    /// The time delta set with [`App::set_delta_time`], 0 for measured values.
+    pub fn delta_time(&self) -> FTime {
+        self.desc.delta_time
+    }
+
+    /// The context set with [`App::context`].
+    pub fn context(&self) -> *mut c_void {
+        self.desc.ctx
+    }
+}
+
+unsafe extern "C" fn run_app_actions(
+    world: *mut sys::ecs_world_t,
+    desc: *mut sys::ecs_app_desc_t,
+) -> c_int {
+    let mut desc = *desc;
+    let actions = &mut *(desc.ctx as *mut AppActions);
+    actions.desc_guard = None;
+    desc.ctx = actions.ctx;
+
+    if let Some(init) = desc.init {
+        init(world);
+    }
+    let world = WorldRef::from_ptr(world);
+    if let Some(on_init) = actions.on_init.as_mut() {
+        on_init(&world);
+    }
+
+    let runner = AppRunner { world, desc };
+    if let Some(run_action) = actions.run_action.as_mut() {
+        return run_action(&runner);
+    }
+
+    let mut result = 0;
+    let mut frame = 0;
+    while desc.frames == 0 || frame < desc.frames {
+        result = sys::ecs_app_run_frame(world.world_ptr_mut(), &desc);
+        if result != 0 {
+            break;
+        }
+        frame += 1;
+    }
+    sys::ecs_quit(world.world_ptr_mut());
+
+    // a frame returns 1 once the world quits
+    if result == 1 {
+        0
+    } else {
+        result
+    }
 }
 
 impl<'a> App<'a> {
@@ -26,6 +142,12 @@ impl<'a> App<'a> {
         let mut obj = Self {
             world: world.world(),
             desc: sys::ecs_app_desc_t::default(),
+            actions: AppActions {
+                ctx: std::ptr::null_mut(),
+                on_init: None,
+                run_action: None,
+                desc_guard: None,
+            },
         };
 
         let stats = unsafe { sys::ecs_get_world_info(obj.world.ptr_mut()) };
@@ -130,7 +252,6 @@ impl<'a> App<'a> {
         self
     }
 
-    // TODO change this to FnMut(&mut World) -> cint
     /// Set the application init action.
     ///
     /// # Arguments
@@ -141,6 +262,7 @@ impl<'a> App<'a> {
     ///
     /// * C++ API: `app_builder::init`
     #[doc(alias = "app_builder::init")]
+    #[deprecated(note = "use `App::on_init`, which accepts closures")]
     pub fn init(&mut self, value: sys::ecs_app_init_action_t) -> &mut Self {
         self.desc.init = value;
         self
@@ -148,6 +270,9 @@ impl<'a> App<'a> {
 
     /// Set the application context.
     ///
+    /// The context is passed to the frame action of flecs, and can be read from a custom run
+    /// action with [`AppRunner::context`].
+    ///
     /// # Arguments
     ///
     /// * `ctx` - The context.
@@ -157,7 +282,65 @@ impl<'a> App<'a> {
     /// * C++ API: `app_builder::ctx`
     #[doc(alias = "app_builder::ctx")]
     pub fn context(&mut self, ctx: *mut c_void) -> &mut Self {
-        self.desc.ctx = ctx;
+        self.actions.ctx = ctx;
+        self
+    }
+
+    /// Set a function that is invoked before the main loop starts, after the init action set with
+    /// [`App::init`].
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function to invoke with the world.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `app_builder::init`
+    #[doc(alias = "app_builder::init")]
+    pub fn on_init(&mut self, func: impl FnMut(&World) + 'a) -> &mut Self {
+        self.actions.on_init = Some(Box::new(func));
+        self
+    }
+
+    /// Set a custom run action that drives the frames of the application, instead of the default
+    /// loop that runs frames until the application quits or the number of frames is reached.
+    ///
+    /// This allows running the application from an external event loop or a test harness. The
+    /// target fps and number of threads are not applied when a run action is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The run action, which returns the exit code of the application.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    ///
+    /// let mut frames = 0;
+    /// let result = world
+    ///     .app()
+    ///     .set_frames(3)
+    ///     .set_run_action(|runner| {
+    ///         while frames < runner.frames() && runner.frame() {
+    ///             frames += 1;
+    ///         }
+    ///         0
+    ///     })
+    ///     .run();
+    ///
+    /// assert!(result.is_ok());
+    /// assert_eq!(frames, 3);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * C API: `ecs_app_set_run_action`
+    #[doc(alias = "ecs_app_set_run_action")]
+    pub fn set_run_action(&mut self, func: impl FnMut(&AppRunner) -> i32 + 'a) -> &mut Self {
+        self.actions.run_action = Some(Box::new(func));
         self
     }
 
@@ -168,38 +351,44 @@ impl<'a> App<'a> {
     ///
     /// # Returns
     ///
-    /// The exit code of the application.
+    /// An error with the exit code of the application if it is not 0.
     ///
     /// # See also
     ///
     /// * C++ API: `app_builder::run`
     #[doc(alias = "app_builder::run")]
-    pub fn run(&mut self) -> i32 {
-        let world_ptr = self.world.ptr_mut();
-        let result = unsafe { sys::ecs_app_run(world_ptr, &mut self.desc) };
-        unsafe {
-            if sys::ecs_should_quit(world_ptr) {
-                // Only free world if quit flag is set. This ensures that we won't
-                // try to cleanup the world if the app is used in an environment
-                // that takes over the main loop, like with emscripten.
-                if sys::flecs_poly_release_(world_ptr as *mut c_void) == 0 {
-                    sys::ecs_fini(world_ptr);
-                }
+    pub fn run(&mut self) -> Result<(), AppError> {
+        let world_ptr = self.world.world_ptr_mut();
+
+        // flecs only applies these for its own run action
+        if self.actions.run_action.is_none() {
+            if self.desc.target_fps != 0.0 {
+                unsafe { sys::ecs_set_target_fps(world_ptr, self.desc.target_fps) };
+            }
+            if self.desc.threads != 0 {
+                unsafe { sys::ecs_set_threads(world_ptr, self.desc.threads) };
             }
         }
-        result
 
-        /*
-                int result = ecs_app_run(world_, &desc_);
-        if (ecs_should_quit(world_)) {
-            // Only free world if quit flag is set. This ensures that we won't
-            // try to cleanup the world if the app is used in an environment
-            // that takes over the main loop, like with emscripten.
-            if (!flecs_poly_release(world_)) {
-                ecs_fini(world_);
+        let code = unsafe {
+            if sys::ecs_app_set_run_action(Some(run_app_actions)) != 0 {
+                return Err(AppError { code: -1 });
             }
+            self.actions.desc_guard =
+                Some(APP_DESC_LOCK.lock().unwrap_or_else(PoisonError::into_inner));
+            self.desc.ctx = &mut self.actions as *mut AppActions as *mut c_void;
+            let code = sys::ecs_app_run(world_ptr, &mut self.desc);
+            self.desc.ctx = std::ptr::null_mut();
+            self.actions.desc_guard = None;
+            code
+        };
+
+        // unlike the C++ app, the app doesn't own the world, it's cleaned up when the world is
+        // dropped.
+        if code == 0 {
+            Ok(())
+        } else {
+            Err(AppError { code })
         }
-        return result;
-         */
     }
 }
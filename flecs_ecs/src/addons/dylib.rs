@@ -0,0 +1,404 @@
+//! Loading modules from dynamic libraries, see [`World::import_dylib`].
+//!
+//! A library exports its module with [`flecs_module_export!`](crate::flecs_module_export). The
+//! host and the library must use the same copy of `flecs_ecs`, for example by linking both
+//! against a crate that is built as `dylib`, as the ids of components are shared between them.
+
+use std::ffi::{c_char, CStr, OsStr};
+use std::fmt::{Display, Formatter};
+
+use libloading::Library;
+
+use crate::addons::Module;
+use crate::core::*;
+use crate::sys;
+
+/// The version of [`ModuleExport`], changed when its layout changes.
+pub const MODULE_ABI_VERSION: u32 = 1;
+
+/// The name of the function that returns the [`ModuleExport`] of a library.
+pub const MODULE_EXPORT_SYMBOL: &str = "flecs_module_export";
+
+// identifies the copy of the crate a module export was created by.
+static CRATE_ANCHOR: u8 = 0;
+
+const CRATE_VERSION: &CStr =
+    match CStr::from_bytes_with_nul(concat!(env!("CARGO_PKG_VERSION"), "\0").as_bytes()) {
+        Ok(version) => version,
+        Err(_) => panic!("invalid crate version"),
+    };
+
+/// The layout of a component that a library shares with the host.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ComponentLayout {
+    /// The symbol of the component, the name of its type.
+    pub symbol: *const c_char,
+    pub size: usize,
+    pub alignment: usize,
+    /// The hash of the layout, see [`layout_hash`].
+    pub hash: u64,
+}
+
+impl ComponentLayout {
+    /// Returns the layout of a component, the symbol is leaked as it's used for the lifetime of
+    /// the library.
+    pub fn of<T: ComponentId>() -> Self {
+        let size = std::mem::size_of::<T>();
+        Self {
+            symbol: type_name_cstring::<T>().into_raw(),
+            size,
+            alignment: if size != 0 {
+                std::mem::align_of::<T>()
+            } else {
+                0
+            },
+            hash: layout_hash::<T>(),
+        }
+    }
+}
+
+/// Returns the hash of the layout of a component, which is the same for the host and a library
+/// when they agree on the symbol, size and alignment of the component, and on whether it's a tag
+/// or enum, and needs drop, implements `Clone` and implements `Default`.
+///
+/// The hashes of the components that are registered in a world are recorded, see
+/// [`ModuleExport::verify`].
+pub fn layout_hash<T: ComponentId>() -> u64 {
+    // FNV-1a, which gives the same hash in every build
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut write = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    };
+    write(std::any::type_name::<T>().as_bytes());
+    write(&(std::mem::size_of::<T>() as u64).to_le_bytes());
+    write(&(std::mem::align_of::<T>() as u64).to_le_bytes());
+    write(&[
+        T::IS_TAG as u8,
+        T::IS_ENUM as u8,
+        T::NEEDS_DROP as u8,
+        T::IMPLS_CLONE as u8,
+        T::IMPLS_DEFAULT as u8,
+    ]);
+    hash
+}
+
+/// The entry point of a module in a dynamic library, created by
+/// [`flecs_module_export!`](crate::flecs_module_export).
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ModuleExport {
+    /// Always [`MODULE_ABI_VERSION`], checked before any other field is read.
+    pub abi_version: u32,
+    /// The major, minor and patch version of flecs.
+    pub flecs_version: [u32; 3],
+    /// The version of `flecs_ecs`.
+    pub crate_version: *const c_char,
+    /// The address of a static in `flecs_ecs`, which is the same for the host and the library
+    /// when they use the same copy of the crate.
+    pub crate_anchor: *const u8,
+    /// The components the library shares with the host.
+    pub components: *const ComponentLayout,
+    pub component_count: usize,
+    /// Imports the module, returns the module entity.
+    pub import: unsafe extern "C" fn(world: *mut sys::ecs_world_t) -> EntityT,
+    /// Unloads the module, returns whether it was imported.
+    pub unload: unsafe extern "C" fn(world: *mut sys::ecs_world_t) -> bool,
+}
+
+// the pointers point to leaked or static data
+unsafe impl Send for ModuleExport {}
+unsafe impl Sync for ModuleExport {}
+
+unsafe extern "C" fn import_module<T: Module>(world: *mut sys::ecs_world_t) -> EntityT {
+    *WorldRef::from_ptr(world).import::<T>().id
+}
+
+unsafe extern "C" fn unload_module<T: Module>(world: *mut sys::ecs_world_t) -> bool {
+    WorldRef::from_ptr(world).unload::<T>()
+}
+
+impl ModuleExport {
+    /// Create the export of a module, the components are leaked as they're used for the lifetime
+    /// of the library.
+    ///
+    /// # Arguments
+    ///
+    /// * `components` - The components the library shares with the host.
+    pub fn new<T: Module>(components: Vec<ComponentLayout>) -> Self {
+        let components = components.leak();
+        Self {
+            abi_version: MODULE_ABI_VERSION,
+            flecs_version: [
+                sys::FLECS_VERSION_MAJOR,
+                sys::FLECS_VERSION_MINOR,
+                sys::FLECS_VERSION_PATCH,
+            ],
+            crate_version: CRATE_VERSION.as_ptr(),
+            crate_anchor: &CRATE_ANCHOR,
+            components: components.as_ptr(),
+            component_count: components.len(),
+            import: import_module::<T>,
+            unload: unload_module::<T>,
+        }
+    }
+
+    /// Check that the module can be imported in a world, see [`DylibError`] for the checks.
+    ///
+    /// The components of the library that are registered in the world must have the same
+    /// [`layout_hash`], or the same size and alignment when they weren't registered with their Rust
+    /// type. The components that aren't registered yet are registered when the module is
+    /// imported, after which other libraries are checked against them.
+    ///
+    /// # Safety
+    ///
+    /// The pointers of the export must be valid, which they are for exports created with
+    /// [`ModuleExport::new`].
+    pub unsafe fn verify(&self, world: &World) -> Result<(), DylibError> {
+        if self.abi_version != MODULE_ABI_VERSION {
+            return Err(DylibError::AbiMismatch {
+                expected: MODULE_ABI_VERSION,
+                found: self.abi_version,
+            });
+        }
+
+        let flecs_version = [
+            sys::FLECS_VERSION_MAJOR,
+            sys::FLECS_VERSION_MINOR,
+            sys::FLECS_VERSION_PATCH,
+        ];
+        if self.flecs_version != flecs_version {
+            return Err(DylibError::FlecsVersionMismatch {
+                expected: flecs_version,
+                found: self.flecs_version,
+            });
+        }
+
+        let crate_version = CStr::from_ptr(self.crate_version).to_string_lossy();
+        if crate_version != env!("CARGO_PKG_VERSION") {
+            return Err(DylibError::CrateVersionMismatch {
+                expected: env!("CARGO_PKG_VERSION").to_owned(),
+                found: crate_version.into_owned(),
+            });
+        }
+
+        if !std::ptr::eq(self.crate_anchor, &CRATE_ANCHOR) {
+            return Err(DylibError::SeparateCrateCopy);
+        }
+
+        let components = if self.component_count == 0 {
+            &[]
+        } else {
+            std::slice::from_raw_parts(self.components, self.component_count)
+        };
+        for layout in components {
+            let id = sys::ecs_lookup_symbol(world.ptr_mut(), layout.symbol, false, false);
+            if id == 0 {
+                continue;
+            }
+            let matches = match world.world_ctx().component_layouts.get(&id) {
+                Some(&hash) => hash == layout.hash,
+                None => {
+                    let type_info = sys::ecs_get_type_info(world.ptr_mut(), id);
+                    let (size, alignment) = if type_info.is_null() {
+                        (0, 0)
+                    } else {
+                        ((*type_info).size as usize, (*type_info).alignment as usize)
+                    };
+                    size == layout.size && alignment == layout.alignment
+                }
+            };
+            if !matches {
+                return Err(DylibError::LayoutMismatch {
+                    component: CStr::from_ptr(layout.symbol).to_string_lossy().into_owned(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned by [`World::import_dylib`].
+#[derive(Debug)]
+pub enum DylibError {
+    /// The library could not be loaded.
+    Load(libloading::Error),
+    /// The library doesn't export a module with [`flecs_module_export!`](crate::flecs_module_export).
+    MissingExport,
+    /// The library was built with an incompatible version of the export.
+    AbiMismatch { expected: u32, found: u32 },
+    /// The library was built with a different version of flecs.
+    FlecsVersionMismatch { expected: [u32; 3], found: [u32; 3] },
+    /// The library was built with a different version of `flecs_ecs`.
+    CrateVersionMismatch { expected: String, found: String },
+    /// The library doesn't use the copy of `flecs_ecs` of the host.
+    SeparateCrateCopy,
+    /// A component of the library has a different layout than the component with the same symbol
+    /// in the world.
+    LayoutMismatch { component: String },
+}
+
+impl Display for DylibError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DylibError::Load(err) => write!(f, "failed to load module library: {err}"),
+            DylibError::MissingExport => {
+                write!(f, "library doesn't export `{MODULE_EXPORT_SYMBOL}`")
+            }
+            DylibError::AbiMismatch { expected, found } => write!(
+                f,
+                "module export version {found} doesn't match version {expected}"
+            ),
+            DylibError::FlecsVersionMismatch { expected, found } => write!(
+                f,
+                "module was built with flecs {}.{}.{}, expected {}.{}.{}",
+                found[0], found[1], found[2], expected[0], expected[1], expected[2]
+            ),
+            DylibError::CrateVersionMismatch { expected, found } => write!(
+                f,
+                "module was built with flecs_ecs {found}, expected {expected}"
+            ),
+            DylibError::SeparateCrateCopy => write!(
+                f,
+                "module doesn't share its copy of flecs_ecs with the host"
+            ),
+            DylibError::LayoutMismatch { component } => write!(
+                f,
+                "component {component} has a different layout in the module"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DylibError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DylibError::Load(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// A module that was imported from a dynamic library with [`World::import_dylib`].
+///
+/// The library is owned by the world, as the systems and hooks of the module may run code of the
+/// library. It's unloaded by [`World::unload_dylib`], or after the world is destroyed.
+pub struct DylibModule {
+    export: ModuleExport,
+    entity: Entity,
+}
+
+impl DylibModule {
+    /// The module entity.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl World {
+    /// Load a dynamic library and import the module it exports with
+    /// [`flecs_module_export!`](crate::flecs_module_export).
+    ///
+    /// The library is checked with [`ModuleExport::verify`] before the module is imported.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the library.
+    ///
+    /// # Safety
+    ///
+    /// Loading a library runs its initialization code, and the export of the library is trusted
+    /// to be created by [`flecs_module_export!`](crate::flecs_module_export).
+    pub unsafe fn import_dylib(&self, path: impl AsRef<OsStr>) -> Result<DylibModule, DylibError> {
+        let library = Library::new(path.as_ref()).map_err(DylibError::Load)?;
+        let export_fn = library
+            .get::<unsafe extern "C" fn() -> *const ModuleExport>(MODULE_EXPORT_SYMBOL.as_bytes())
+            .map_err(|_| DylibError::MissingExport)?;
+
+        let export = export_fn();
+        if export.is_null() {
+            return Err(DylibError::MissingExport);
+        }
+        // only the version is read before it's verified
+        if (*export).abi_version != MODULE_ABI_VERSION {
+            return Err(DylibError::AbiMismatch {
+                expected: MODULE_ABI_VERSION,
+                found: (*export).abi_version,
+            });
+        }
+        let export = *export;
+        export.verify(self)?;
+
+        let entity = Entity::new((export.import)(self.ptr_mut()));
+        self.world_ctx_mut().dylibs.push((*entity, library));
+        Ok(DylibModule { export, entity })
+    }
+
+    /// Unload a module that was imported with [`World::import_dylib`] and unload its library.
+    ///
+    /// The module is unloaded like [`World::unload`].
+    ///
+    /// # Returns
+    ///
+    /// Whether the module was still imported.
+    ///
+    /// # Safety
+    ///
+    /// No code of the library may run after it is unloaded, so there may be no entities left with
+    /// components that have hooks defined in the library, and no other worlds that use the library.
+    pub unsafe fn unload_dylib(&self, module: DylibModule) -> bool {
+        let unloaded = (module.export.unload)(self.ptr_mut());
+        self.world_ctx_mut()
+            .dylibs
+            .retain(|(entity, _)| *entity != *module.entity);
+        unloaded
+    }
+}
+
+/// Export a module from a dynamic library, so it can be imported with [`World::import_dylib`].
+///
+/// The first argument is the module, the other arguments are the components the library shares
+/// with the host, whose layout is checked when the module is imported. A library can export one
+/// module.
+///
+/// # Example
+///
+/// ```ignore
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[derive(Component)]
+/// struct GameplayModule;
+///
+/// impl Module for GameplayModule {
+///     fn module(world: &World) {
+///         world.system::<&mut Position>().each(|pos| pos.x += 1.0);
+///     }
+/// }
+///
+/// flecs_ecs::flecs_module_export!(GameplayModule, Position);
+/// ```
+#[macro_export]
+macro_rules! flecs_module_export {
+    ($module:ty $(, $component:ty)* $(,)?) => {
+        #[no_mangle]
+        pub extern "C" fn flecs_module_export() -> *const $crate::addons::ModuleExport {
+            static EXPORT: ::std::sync::OnceLock<$crate::addons::ModuleExport> =
+                ::std::sync::OnceLock::new();
+            EXPORT.get_or_init(|| {
+                $crate::addons::ModuleExport::new::<$module>(::std::vec![
+                    $($crate::addons::ComponentLayout::of::<$component>()),*
+                ])
+            })
+        }
+    };
+}
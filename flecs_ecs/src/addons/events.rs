@@ -0,0 +1,386 @@
+//! Buffered events that systems read when they run, rather than observing them when emitted.
+//!
+//! Events sent with [`World::send_event`] are stored in a double buffered [`Events`] singleton.
+//! The buffers are swapped in the [`flecs::pipeline::PostFrame`] phase, so an event can be read
+//! by systems during the frame it was sent in and the frame after, and is cleared after that.
+//!
+//! Systems read the events with [`Iter::read_events`], which keeps track of the events each system
+//! read. An [`EventReader`] does the same outside of systems.
+//!
+//! Unlike the events emitted with an [`EventBuilder`], these events are owned by the queue until
+//! they are cleared instead of being dispatched to observers when sent, so they aren't emitted
+//! through flecs.
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use flecs_ecs_derive::Component;
+
+use crate::core::*;
+
+/// Double buffered queue of events of type `E`, stored as a world singleton.
+///
+/// Events are usually sent with [`World::send_event`] or an [`EventWriter`] and read with an
+/// [`EventReader`].
+#[derive(Component)]
+pub struct Events<E>
+where
+    E: Event,
+{
+    previous: Vec<E>,
+    current: Vec<E>,
+    /// Index of the first event in `previous`, events are numbered in the order they were sent.
+    previous_start: usize,
+    /// Number of the next event to read for each system, see [`Iter::read_events`].
+    system_readers: HashMap<EntityT, usize, fxhash::FxBuildHasher>,
+}
+
+impl<E> Default for Events<E>
+where
+    E: Event,
+{
+    fn default() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+            previous_start: 0,
+            system_readers: Default::default(),
+        }
+    }
+}
+
+impl<E> Events<E>
+where
+    E: Event,
+{
+    /// Add an event to the queue.
+    pub fn send(&mut self, event: E) {
+        self.current.push(event);
+    }
+
+    /// Swap the buffers, dropping the events sent before the previous swap.
+    ///
+    /// Called once per frame by the system registered in [`World::add_events`].
+    pub fn update(&mut self) {
+        self.previous_start += self.previous.len();
+        self.previous = std::mem::take(&mut self.current);
+    }
+
+    /// Remove all events from the queue.
+    pub fn clear(&mut self) {
+        self.previous_start += self.previous.len() + self.current.len();
+        self.previous.clear();
+        self.current.clear();
+    }
+
+    /// Returns the number of events in the queue.
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+
+    /// Returns true if the queue has no events.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of the event that will be sent next.
+    fn end(&self) -> usize {
+        self.previous_start + self.len()
+    }
+
+    /// Take the events out of the queue, to be put back with [`Events::restore`].
+    fn take(&mut self) -> (Vec<E>, Vec<E>) {
+        (
+            std::mem::take(&mut self.previous),
+            std::mem::take(&mut self.current),
+        )
+    }
+
+    /// Put back the events taken with [`Events::take`], before the events sent since.
+    fn restore(&mut self, previous: Vec<E>, mut current: Vec<E>) {
+        current.append(&mut self.current);
+        self.previous = previous;
+        self.current = current;
+    }
+}
+
+/// Reads the events of type `E` that were sent since it last read.
+///
+/// Each reader keeps track of the events it read, so a reader is typically moved into the
+/// closure of the system that reads the events.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Event)]
+/// struct Scored {
+///     points: u32,
+/// }
+///
+/// #[derive(Component, Default)]
+/// struct Score(u32);
+///
+/// let world = World::new();
+/// world.set(Score::default());
+///
+/// let mut reader = world.event_reader::<Scored>();
+/// world.system::<()>().run(move |it| {
+///     let world = it.world();
+///     reader.read(&world, |scored| {
+///         world.get::<&mut Score>(|score| score.0 += scored.points);
+///     });
+/// });
+///
+/// world.send_event(Scored { points: 10 });
+/// world.send_event(Scored { points: 5 });
+///
+/// world.progress();
+/// world.progress();
+///
+/// world.get::<&Score>(|score| assert_eq!(score.0, 15));
+/// ```
+pub struct EventReader<E>
+where
+    E: Event,
+{
+    /// Number of the next event to read.
+    next: usize,
+    _phantom: PhantomData<fn() -> E>,
+}
+
+impl<E> EventReader<E>
+where
+    E: Event,
+{
+    /// Create a reader that reads the events currently in the queue and the events sent after.
+    pub(crate) fn new(world: &World) -> Self {
+        world.add_events::<E>();
+        Self::at(world.map::<&Events<E>, _>(|events| events.previous_start))
+    }
+
+    /// Create a reader that reads from event number `next`.
+    fn at(next: usize) -> Self {
+        Self {
+            next,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Invoke `func` for each event sent since the last read, in the order they were sent.
+    ///
+    /// The events are taken out of the queue while `func` is invoked, so `func` can send events of
+    /// type `E`, which are read by the next read. Other readers of `E` don't see any events when
+    /// they read from `func`.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world the events were sent in
+    /// * `func` - The function to invoke for each event
+    pub fn read(&mut self, world: &World, func: impl FnMut(&E)) {
+        let (previous_start, (previous, current)) =
+            world.map::<&mut Events<E>, _>(|events| (events.previous_start, events.take()));
+
+        let start = self.next.max(previous_start);
+        self.next = previous_start + previous.len() + current.len();
+        previous
+            .iter()
+            .chain(current.iter())
+            .skip(start - previous_start)
+            .for_each(func);
+
+        world.get::<&mut Events<E>>(|events| events.restore(previous, current));
+    }
+
+    /// Returns the number of events that weren't read yet.
+    pub fn len(&self, world: &World) -> usize {
+        world.map::<&Events<E>, _>(|events| events.end() - self.next.max(events.previous_start))
+    }
+
+    /// Returns true if all events were read.
+    pub fn is_empty(&self, world: &World) -> bool {
+        self.len(world) == 0
+    }
+
+    /// Mark all events as read without reading them.
+    pub fn clear(&mut self, world: &World) {
+        self.next = world.map::<&Events<E>, _>(|events| events.end());
+    }
+}
+
+/// Sends events of type `E` to the [`Events`] queue of a world.
+pub struct EventWriter<'a, E>
+where
+    E: Event,
+{
+    world: WorldRef<'a>,
+    _phantom: PhantomData<fn(E)>,
+}
+
+impl<'a, E> EventWriter<'a, E>
+where
+    E: Event,
+{
+    pub(crate) fn new(world: &'a World) -> Self {
+        world.add_events::<E>();
+        Self {
+            world: world.world(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Send an event.
+    pub fn send(&self, event: E) {
+        self.world
+            .get::<&mut Events<E>>(|events| events.send(event));
+    }
+
+    /// Send a batch of events.
+    pub fn send_batch(&self, events: impl IntoIterator<Item = E>) {
+        self.world
+            .get::<&mut Events<E>>(|queue| queue.current.extend(events));
+    }
+}
+
+impl<'a, const IS_RUN: bool, P> Iter<'a, IS_RUN, P>
+where
+    P: EventId,
+{
+    /// Invoke `func` for each event of type `E` sent since the system last read them, in the order
+    /// they were sent.
+    ///
+    /// The events each system read are tracked by the [`Events`] queue, the first read of a system
+    /// starts with the events that are in the queue. The queue has to be registered with
+    /// [`World::add_events`] before the system runs, there are no events to read otherwise.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `E` - The type of the events
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function to invoke for each event
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Event)]
+    /// struct Scored {
+    ///     points: u32,
+    /// }
+    ///
+    /// #[derive(Component, Default)]
+    /// struct Score(u32);
+    ///
+    /// let world = World::new();
+    /// world.set(Score::default());
+    /// world.add_events::<Scored>();
+    ///
+    /// world.system::<()>().run(|it| {
+    ///     let world = it.world();
+    ///     it.read_events::<Scored>(|scored| {
+    ///         world.get::<&mut Score>(|score| score.0 += scored.points);
+    ///     });
+    /// });
+    ///
+    /// world.send_event(Scored { points: 10 });
+    /// world.send_event(Scored { points: 5 });
+    ///
+    /// world.progress();
+    /// world.progress();
+    ///
+    /// world.get::<&Score>(|score| assert_eq!(score.0, 15));
+    /// ```
+    pub fn read_events<E>(&self, func: impl FnMut(&E))
+    where
+        E: Event,
+    {
+        let world = self.world();
+        if !world.has::<Events<E>>() {
+            return;
+        }
+
+        let system = *self.system().id();
+        let next = world.map::<&Events<E>, _>(|events| {
+            events
+                .system_readers
+                .get(&system)
+                .copied()
+                .unwrap_or(events.previous_start)
+        });
+        let mut reader = EventReader::<E>::at(next);
+        reader.read(&world, func);
+        world.get::<&mut Events<E>>(|events| {
+            events.system_readers.insert(system, reader.next);
+        });
+    }
+}
+
+/// Buffered events mixin implementation
+impl World {
+    /// Register the [`Events`] queue for events of type `E`.
+    ///
+    /// This adds the queue as singleton, with a system in the [`flecs::pipeline::PostFrame`]
+    /// phase that swaps its buffers every frame. Queues are also registered when events are
+    /// first sent or read, registering them up front is required when that happens in a system.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `E` - The type of the events
+    pub fn add_events<E>(&self)
+    where
+        E: Event,
+    {
+        if self.has::<Events<E>>() {
+            return;
+        }
+
+        self.set(Events::<E>::default());
+        self.system::<&mut Events<E>>()
+            .term_at(0)
+            .singleton()
+            .kind::<flecs::pipeline::PostFrame>()
+            .each_iter(|it, _, events| {
+                let world = it.world();
+                events.update();
+                events
+                    .system_readers
+                    .retain(|&system, _| world.is_alive(system));
+            });
+    }
+
+    /// Send an event to the [`Events`] queue for events of type `E`.
+    ///
+    /// The event can be read with an [`EventReader`] during this frame and the next.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The event to send
+    pub fn send_event<E>(&self, event: E)
+    where
+        E: Event,
+    {
+        self.event_writer::<E>().send(event);
+    }
+
+    /// Create a reader for events of type `E`.
+    ///
+    /// The reader starts with the events that are currently in the queue.
+    pub fn event_reader<E>(&self) -> EventReader<E>
+    where
+        E: Event,
+    {
+        EventReader::new(self)
+    }
+
+    /// Create a writer for events of type `E`.
+    pub fn event_writer<E>(&self) -> EventWriter<'_, E>
+    where
+        E: Event,
+    {
+        EventWriter::new(self)
+    }
+}
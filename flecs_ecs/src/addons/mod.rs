@@ -10,10 +10,34 @@ pub mod module;
 #[cfg(feature = "flecs_module")]
 pub use module::*;
 
+#[cfg(feature = "flecs_module_dylib")]
+pub mod dylib;
+
+#[cfg(feature = "flecs_module_dylib")]
+pub use dylib::*;
+
 #[cfg(feature = "flecs_system")]
 pub mod system;
 
 #[cfg(feature = "flecs_pipeline")]
 pub mod pipeline;
 
+#[cfg(feature = "flecs_pipeline")]
+pub use pipeline::{FixedTime, FixedUpdate};
+
+#[cfg(feature = "flecs_pipeline")]
+pub mod events;
+
+#[cfg(feature = "flecs_pipeline")]
+pub use events::*;
+
+#[cfg(feature = "flecs_pipeline")]
+pub mod state;
+
+#[cfg(feature = "flecs_pipeline")]
+pub use state::*;
+
+pub mod profiler;
+pub use profiler::*;
+
 pub mod experimental;
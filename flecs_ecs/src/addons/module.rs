@@ -1,21 +1,83 @@
+use std::any::TypeId;
+
 use crate::core::{flecs, ComponentId, EntityView, World};
+use crate::sys;
 
+/// A module, which groups the components, systems and observers of a feature.
+///
+/// The module is a component that is registered with the world when it is imported with
+/// [`World::import`]. Entities created in [`Module::module`] are created in the scope of the module.
+///
+/// The path of the module is the name of the type, unless it is set with the
+/// `#[flecs(module = "...")]` attribute of the component derive, such as
+/// `#[flecs(module = "game.physics")]`. The modules a module depends on are set with the
+/// `#[flecs(import(...))]` attribute, and are imported in order before the module.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Velocity(f32);
+///
+/// #[derive(Component)]
+/// #[flecs(module = "game.transform")]
+/// struct TransformModule;
+///
+/// impl Module for TransformModule {
+///     fn module(_world: &World) {}
+/// }
+///
+/// #[derive(Component)]
+/// #[flecs(module = "game.physics", import(TransformModule))]
+/// struct PhysicsModule;
+///
+/// impl Module for PhysicsModule {
+///     fn module(world: &World) {
+///         world.system_named::<&Velocity>("Move").each(|_velocity| {});
+///     }
+/// }
+///
+/// let world = World::new();
+///
+/// world.import::<PhysicsModule>();
+/// assert!(world.try_lookup("game::transform").is_some());
+/// assert!(world.try_lookup("game::physics::Move").is_some());
+///
+/// assert!(world.unload::<PhysicsModule>());
+/// assert!(world.try_lookup("game::physics::Move").is_none());
+/// ```
 pub trait Module: ComponentId {
     fn module(world: &World);
 }
 
 impl World {
+    /// Import a module, after the modules it depends on.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The module to import.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the module depends on itself, directly or through the modules it depends on.
     pub fn import<T: Module>(&self) -> EntityView {
         // If we have already registered this type don't re-create the module
         if T::is_registered_with_world(self) {
             return self.component::<T>().entity;
         }
 
+        self.import_module_deps::<T>();
+
         // Reset scope
         let prev_scope = self.set_scope_id(0);
 
         // Initialise component for the module and add Module tag
-        let module = self.component::<T>();
+        let module = match T::__module_path() {
+            Some(path) => self.component_named::<T>(&path.replace('.', "::")),
+            None => self.component::<T>(),
+        };
         module.add::<flecs::EcsModule>();
 
         // Set scope to our module
@@ -29,4 +91,59 @@ impl World {
 
         module.entity
     }
+
+    /// Unload a module, deleting the module and the entities in its scope, such as its systems and
+    /// observers.
+    ///
+    /// Components that were registered by the module are not deleted. Modules that depend on the
+    /// module are not unloaded. The module can be imported again afterwards, such as after
+    /// reloading the plugin that contains it.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The module to unload.
+    ///
+    /// # Returns
+    ///
+    /// Whether the module was imported.
+    pub fn unload<T: Module>(&self) -> bool {
+        if !T::is_registered_with_world(self) {
+            return false;
+        }
+
+        self.component::<T>().entity.destruct();
+        self.forget_deleted_components();
+        true
+    }
+
+    fn import_module_deps<T: Module>(&self) {
+        let module = (TypeId::of::<T>(), std::any::type_name::<T>());
+        let importing = &mut self.world_ctx_mut().importing_modules;
+        if let Some(index) = importing.iter().position(|(id, _)| *id == module.0) {
+            let cycle: Vec<&str> = importing[index..]
+                .iter()
+                .chain([&module])
+                .map(|(_, name)| *name)
+                .collect();
+            panic!("cyclic module dependency: {}", cycle.join(" -> "));
+        }
+        importing.push(module);
+
+        T::__import_module_deps(self);
+
+        self.world_ctx_mut().importing_modules.pop();
+    }
+
+    // the registered ids of deleted components are cleared, so they are registered again when used
+    fn forget_deleted_components(&self) {
+        let world = self.ptr_mut();
+        let is_alive = |id: u64| unsafe { sys::ecs_is_alive(world, id) };
+
+        for id in self.components_array().iter_mut() {
+            if *id != 0 && !is_alive(*id) {
+                *id = 0;
+            }
+        }
+        self.components_map().retain(|_, id| is_alive(*id));
+    }
 }
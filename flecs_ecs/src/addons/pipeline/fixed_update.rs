@@ -0,0 +1,136 @@
+//! Systems that run at a fixed rate, independent of the frame rate.
+//!
+//! Systems in the [`FixedUpdate`] phase are not run by the pipeline. Once a timestep is set with
+//! [`World::set_fixed_timestep`], a system in the [`flecs::pipeline::PreUpdate`] phase adds the
+//! time of each frame to an accumulator, and runs the [`FixedUpdate`] systems once for each
+//! timestep that fits in it. The time that is left is exposed as interpolation alpha by the
+//! [`FixedTime`] singleton, so rendering can interpolate between the last two steps.
+
+use flecs_ecs_derive::Component;
+
+use super::run_phase;
+use crate::core::*;
+
+/// Phase of the systems that run at a fixed rate, see [`World::set_fixed_timestep`].
+///
+/// Systems are added to the phase with `kind::<FixedUpdate>()`, and are passed the timestep as
+/// delta time.
+#[derive(Component)]
+pub struct FixedUpdate;
+
+/// The fixed timestep and the state of its accumulator, stored as a world singleton.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct FixedTime {
+    /// The duration of a step, in seconds.
+    pub timestep: FTime,
+    /// The maximum number of steps in a frame. When a frame takes longer than this many steps,
+    /// the time that is left is dropped, so the simulation slows down rather than falling further
+    /// behind.
+    pub max_steps: u32,
+    accumulator: FTime,
+    steps: u32,
+}
+
+impl FixedTime {
+    /// The default of [`FixedTime::max_steps`].
+    pub const DEFAULT_MAX_STEPS: u32 = 5;
+
+    /// Create a fixed timestep that runs at `hz` steps per second.
+    pub fn from_hz(hz: FTime) -> Self {
+        Self {
+            timestep: 1.0 / hz,
+            max_steps: Self::DEFAULT_MAX_STEPS,
+            accumulator: 0.0,
+            steps: 0,
+        }
+    }
+
+    /// Returns how far the accumulated time is into the next step, between 0 and 1.
+    ///
+    /// Rendering interpolates between the state of the last two steps with this alpha.
+    pub fn alpha(&self) -> FTime {
+        self.accumulator / self.timestep
+    }
+
+    /// Returns the accumulated time that didn't fit in a step.
+    pub fn accumulator(&self) -> FTime {
+        self.accumulator
+    }
+
+    /// Returns the number of steps that ran this frame.
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// Add the time of a frame, and return the number of steps to run.
+    pub fn accumulate(&mut self, delta_time: FTime) -> u32 {
+        self.accumulator += delta_time;
+        let mut steps = (self.accumulator / self.timestep) as u32;
+        if steps > self.max_steps {
+            steps = self.max_steps;
+            self.accumulator %= self.timestep;
+        } else {
+            self.accumulator -= steps as FTime * self.timestep;
+        }
+        self.steps = steps;
+        steps
+    }
+}
+
+/// Fixed timestep mixin implementation
+impl World {
+    /// Run the systems in the [`FixedUpdate`] phase `hz` times per second.
+    ///
+    /// The systems run in the [`flecs::pipeline::PreUpdate`] phase, zero or more times per frame,
+    /// with the timestep as delta time. Setting the timestep again changes the rate and keeps the
+    /// accumulated time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component, Default)]
+    /// struct Ticks(u32);
+    ///
+    /// let world = World::new();
+    /// world.set(Ticks::default());
+    /// world.set_fixed_timestep(10.0);
+    ///
+    /// world
+    ///     .system::<&mut Ticks>()
+    ///     .term_at(0)
+    ///     .singleton()
+    ///     .kind::<FixedUpdate>()
+    ///     .each(|ticks| ticks.0 += 1);
+    ///
+    /// world.progress_time(0.25);
+    ///
+    /// world.get::<&Ticks>(|ticks| assert_eq!(ticks.0, 2));
+    /// world.get::<&FixedTime>(|time| assert_eq!(time.steps(), 2));
+    /// ```
+    pub fn set_fixed_timestep(&self, hz: FTime) {
+        if self.has::<FixedTime>() {
+            self.get::<&mut FixedTime>(|time| time.timestep = 1.0 / hz);
+            return;
+        }
+
+        self.set(FixedTime::from_hz(hz));
+        self.system::<()>()
+            .kind::<flecs::pipeline::PreUpdate>()
+            .immediate(true)
+            .run(|mut it| {
+                while it.next_iter() {}
+                let world = it.world();
+                let delta_time = it.delta_time();
+                let (steps, timestep) = world
+                    .map::<&mut FixedTime, _>(|time| (time.accumulate(delta_time), time.timestep));
+                // the changes of a step are applied before the next step runs
+                world.defer_suspend();
+                for _ in 0..steps {
+                    run_phase(&world, FixedUpdate::id(world), timestep);
+                }
+                world.defer_resume();
+            });
+    }
+}
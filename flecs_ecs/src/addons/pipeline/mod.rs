@@ -1,7 +1,15 @@
 //! Pipelines order and schedule systems for execution.
 
+mod fixed_update;
+pub use fixed_update::*;
 mod pipeline_builder;
 pub use pipeline_builder::*;
+mod schedule;
+pub use schedule::*;
+mod system_order;
+pub(crate) use system_order::SystemConstraints;
+pub(crate) use system_order::SystemOrder;
+pub use system_order::SystemOrderError;
 
 use std::ops::{Deref, DerefMut};
 
@@ -81,4 +89,106 @@ where
     pub fn entity(&self) -> EntityView<'a> {
         self.entity
     }
+
+    /// Returns the schedule of the pipeline: the systems in the order they run, and the merges in
+    /// between.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Position { x: 0.0 });
+    ///
+    /// let pipeline = world
+    ///     .pipeline()
+    ///     .with::<flecs::system::System>()
+    ///     .build();
+    /// world.system_named::<&mut Position>("Move").each(|pos| pos.x += 1.0);
+    ///
+    /// let schedule = pipeline.schedule();
+    /// assert_eq!(schedule.systems().count(), 1);
+    /// assert_eq!(schedule.sync_points(), 1);
+    /// ```
+    pub fn schedule(&self) -> PipelineSchedule {
+        PipelineSchedule::new(self.entity.world(), *self.entity.id())
+    }
+}
+
+/// Pipeline schedule mixin implementation
+impl World {
+    /// Returns the schedule of the current pipeline, see [`Pipeline::schedule`].
+    pub fn schedule(&self) -> PipelineSchedule {
+        PipelineSchedule::new(self.world(), unsafe {
+            sys::ecs_get_pipeline(self.world_ptr())
+        })
+    }
+}
+
+/// Returns the cached query that matches the enabled systems of a phase.
+///
+/// The query is created the first time the phase is run, as a child of the phase so it's deleted
+/// with the phase.
+fn phase_query(world: &World, phase: EntityT) -> *mut QueryT {
+    let world_ptr = world.world_ptr_mut();
+    let cached = world.world_ctx().phase_queries.get(&phase).copied();
+    if let Some(query) = cached.and_then(|entity| query_ptr_from_entity(world_ptr, entity)) {
+        return query.as_ptr();
+    }
+
+    let mut desc = sys::ecs_query_desc_t {
+        entity: unsafe {
+            sys::ecs_entity_init(
+                world_ptr,
+                &sys::ecs_entity_desc_t {
+                    parent: phase,
+                    ..Default::default()
+                },
+            )
+        },
+        cache_kind: sys::ecs_query_cache_kind_t_EcsQueryCacheAll,
+        ..Default::default()
+    };
+    desc.terms[0].id = ECS_SYSTEM;
+    desc.terms[1].id = ecs_dependson(phase);
+    desc.terms[2].id = ECS_DISABLED;
+    desc.terms[2].oper = OperKind::Not as i16;
+    let query = unsafe { sys::ecs_query_init(world_ptr, &desc) };
+    world
+        .world_ctx_mut()
+        .phase_queries
+        .insert(phase, desc.entity);
+    query
+}
+
+/// Run the enabled systems of a phase that is not part of the pipeline, in the order the pipeline
+/// would run them.
+pub(crate) fn run_phase(world: &World, phase: impl Into<Entity>, delta_time: FTime) {
+    let query = phase_query(world, *phase.into());
+    let mut systems = Vec::new();
+    unsafe {
+        let mut it = sys::ecs_query_iter(world.world_ptr(), query);
+        while sys::ecs_query_next(&mut it) {
+            systems.extend_from_slice(std::slice::from_raw_parts(it.entities, it.count as usize));
+        }
+    }
+
+    let order = &world.world_ctx().system_order;
+    systems.sort_by_key(|&system| order.key(system));
+    for system in systems {
+        unsafe {
+            sys::ecs_run(
+                world.world_ptr_mut(),
+                system,
+                delta_time,
+                std::ptr::null_mut(),
+            );
+        }
+    }
 }
@@ -0,0 +1,367 @@
+//! The schedule of a pipeline: the systems in the order they run, and the merges in between.
+//!
+//! Flecs builds the schedule of a pipeline when it runs, and doesn't expose it. The schedule is
+//! computed here the same way: systems run in the order of the pipeline query, and commands are
+//! merged when the next system has a different threading or staging mode, is immediate, or reads
+//! a component that an earlier system wrote to the stage since the last merge.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use super::system_order::system_path;
+use crate::core::*;
+use crate::sys;
+
+/// A system in the schedule of a pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledSystem {
+    /// The system.
+    pub id: Entity,
+    /// The path of the system, or `#id` for a system without name.
+    pub path: String,
+    /// The phase of the system, or 0 for a system without phase.
+    pub phase: Entity,
+    /// The path of the phase, empty for a system without phase.
+    pub phase_path: String,
+    /// Whether the system runs on multiple threads.
+    pub multi_threaded: bool,
+    /// Whether the system writes to the world directly, instead of to the stage.
+    pub immediate: bool,
+}
+
+/// Systems that run without merges in between, after which the commands are merged unless the
+/// systems are immediate.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScheduleStage {
+    /// The systems, in the order they run.
+    pub systems: Vec<ScheduledSystem>,
+    /// Whether the systems run on multiple threads.
+    pub multi_threaded: bool,
+    /// Whether the systems write to the world directly, instead of to the stage.
+    pub immediate: bool,
+}
+
+/// The schedule of a pipeline, see [`Pipeline::schedule`].
+///
+/// Only systems that match entities are scheduled, like flecs does, so the schedule changes as
+/// systems start or stop matching.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PipelineSchedule {
+    /// The path of the pipeline.
+    pub pipeline: String,
+    /// The stages, each stage that isn't immediate is followed by a merge.
+    pub stages: Vec<ScheduleStage>,
+    /// The ordering constraints between the scheduled systems, each system runs before the other.
+    pub constraints: Vec<(Entity, Entity)>,
+}
+
+impl PipelineSchedule {
+    /// Compute the schedule of the pipeline.
+    pub(crate) fn new(world: WorldRef, pipeline: EntityT) -> Self {
+        let world_ptr = world.world_ptr_mut();
+        let mut systems = Vec::new();
+        if let Some(query) = Query::<()>::new_from_entity(world, pipeline) {
+            query.each_entity(|e, _| systems.push(*e.id()));
+        }
+
+        let mut write_state = WriteState::default();
+        let mut stages: Vec<ScheduleStage> = Vec::new();
+        let mut open = false;
+        let mut multi_threaded = false;
+        let mut immediate = false;
+        let mut first = true;
+
+        for &system in &systems {
+            let system_ptr = unsafe { sys::ecs_system_get(world_ptr, system) };
+            let Some(system_t) = (unsafe { system_ptr.as_ref() }) else {
+                continue;
+            };
+            let query = unsafe { &*system_t.query };
+            let terms = &query.terms[..query.term_count as usize];
+            let is_active = !unsafe { sys::ecs_has_id(world_ptr, system, ECS_EMPTY) };
+
+            let mut needs_merge = write_state.check_terms(terms, is_active);
+            if is_active {
+                if first {
+                    multi_threaded = system_t.multi_threaded;
+                    immediate = system_t.immediate;
+                    first = false;
+                }
+                if system_t.multi_threaded != multi_threaded {
+                    needs_merge = true;
+                    multi_threaded = system_t.multi_threaded;
+                }
+                if system_t.immediate != immediate {
+                    needs_merge = true;
+                    immediate = system_t.immediate;
+                }
+            }
+            if immediate {
+                needs_merge = true;
+            }
+
+            if needs_merge {
+                // all components are merged, an inactive system that triggered the merge doesn't
+                // start a stage of its own
+                write_state = WriteState::default();
+                if stages.last().is_some_and(|stage| !stage.systems.is_empty()) {
+                    open = false;
+                }
+                if is_active {
+                    write_state.check_terms(terms, true);
+                }
+            }
+
+            if !open {
+                stages.push(ScheduleStage::default());
+                open = true;
+            }
+
+            if is_active {
+                let stage = stages.last_mut().unwrap();
+                if stage.systems.is_empty() {
+                    stage.multi_threaded = multi_threaded;
+                    stage.immediate = immediate;
+                }
+                let phase = unsafe { sys::ecs_get_target(world_ptr, system, ECS_DEPENDS_ON, 0) };
+                stage.systems.push(ScheduledSystem {
+                    id: Entity(system),
+                    path: system_path(world, system),
+                    phase: Entity(phase),
+                    phase_path: if phase == 0 {
+                        String::new()
+                    } else {
+                        system_path(world, phase)
+                    },
+                    multi_threaded: system_t.multi_threaded,
+                    immediate: system_t.immediate,
+                });
+            }
+        }
+
+        if stages.len() > 1 && stages.last().is_some_and(|stage| stage.systems.is_empty()) {
+            stages.pop();
+        }
+
+        let mut schedule = Self {
+            pipeline: system_path(world, pipeline),
+            stages,
+            constraints: Vec::new(),
+        };
+        schedule.constraints = world
+            .world_ctx()
+            .system_order
+            .edges(world)
+            .into_iter()
+            .filter(|&(from, to)| schedule.contains(from) && schedule.contains(to))
+            .map(|(from, to)| (Entity(from), Entity(to)))
+            .collect();
+        schedule
+    }
+
+    /// Returns the systems, in the order they run.
+    pub fn systems(&self) -> impl Iterator<Item = &ScheduledSystem> {
+        self.stages.iter().flat_map(|stage| stage.systems.iter())
+    }
+
+    /// Returns the phases of the systems, in the order they run.
+    pub fn phases(&self) -> Vec<Entity> {
+        let mut phases: Vec<Entity> = Vec::new();
+        for system in self.systems() {
+            if system.phase != 0 && !phases.contains(&system.phase) {
+                phases.push(system.phase);
+            }
+        }
+        phases
+    }
+
+    /// Returns the number of merges in a frame, including the merge at the end of the frame.
+    ///
+    /// Immediate systems write to the world directly, so there is no merge after their stage.
+    pub fn sync_points(&self) -> usize {
+        self.stages.iter().filter(|stage| !stage.immediate).count()
+    }
+
+    /// Returns whether the system is scheduled.
+    pub fn contains(&self, system: impl Into<Entity>) -> bool {
+        let system = system.into();
+        self.systems().any(|scheduled| scheduled.id == system)
+    }
+
+    /// Export the schedule as a Graphviz graph.
+    ///
+    /// Systems are grouped by phase and linked in the order they run, with a node for each
+    /// merge. Ordering constraints are drawn as dashed edges.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        let _ = writeln!(dot, "digraph {} {{", quote(&self.pipeline));
+        let _ = writeln!(dot, "    node [shape=box];");
+
+        let mut clusters: Vec<(&str, Vec<&ScheduledSystem>)> = Vec::new();
+        for system in self.systems() {
+            match clusters
+                .iter_mut()
+                .find(|(phase, _)| *phase == system.phase_path)
+            {
+                Some((_, systems)) => systems.push(system),
+                None => clusters.push((&system.phase_path, vec![system])),
+            }
+        }
+        for (index, (phase, systems)) in clusters.iter().enumerate() {
+            if phase.is_empty() {
+                for system in systems {
+                    let _ = writeln!(dot, "    {};", quote(&system.path));
+                }
+                continue;
+            }
+            let _ = writeln!(dot, "    subgraph cluster_{index} {{");
+            let _ = writeln!(dot, "        label={};", quote(phase));
+            for system in systems {
+                let _ = writeln!(dot, "        {};", quote(&system.path));
+            }
+            let _ = writeln!(dot, "    }}");
+        }
+
+        let mut prev: Option<String> = None;
+        let mut merges = 0;
+        for stage in &self.stages {
+            let merge = (!stage.immediate).then(|| format!("merge {merges}"));
+            if let Some(merge) = &merge {
+                let _ = writeln!(dot, "    {} [shape=point, xlabel=\"merge\"];", quote(merge));
+                merges += 1;
+            }
+            for node in stage
+                .systems
+                .iter()
+                .map(|system| system.path.clone())
+                .chain(merge)
+            {
+                if let Some(prev) = prev {
+                    let _ = writeln!(dot, "    {} -> {};", quote(&prev), quote(&node));
+                }
+                prev = Some(node);
+            }
+        }
+
+        for (from, to) in &self.constraints {
+            let path = |id: &Entity| {
+                self.systems()
+                    .find(|system| system.id == *id)
+                    .map(|system| system.path.as_str())
+                    .unwrap_or_default()
+            };
+            let _ = writeln!(
+                dot,
+                "    {} -> {} [style=dashed];",
+                quote(path(from)),
+                quote(path(to))
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// The components written to the stage since the last merge.
+#[derive(Default)]
+struct WriteState {
+    ids: HashSet<IdT>,
+    wildcard_ids: HashSet<IdT>,
+    write_barrier: bool,
+}
+
+impl WriteState {
+    fn is_written(&self, id: IdT) -> bool {
+        if self.write_barrier {
+            return true;
+        }
+        if id == ECS_WILDCARD && !(self.ids.is_empty() && self.wildcard_ids.is_empty()) {
+            return true;
+        }
+        let written = if unsafe { sys::ecs_id_is_wildcard(id) } {
+            self.ids
+                .iter()
+                .any(|&written| unsafe { sys::ecs_id_match(written, id) })
+        } else {
+            self.ids.contains(&id)
+        };
+        written
+            || self
+                .wildcard_ids
+                .iter()
+                .any(|&written| unsafe { sys::ecs_id_match(id, written) })
+    }
+
+    fn write(&mut self, id: IdT) {
+        if id == ECS_WILDCARD {
+            self.write_barrier = true;
+        } else if unsafe { sys::ecs_id_is_wildcard(id) } {
+            self.wildcard_ids.insert(id);
+        } else {
+            self.ids.insert(id);
+        }
+    }
+
+    /// Returns whether the terms read a component that must be merged first.
+    fn check_terms(&mut self, terms: &[sys::ecs_term_t], is_active: bool) -> bool {
+        // terms matched on `$this` first, so a term that writes to the stage before them doesn't
+        // cause a merge
+        let (this, other): (Vec<_>, Vec<_>) = terms
+            .iter()
+            .partition(|term| unsafe { sys::ecs_term_match_this(*term) });
+        this.into_iter()
+            .chain(other)
+            .fold(false, |needs_merge, term| {
+                self.check_term(term, is_active) | needs_merge
+            })
+    }
+
+    fn check_term(&mut self, term: &sys::ecs_term_t, is_active: bool) -> bool {
+        let mut inout = InOutKind::from(term.inout as sys::ecs_inout_kind_t);
+        if matches!(inout, InOutKind::None | InOutKind::Filter) {
+            return false;
+        }
+
+        let mut from_any = unsafe { sys::ecs_term_match_0(term) };
+        let from_this = unsafe { sys::ecs_term_match_this(term) };
+        let is_shared = !from_any && (!from_this || term.src.id & ECS_SELF == 0);
+        let written = self.is_written(term.id);
+
+        if from_this && written {
+            // a later write to the main storage must not be overwritten by the staged write
+            return true;
+        }
+
+        if inout == InOutKind::Default {
+            if from_any {
+                // an id passed to the system, not a component that is read or written
+                return false;
+            }
+            inout = if is_shared {
+                InOutKind::In
+            } else {
+                InOutKind::InOut
+            };
+        }
+
+        // a `Not` term that is written to adds the component
+        if term.oper == OperKind::Not as i16 && inout == InOutKind::Out {
+            from_any = true;
+        }
+
+        if from_any {
+            if is_active && matches!(inout, InOutKind::Out | InOutKind::InOut) {
+                self.write(term.id);
+            }
+            // a component that is fetched from the main storage must be merged first
+            if written && matches!(inout, InOutKind::In | InOutKind::InOut) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,300 @@
+//! Ordering of systems within a phase with `before` and `after` constraints.
+//!
+//! Flecs runs the systems of a phase in the order they were created. Once a system is created with
+//! an ordering constraint, the builtin pipeline is replaced by a copy that sorts systems on a key
+//! computed here: the ordered systems are sorted topologically, and take the places of each other
+//! in the creation order, so systems without constraints keep their place.
+//!
+//! Custom pipelines are not replaced, they run systems in their own order and a warning is logged
+//! when they are used together with ordering constraints.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+use std::os::raw::{c_int, c_void};
+
+use crate::core::*;
+use crate::sys;
+
+/// Error for ordering constraints between systems that form a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemOrderError {
+    /// The paths of the systems in the cycle, each system runs before the next one, and the last
+    /// before the first.
+    pub cycle: Vec<String>,
+}
+
+impl Display for SystemOrderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "cycle detected in system order: {} -> {}",
+            self.cycle.join(" -> "),
+            self.cycle.first().map(String::as_str).unwrap_or_default()
+        )
+    }
+}
+
+impl std::error::Error for SystemOrderError {}
+
+/// The ordering constraints of a system, set with the `SystemBuilder`.
+#[derive(Default, Clone)]
+pub(crate) struct SystemConstraints {
+    pub(crate) sets: Vec<EntityT>,
+    pub(crate) before: Vec<EntityT>,
+    pub(crate) after: Vec<EntityT>,
+}
+
+impl SystemConstraints {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.sets.is_empty() && self.before.is_empty() && self.after.is_empty()
+    }
+}
+
+/// Systems of a world that are ordered with constraints or are in a set.
+#[derive(Default)]
+pub(crate) struct SystemOrder {
+    systems: Vec<(EntityT, SystemConstraints)>,
+    keys: HashMap<EntityT, EntityT, fxhash::FxBuildHasher>,
+    pipeline: EntityT,
+}
+
+impl SystemOrder {
+    /// Register the constraints of a system, before the system is created.
+    ///
+    /// The constraints are not registered if they form a cycle with the constraints of other
+    /// systems.
+    pub(crate) fn insert(
+        &mut self,
+        world: WorldRef,
+        system: EntityT,
+        constraints: SystemConstraints,
+    ) -> Result<(), SystemOrderError> {
+        let world_ptr = world.world_ptr_mut();
+        self.systems
+            .retain(|(entity, _)| unsafe { sys::ecs_is_alive(world_ptr, *entity) });
+        self.systems.push((system, constraints));
+
+        let keys = match self.compute_keys(world) {
+            Ok(keys) => keys,
+            Err(err) => {
+                self.systems.pop();
+                return Err(err);
+            }
+        };
+
+        // sorting is only redone for tables with changes
+        let poly = ecs_pair(ECS_POLY, ECS_SYSTEM);
+        for (&entity, key) in &keys {
+            if self.keys.get(&entity) != Some(key)
+                && unsafe { sys::ecs_has_id(world_ptr, entity, poly) }
+            {
+                unsafe { sys::ecs_modified_id(world_ptr, entity, poly) };
+            }
+        }
+        self.keys = keys;
+        Ok(())
+    }
+
+    /// Returns the key the system is sorted on in its phase.
+    pub(crate) fn key(&self, system: EntityT) -> EntityT {
+        self.keys.get(&system).copied().unwrap_or(system)
+    }
+
+    /// Returns the edges between systems, each system runs before the other.
+    pub(crate) fn edges(&self, world: WorldRef) -> Vec<(EntityT, EntityT)> {
+        let world_ptr = world.world_ptr_mut();
+        let is_system = |entity: EntityT| {
+            self.systems.iter().any(|(system, _)| *system == entity)
+                || unsafe {
+                    sys::ecs_is_alive(world_ptr, entity)
+                        && sys::ecs_has_id(world_ptr, entity, ECS_SYSTEM)
+                }
+        };
+        // a target is a system, a set, or both
+        let expand = |target: EntityT| {
+            let mut systems: Vec<EntityT> = self
+                .systems
+                .iter()
+                .filter(|(_, constraints)| constraints.sets.contains(&target))
+                .map(|(system, _)| *system)
+                .collect();
+            if is_system(target) {
+                systems.push(target);
+            }
+            systems
+        };
+
+        let mut edges: Vec<(EntityT, EntityT)> = Vec::new();
+        for (system, constraints) in &self.systems {
+            for &target in &constraints.before {
+                for other in expand(target) {
+                    edges.push((*system, other));
+                }
+            }
+            for &target in &constraints.after {
+                for other in expand(target) {
+                    edges.push((other, *system));
+                }
+            }
+        }
+        edges.retain(|(from, to)| from != to);
+        edges
+    }
+
+    fn compute_keys(
+        &self,
+        world: WorldRef,
+    ) -> Result<HashMap<EntityT, EntityT, fxhash::FxBuildHasher>, SystemOrderError> {
+        let edges = self.edges(world);
+        let mut nodes: BTreeSet<EntityT> = self.systems.iter().map(|(system, _)| *system).collect();
+        for &(from, to) in &edges {
+            nodes.insert(from);
+            nodes.insert(to);
+        }
+
+        // sorted topologically, with the earliest created system first when there is a choice
+        let mut incoming: HashMap<EntityT, usize> = nodes.iter().map(|&node| (node, 0)).collect();
+        for (_, to) in &edges {
+            *incoming.get_mut(to).unwrap() += 1;
+        }
+        let mut ready: BTreeSet<EntityT> = incoming
+            .iter()
+            .filter(|(_, &count)| count == 0)
+            .map(|(&node, _)| node)
+            .collect();
+        let mut order = Vec::with_capacity(nodes.len());
+        while let Some(node) = ready.pop_first() {
+            order.push(node);
+            for (_, to) in edges.iter().filter(|(from, _)| *from == node) {
+                let count = incoming.get_mut(to).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    ready.insert(*to);
+                }
+            }
+        }
+
+        if order.len() != nodes.len() {
+            let remaining: Vec<EntityT> = nodes
+                .iter()
+                .copied()
+                .filter(|node| !order.contains(node))
+                .collect();
+            return Err(SystemOrderError {
+                cycle: find_cycle(&remaining, &edges)
+                    .into_iter()
+                    .map(|system| system_path(world, system))
+                    .collect(),
+            });
+        }
+
+        Ok(nodes
+            .into_iter()
+            .zip(order)
+            .map(|(key, node)| (node, key))
+            .collect())
+    }
+
+    /// Replace the builtin pipeline with a copy that sorts systems on their key, or warn that the
+    /// constraints are ignored when a custom pipeline is used.
+    ///
+    /// This is not a method, as the pipeline query sorts the systems when it is created.
+    pub(crate) fn use_ordered_pipeline(world: WorldRef) {
+        let world_ptr = world.world_ptr_mut();
+        let current = unsafe { sys::ecs_get_pipeline(world_ptr) };
+        let pipeline = world.world_ctx().system_order.pipeline;
+        if pipeline != 0 && unsafe { sys::ecs_is_alive(world_ptr, pipeline) } {
+            Self::warn_if_unordered(world, current);
+            return;
+        }
+        let builtin = world.try_lookup("flecs::pipeline::BuiltinPipeline");
+        if builtin.map(|builtin| *builtin.id) != Some(current) {
+            Self::warn_if_unordered(world, current);
+            return;
+        }
+        let Some(query) = query_ptr_from_entity(world_ptr, current) else {
+            return;
+        };
+
+        // the terms of the builtin pipeline, followed by the term the systems are sorted on
+        let mut desc = sys::ecs_pipeline_desc_t::default();
+        let query = unsafe { query.as_ref() };
+        let count = query.term_count as usize;
+        let terms = &mut desc.query.terms;
+        terms[..count].copy_from_slice(&query.terms[..count]);
+        terms[count].id = ecs_pair(ECS_POLY, ECS_SYSTEM);
+        desc.query.order_by = terms[count].id;
+        desc.query.order_by_callback = Some(compare_systems);
+
+        let pipeline = unsafe { sys::ecs_pipeline_init(world_ptr, &desc) };
+        world.world_ctx_mut().system_order.pipeline = pipeline;
+        unsafe { sys::ecs_set_pipeline(world_ptr, pipeline) };
+    }
+
+    /// Warn that the ordering constraints of systems are ignored if the pipeline isn't the one
+    /// that sorts systems on their key.
+    pub(crate) fn warn_if_unordered(world: WorldRef, pipeline: EntityT) {
+        let order = &world.world_ctx().system_order;
+        if order.systems.is_empty() || order.pipeline == pipeline {
+            return;
+        }
+        log_warning(&format!(
+            "pipeline {} doesn't order systems with `before`, `after` and `in_set`, \
+             their constraints are ignored",
+            system_path(world, pipeline)
+        ));
+    }
+}
+
+/// Returns a cycle in the edges between the nodes, which all are part of a cycle or come after one.
+fn find_cycle(nodes: &[EntityT], edges: &[(EntityT, EntityT)]) -> Vec<EntityT> {
+    // following edges back from any node ends up in a cycle
+    let mut path: Vec<EntityT> = vec![nodes[0]];
+    loop {
+        let node = *path.last().unwrap();
+        let prev = edges
+            .iter()
+            .find(|(from, to)| *to == node && nodes.contains(from))
+            .map(|(from, _)| *from)
+            .unwrap();
+        if let Some(start) = path.iter().position(|&n| n == prev) {
+            let mut cycle = path.split_off(start);
+            cycle.reverse();
+            // start with the earliest created system
+            let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+            cycle.rotate_left(first);
+            return cycle;
+        }
+        path.push(prev);
+    }
+}
+
+pub(super) fn system_path(world: WorldRef, system: EntityT) -> String {
+    let entity = EntityView::new_from(world, system);
+    match entity.get_name() {
+        Some(_) => entity.path().unwrap_or_default(),
+        None => format!("#{system}"),
+    }
+}
+
+/// Sort systems on their key, like flecs sorts them on their id.
+unsafe extern "C" fn compare_systems(
+    e1: EntityT,
+    ptr1: *const c_void,
+    e2: EntityT,
+    _ptr2: *const c_void,
+) -> c_int {
+    let poly = unsafe { &*(ptr1 as *const sys::EcsPoly) };
+    let (k1, k2) = if poly.poly.is_null() {
+        (e1, e2)
+    } else {
+        let world = unsafe {
+            WorldRef::from_ptr(sys::ecs_get_world(
+                (*(poly.poly as *const sys::ecs_system_t)).world as *const c_void,
+            ) as *mut WorldT)
+        };
+        let order = &world.world_ctx().system_order;
+        (order.key(e1), order.key(e2))
+    };
+    (k1 > k2) as c_int - (k1 < k2) as c_int
+}
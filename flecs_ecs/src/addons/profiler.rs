@@ -0,0 +1,361 @@
+//! Timing of system runs and observer invocations.
+//!
+//! The profiler is enabled with [`World::enable_profiler`]. Systems and observers that are created
+//! while the profiler is enabled record when each run starts and how long it takes in a ring
+//! buffer. The recorded runs are exported in the Chrome trace event format with
+//! [`World::chrome_trace`], which is loaded with `chrome://tracing` or Perfetto.
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::core::*;
+use crate::sys;
+
+/// What was profiled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileKind {
+    /// A run of a system.
+    System,
+    /// An invocation of an observer.
+    Observer,
+}
+
+/// A recorded system run or observer invocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileEvent {
+    /// The system or observer.
+    pub entity: Entity,
+    pub kind: ProfileKind,
+    /// The stage the run was on, which is 0 on the main thread and the index of the worker
+    /// thread for multi threaded systems.
+    pub thread: i32,
+    /// When the run started, relative to when the profiler was enabled.
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+/// Records system runs and observer invocations in a ring buffer, see [`World::enable_profiler`].
+pub struct Profiler {
+    enabled: AtomicBool,
+    epoch: Instant,
+    events: Mutex<ProfileBuffer>,
+}
+
+struct ProfileBuffer {
+    events: VecDeque<ProfileEvent>,
+    capacity: usize,
+}
+
+impl Profiler {
+    fn new(capacity: usize) -> Self {
+        Self {
+            enabled: AtomicBool::new(true),
+            epoch: Instant::now(),
+            events: Mutex::new(ProfileBuffer {
+                events: VecDeque::with_capacity(capacity),
+                capacity,
+            }),
+        }
+    }
+
+    /// Returns whether runs are recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Returns the maximum number of recorded runs, older runs are dropped first.
+    pub fn capacity(&self) -> usize {
+        self.events.lock().unwrap().capacity
+    }
+
+    /// Returns the recorded runs, in the order they ended.
+    pub fn events(&self) -> Vec<ProfileEvent> {
+        self.events.lock().unwrap().events.iter().copied().collect()
+    }
+
+    /// Remove the recorded runs.
+    pub fn clear(&self) {
+        self.events.lock().unwrap().events.clear();
+    }
+
+    /// Run `func`, and record the run if the profiler is enabled.
+    fn profile(&self, it: &mut IterT, kind: ProfileKind, func: impl FnOnce(&mut IterT)) {
+        let (entity, stage) = (it.system, it.world);
+        if !self.is_enabled() {
+            func(it);
+            return;
+        }
+
+        let start = Instant::now();
+        func(it);
+        let duration = start.elapsed();
+
+        let event = ProfileEvent {
+            entity: Entity(entity),
+            kind,
+            thread: unsafe { sys::ecs_stage_get_id(stage) },
+            start: start - self.epoch,
+            duration,
+        };
+        let mut buffer = self.events.lock().unwrap();
+        if buffer.events.len() == buffer.capacity {
+            buffer.events.pop_front();
+        }
+        buffer.events.push_back(event);
+    }
+}
+
+/// Profiler mixin implementation
+impl World {
+    /// Record the runs of the systems and observers that are created from now on, keeping the
+    /// last `capacity` runs.
+    ///
+    /// Enabling the profiler again resumes recording and changes its capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.enable_profiler(1024);
+    ///
+    /// world.entity().set(Position { x: 0.0 });
+    /// world.system_named::<&mut Position>("Move").each(|pos| pos.x += 1.0);
+    /// world.progress();
+    ///
+    /// let events = world.profiler().unwrap().events();
+    /// assert_eq!(events.len(), 1);
+    /// assert!(world.chrome_trace().contains("\"name\":\"::Move\""));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn enable_profiler(&self, capacity: usize) {
+        assert!(capacity > 0, "profiler capacity must be at least 1");
+        let ctx = self.world_ctx_mut();
+        match &ctx.profiler {
+            Some(profiler) => {
+                let mut buffer = profiler.events.lock().unwrap();
+                buffer.capacity = capacity;
+                let len = buffer.events.len();
+                buffer.events.drain(..len.saturating_sub(capacity));
+                profiler.enabled.store(true, Ordering::Relaxed);
+            }
+            None => ctx.profiler = Some(Arc::new(Profiler::new(capacity))),
+        }
+    }
+
+    /// Stop recording runs, the recorded runs are kept.
+    pub fn disable_profiler(&self) {
+        if let Some(profiler) = &self.world_ctx().profiler {
+            profiler.enabled.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the profiler, if it was enabled.
+    pub fn profiler(&self) -> Option<&Profiler> {
+        self.world_ctx().profiler.as_deref()
+    }
+
+    /// Export the recorded runs in the Chrome trace event format.
+    ///
+    /// Runs are complete events named after the path of the system or observer, with the stage
+    /// as thread. Returns a trace without events if the profiler was never enabled.
+    pub fn chrome_trace(&self) -> String {
+        let events = self.profiler().map(Profiler::events).unwrap_or_default();
+
+        let mut threads: Vec<i32> = events.iter().map(|event| event.thread).collect();
+        threads.sort_unstable();
+        threads.dedup();
+
+        let mut trace = String::from("{\"traceEvents\":[");
+        let mut first = true;
+        let mut separator = |trace: &mut String| {
+            if !first {
+                trace.push(',');
+            }
+            first = false;
+        };
+        for thread in threads {
+            separator(&mut trace);
+            let name = if thread == 0 {
+                "main".to_string()
+            } else {
+                format!("worker {thread}")
+            };
+            let _ = write!(
+                trace,
+                "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":1,\"tid\":{thread},\"args\":{{\"name\":\"{name}\"}}}}"
+            );
+        }
+        for event in &events {
+            separator(&mut trace);
+            let entity = self.entity_from_id(event.entity);
+            let name = if entity.is_alive() && entity.get_name().is_some() {
+                entity.path().unwrap_or_default()
+            } else {
+                format!("#{}", event.entity)
+            };
+            let category = match event.kind {
+                ProfileKind::System => "system",
+                ProfileKind::Observer => "observer",
+            };
+            let _ = write!(
+                trace,
+                "{{\"name\":\"{}\",\"cat\":\"{category}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":{}}}",
+                escape_json(&name),
+                event.start.as_secs_f64() * 1e6,
+                event.duration.as_secs_f64() * 1e6,
+                event.thread
+            );
+        }
+        trace.push_str("],\"displayTimeUnit\":\"ms\"}");
+        trace
+    }
+}
+
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The run action of a profiled system or observer, and the profiler it records to.
+struct ProfiledRun {
+    profiler: Arc<Profiler>,
+    run: sys::ecs_run_action_t,
+    run_ctx: *mut c_void,
+    run_ctx_free: sys::ecs_ctx_free_t,
+}
+
+/// Profile the runs of the system, if the profiler is enabled.
+#[cfg(feature = "flecs_system")]
+pub(crate) fn profile_system(world: WorldRef, desc: &mut sys::ecs_system_desc_t) {
+    if let Some(profiler) = world.world_ctx().profiler.clone() {
+        (desc.run, desc.run_ctx, desc.run_ctx_free) = profile_run(
+            profiler,
+            (desc.run, desc.run_ctx, desc.run_ctx_free),
+            run_profiled_system,
+        );
+    }
+}
+
+type RunAction = (sys::ecs_run_action_t, *mut c_void, sys::ecs_ctx_free_t);
+
+fn profile_run(
+    profiler: Arc<Profiler>,
+    (run, run_ctx, run_ctx_free): RunAction,
+    profiled: unsafe extern "C" fn(*mut IterT),
+) -> RunAction {
+    let ctx = Box::new(ProfiledRun {
+        profiler,
+        run,
+        run_ctx,
+        run_ctx_free,
+    });
+    (
+        Some(profiled),
+        Box::into_raw(ctx) as *mut c_void,
+        Some(free_profiled_run),
+    )
+}
+
+#[cfg(feature = "flecs_system")]
+unsafe extern "C" fn run_profiled_system(it: *mut IterT) {
+    let it = unsafe { &mut *it };
+    let ctx = unsafe { &*(it.run_ctx as *const ProfiledRun) };
+    ctx.profiler.profile(it, ProfileKind::System, |it| unsafe {
+        crate::addons::system::run_system(it, ctx.run, ctx.run_ctx);
+    });
+}
+
+unsafe extern "C" fn run_profiled_observer(it: *mut IterT) {
+    let it = unsafe { &mut *it };
+    let ctx = unsafe { &*(it.run_ctx as *const ProfiledRun) };
+    let Some(run) = ctx.run else {
+        return;
+    };
+    it.run_ctx = ctx.run_ctx;
+    ctx.profiler
+        .profile(it, ProfileKind::Observer, |it| unsafe { run(it) });
+}
+
+unsafe extern "C" fn free_profiled_run(ctx: *mut c_void) {
+    let ctx = unsafe { Box::from_raw(ctx as *mut ProfiledRun) };
+    if let Some(free) = ctx.run_ctx_free {
+        unsafe { free(ctx.run_ctx) };
+    }
+}
+
+/// The callback of a profiled observer, and the profiler it records to.
+struct ProfiledCallback {
+    profiler: Arc<Profiler>,
+    callback: sys::ecs_iter_action_t,
+    callback_ctx: *mut c_void,
+    callback_ctx_free: sys::ecs_ctx_free_t,
+}
+
+/// Profile the invocations of the observer, if the profiler is enabled.
+///
+/// The callback is profiled rather than the run action when the observer has one, so ordered
+/// observers that are invoked by another observer are recorded on their own.
+pub(crate) fn profile_observer(world: WorldRef, desc: &mut sys::ecs_observer_desc_t) {
+    let Some(profiler) = world.world_ctx().profiler.clone() else {
+        return;
+    };
+    if desc.callback.is_none() {
+        (desc.run, desc.run_ctx, desc.run_ctx_free) = profile_run(
+            profiler,
+            (desc.run, desc.run_ctx, desc.run_ctx_free),
+            run_profiled_observer,
+        );
+        return;
+    }
+    let ctx = Box::new(ProfiledCallback {
+        profiler,
+        callback: desc.callback,
+        callback_ctx: desc.callback_ctx,
+        callback_ctx_free: desc.callback_ctx_free,
+    });
+    desc.callback = Some(invoke_profiled);
+    desc.callback_ctx = Box::into_raw(ctx) as *mut c_void;
+    desc.callback_ctx_free = Some(free_profiled_callback);
+}
+
+unsafe extern "C" fn invoke_profiled(it: *mut IterT) {
+    let it = unsafe { &mut *it };
+    let ctx = unsafe { &*(it.callback_ctx as *const ProfiledCallback) };
+    let Some(callback) = ctx.callback else {
+        return;
+    };
+    it.callback_ctx = ctx.callback_ctx;
+    ctx.profiler
+        .profile(it, ProfileKind::Observer, |it| unsafe { callback(it) });
+}
+
+unsafe extern "C" fn free_profiled_callback(ctx: *mut c_void) {
+    let ctx = unsafe { Box::from_raw(ctx as *mut ProfiledCallback) };
+    if let Some(free) = ctx.callback_ctx_free {
+        unsafe { free(ctx.callback_ctx) };
+    }
+}
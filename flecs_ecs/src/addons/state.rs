@@ -0,0 +1,305 @@
+//! States of the world modeled with an enum, with systems that run when a state is entered or exited.
+//!
+//! A state is registered with [`World::init_state`], which adds the [`State`] and [`NextState`]
+//! singletons, and a system in the [`flecs::pipeline::PreFrame`] phase that applies the queued
+//! transitions. For each transition, the systems in the [`World::on_exit`] phase of the old state
+//! run, then the [`StateTransition`] event is emitted, and then the systems in the
+//! [`World::on_enter`] phase of the new state run.
+//!
+//! The current state is also added to the enum singleton, so it can be checked with
+//! [`World::has_enum`] and used as run condition with [`SystemBuilder::in_state`].
+
+use std::collections::VecDeque;
+use std::ffi::CStr;
+
+use flecs_ecs_derive::Component;
+
+use crate::addons::pipeline::run_phase;
+use crate::addons::system::SystemBuilder;
+use crate::core::*;
+use crate::sys;
+
+/// An enum that is used as state, see [`World::init_state`].
+pub trait States: ComponentId + ComponentType<Enum> + CachedEnumData + Copy + PartialEq {}
+
+impl<S> States for S where S: ComponentId + ComponentType<Enum> + CachedEnumData + Copy + PartialEq {}
+
+/// The current state, stored as a world singleton.
+#[derive(Component)]
+pub struct State<S>
+where
+    S: States,
+{
+    current: S,
+    /// Whether the systems entering the current state ran.
+    entered: bool,
+}
+
+impl<S> State<S>
+where
+    S: States,
+{
+    /// Returns the current state.
+    pub fn get(&self) -> S {
+        self.current
+    }
+}
+
+/// The transitions to apply at the start of the next frame, stored as a world singleton.
+#[derive(Component)]
+pub struct NextState<S>
+where
+    S: States,
+{
+    queue: VecDeque<S>,
+}
+
+impl<S> NextState<S>
+where
+    S: States,
+{
+    /// Queue a transition to the state.
+    pub fn set(&mut self, state: S) {
+        self.queue.push_back(state);
+    }
+
+    /// Returns the queued transitions, in the order they are applied.
+    pub fn pending(&self) -> impl Iterator<Item = &S> {
+        self.queue.iter()
+    }
+}
+
+/// Event emitted on the enum singleton when the state changes.
+///
+/// The event is observed with [`World::on_transition`], or with an observer for the
+/// `(S, flecs::Wildcard)` pair.
+#[derive(Component)]
+pub struct StateTransition<S>
+where
+    S: States,
+{
+    pub from: S,
+    pub to: S,
+}
+
+impl<S> Event for StateTransition<S> where S: States {}
+
+/// State machine mixin implementation
+impl World {
+    /// Register the state `S`, starting in the `initial` state.
+    ///
+    /// The systems entering the initial state run at the start of the first frame. Registering
+    /// a state that is already registered does nothing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[repr(C)]
+    /// #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    /// enum GameState {
+    ///     Menu,
+    ///     Playing,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.init_state(GameState::Menu);
+    ///
+    /// world
+    ///     .system_named::<()>("SpawnLevel")
+    ///     .on_enter(GameState::Playing)
+    ///     .run(|_it| {});
+    ///
+    /// world
+    ///     .system_named::<()>("Update")
+    ///     .in_state(GameState::Playing)
+    ///     .run(|_it| {});
+    ///
+    /// world.set_next_state(GameState::Playing);
+    /// world.progress();
+    /// assert_eq!(world.state::<GameState>(), GameState::Playing);
+    /// ```
+    pub fn init_state<S>(&self, initial: S)
+    where
+        S: States,
+    {
+        if self.has::<State<S>>() {
+            return;
+        }
+
+        self.set(State {
+            current: initial,
+            entered: false,
+        });
+        self.set(NextState::<S> {
+            queue: VecDeque::new(),
+        });
+        self.add_enum(initial);
+        // registered up front, as transitions are applied by a system
+        self.component::<StateTransition<S>>();
+        for state in S::iter() {
+            self.on_enter(state);
+            self.on_exit(state);
+        }
+        self.system::<()>()
+            .kind::<flecs::pipeline::PreFrame>()
+            .immediate(true)
+            .run(|mut it| {
+                while it.next_iter() {}
+                // transitions are applied right away, so the systems of the frame see the state
+                let world = it.world();
+                world.defer_suspend();
+                apply_transitions::<S>(&world, it.delta_time());
+                world.defer_resume();
+            });
+    }
+
+    /// Returns the current state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the state is not registered with [`World::init_state`].
+    pub fn state<S>(&self) -> S
+    where
+        S: States,
+    {
+        self.map::<&State<S>, _>(|state| state.current)
+    }
+
+    /// Queue a transition to the state, which is applied at the start of the next frame.
+    ///
+    /// Transitions are applied in the order they were queued. A transition to the current state
+    /// does nothing.
+    pub fn set_next_state<S>(&self, state: S)
+    where
+        S: States,
+    {
+        self.get::<&mut NextState<S>>(|next| next.set(state));
+    }
+
+    /// Returns the phase of the systems that run when the state is entered.
+    ///
+    /// The phase is not part of the pipeline, its systems only run on transitions. Systems are
+    /// usually added to the phase with [`SystemBuilder::on_enter`].
+    pub fn on_enter<S>(&self, state: S) -> EntityView<'_>
+    where
+        S: States,
+    {
+        self.state_phase(c"OnEnter", state)
+    }
+
+    /// Returns the phase of the systems that run when the state is exited.
+    ///
+    /// The phase is not part of the pipeline, its systems only run on transitions. Systems are
+    /// usually added to the phase with [`SystemBuilder::on_exit`].
+    pub fn on_exit<S>(&self, state: S) -> EntityView<'_>
+    where
+        S: States,
+    {
+        self.state_phase(c"OnExit", state)
+    }
+
+    /// Observe the transitions of the state, which are passed the old and new state.
+    ///
+    /// The observer is invoked after the systems exiting the old state ran, and before the
+    /// systems entering the new state run.
+    pub fn on_transition<S>(&self, mut func: impl FnMut(&World, S, S) + 'static) -> Observer<'_>
+    where
+        S: States,
+    {
+        self.observer::<StateTransition<S>, ()>()
+            .with_id(ecs_pair(S::id(self), ECS_WILDCARD))
+            .each_event(move |e, transition, _| {
+                func(&e.world(), transition.from, transition.to);
+            })
+    }
+
+    // phases are named `S::OnEnter::Variant`
+    fn state_phase<S>(&self, kind: &CStr, state: S) -> EntityView<'_>
+    where
+        S: States,
+    {
+        let world = self.world_ptr_mut();
+        let kind = unsafe {
+            sys::ecs_entity_init(
+                world,
+                &sys::ecs_entity_desc_t {
+                    parent: S::id(self),
+                    name: kind.as_ptr(),
+                    ..Default::default()
+                },
+            )
+        };
+        let phase = unsafe {
+            sys::ecs_entity_init(
+                world,
+                &sys::ecs_entity_desc_t {
+                    parent: kind,
+                    name: state.name_cstr().as_ptr(),
+                    ..Default::default()
+                },
+            )
+        };
+        EntityView::new_from(self, phase)
+    }
+}
+
+impl<'a, T> SystemBuilder<'a, T>
+where
+    T: Iterable,
+{
+    /// Run the system when the state is entered, instead of every frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state.
+    pub fn on_enter<S>(&mut self, state: S) -> &mut Self
+    where
+        S: States,
+    {
+        let phase = *self.world().on_enter(state).id();
+        self.kind_id(phase)
+    }
+
+    /// Run the system when the state is exited, instead of every frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state.
+    pub fn on_exit<S>(&mut self, state: S) -> &mut Self
+    where
+        S: States,
+    {
+        let phase = *self.world().on_exit(state).id();
+        self.kind_id(phase)
+    }
+}
+
+fn apply_transitions<S>(world: &World, delta_time: FTime)
+where
+    S: States,
+{
+    let (current, entered) = world.map::<&State<S>, _>(|state| (state.current, state.entered));
+    if !entered {
+        world.get::<&mut State<S>>(|state| state.entered = true);
+        run_phase(world, world.on_enter(current), delta_time);
+    }
+
+    while let Some(next) = world.map::<&mut NextState<S>, _>(|next| next.queue.pop_front()) {
+        let from = world.state::<S>();
+        if next == from {
+            continue;
+        }
+
+        run_phase(world, world.on_exit(from), delta_time);
+        world.get::<&mut State<S>>(|state| state.current = next);
+        world.add_enum(next);
+        world
+            .event()
+            .add_id(ecs_pair(S::id(world), *next.get_id_variant(world).id()))
+            .target(S::id(world))
+            .emit(&StateTransition { from, to: next });
+        run_phase(world, world.on_enter(next), delta_time);
+    }
+}
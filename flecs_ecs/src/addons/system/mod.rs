@@ -5,6 +5,8 @@
 //! support for time management, scheduling via pipeline and can be monitored by the stats addon.
 
 mod system_builder;
+mod system_condition;
+pub(crate) use system_condition::run_system;
 mod system_runner_fluent;
 pub use system_builder::*;
 pub use system_runner_fluent::*;
@@ -1,5 +1,6 @@
 //! `SystemBuilder` is a builder pattern for creating systems.
 
+use crate::addons::system::system_condition::{self, Condition};
 use crate::addons::system::*;
 use crate::core::internals::*;
 use crate::core::private::internal_ReactorAPI;
@@ -14,6 +15,9 @@ where
     term_builder: TermBuilder,
     world: WorldRef<'a>,
     is_instanced: bool,
+    conditions: Vec<Condition>,
+    #[cfg(feature = "flecs_pipeline")]
+    constraints: crate::addons::pipeline::SystemConstraints,
     _phantom: std::marker::PhantomData<&'a T>,
 }
 
@@ -29,6 +33,9 @@ where
             world: world.into(),
             _phantom: std::marker::PhantomData,
             is_instanced: false,
+            conditions: Vec::new(),
+            #[cfg(feature = "flecs_pipeline")]
+            constraints: Default::default(),
         };
 
         obj.desc.entity = unsafe { sys::ecs_entity_init(obj.world_ptr_mut(), &Default::default()) };
@@ -55,6 +62,9 @@ where
             world: world.into(),
             _phantom: std::marker::PhantomData,
             is_instanced: false,
+            conditions: Vec::new(),
+            #[cfg(feature = "flecs_pipeline")]
+            constraints: Default::default(),
         };
 
         if obj.desc.entity == 0 {
@@ -87,6 +97,9 @@ where
             world: world.into(),
             _phantom: std::marker::PhantomData,
             is_instanced: false,
+            conditions: Vec::new(),
+            #[cfg(feature = "flecs_pipeline")]
+            constraints: Default::default(),
         };
 
         let entity_desc: sys::ecs_entity_desc_t = sys::ecs_entity_desc_t {
@@ -296,6 +309,273 @@ where
         self.desc.tick_source = Component::id(self.world());
         self
     }
+
+    /// Only run the system when the condition is true.
+    ///
+    /// Conditions are evaluated each time the system would run, such as by the pipeline, before the
+    /// query of the system is iterated. A system with multiple conditions runs when all of them are
+    /// true.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition, which is passed the world.
+    ///
+    /// # Panics
+    ///
+    /// Building the system panics if it's [`multi_threaded`](SystemBuilder::multi_threaded), as
+    /// flecs runs multi threaded systems on each worker, which would each evaluate the conditions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Paused;
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .system::<()>()
+    ///     .run_if(|world| !world.has::<Paused>())
+    ///     .run(|_it| {});
+    ///
+    /// world.add::<Paused>();
+    /// world.progress();
+    /// ```
+    pub fn run_if(&mut self, condition: impl FnMut(&World) -> bool + 'static) -> &mut Self {
+        self.conditions.push(Box::new(condition));
+        self
+    }
+
+    /// Only run the system when the singleton `S` exists and the condition on its value is true.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `S` - The singleton component.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition, which is passed the singleton.
+    pub fn run_if_singleton<S>(
+        &mut self,
+        mut condition: impl FnMut(&S) -> bool + 'static,
+    ) -> &mut Self
+    where
+        S: ComponentId + NotEmptyComponent,
+    {
+        let id = S::id(self.world());
+        self.run_if(move |world| {
+            let value = unsafe { sys::ecs_get_id(world.world_ptr(), id, id) } as *const S;
+            !value.is_null() && condition(unsafe { &*value })
+        })
+    }
+
+    /// Only run the system when the singleton `S` was set or modified since the system last
+    /// evaluated its conditions.
+    ///
+    /// The system runs the first time its conditions are evaluated after the singleton is added.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `S` - The singleton component.
+    pub fn run_if_resource_changed<S>(&mut self) -> &mut Self
+    where
+        S: ComponentId,
+    {
+        let condition = system_condition::resource_changed::<S>(&self.world(), self.desc.entity);
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Only run the system when the enum singleton is in the state, as set with
+    /// [`World::add_enum`].
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state the system runs in.
+    pub fn in_state<S>(&mut self, state: S) -> &mut Self
+    where
+        S: ComponentId + ComponentType<Enum> + CachedEnumData,
+    {
+        let world = self.world();
+        let state = ecs_pair(S::id(world), *state.get_id_variant(world).id());
+        self.run_if(move |world| world.singleton::<S>().has_id(state))
+    }
+
+    /// Run the system before another system, or before the systems in a set.
+    ///
+    /// Constraints order systems within their phase, and apply when the default pipeline is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `system` - The system or the set.
+    ///
+    /// # Panics
+    ///
+    /// [`build`](Builder::build) panics if the constraints form a cycle, see
+    /// [`try_build`](SystemBuilder::try_build).
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn before_id(&mut self, system: impl Into<Entity>) -> &mut Self {
+        self.constraints.before.push(*system.into());
+        self
+    }
+
+    /// Run the system before the systems in a set, or before the system registered as `S`.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `S` - The set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Physics;
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .system_named::<()>("Input")
+    ///     .before::<Physics>()
+    ///     .run(|_it| {});
+    ///
+    /// world
+    ///     .system_named::<()>("Collide")
+    ///     .in_set::<Physics>()
+    ///     .run(|_it| {});
+    ///
+    /// world.progress();
+    /// ```
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn before<S>(&mut self) -> &mut Self
+    where
+        S: ComponentId,
+    {
+        self.before_id(S::id(self.world()))
+    }
+
+    /// Run the system after another system, or after the systems in a set.
+    ///
+    /// Constraints order systems within their phase, and apply when the default pipeline is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `system` - The system or the set.
+    ///
+    /// # Panics
+    ///
+    /// [`build`](Builder::build) panics if the constraints form a cycle, see
+    /// [`try_build`](SystemBuilder::try_build).
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn after_id(&mut self, system: impl Into<Entity>) -> &mut Self {
+        self.constraints.after.push(*system.into());
+        self
+    }
+
+    /// Run the system after the systems in a set, or after the system registered as `S`.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `S` - The set.
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn after<S>(&mut self) -> &mut Self
+    where
+        S: ComponentId,
+    {
+        self.after_id(S::id(self.world()))
+    }
+
+    /// Add the system to a set, so other systems can be ordered relative to all systems of the set.
+    ///
+    /// # Arguments
+    ///
+    /// * `set` - The set, which can be any entity.
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn in_set_id(&mut self, set: impl Into<Entity>) -> &mut Self {
+        self.constraints.sets.push(*set.into());
+        self
+    }
+
+    /// Add the system to a set, so other systems can be ordered relative to all systems of the set.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `S` - The set.
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn in_set<S>(&mut self) -> &mut Self
+    where
+        S: ComponentId,
+    {
+        self.in_set_id(S::id(self.world()))
+    }
+
+    /// Build the system, or return an error if its ordering constraints form a cycle with the
+    /// constraints of other systems.
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn try_build(&mut self) -> Result<System<'a>, crate::addons::pipeline::SystemOrderError> {
+        self.register_order()?;
+        Ok(self.build_system())
+    }
+
+    #[cfg(feature = "flecs_pipeline")]
+    fn register_order(&mut self) -> Result<(), crate::addons::pipeline::SystemOrderError> {
+        use crate::addons::pipeline::SystemOrder;
+
+        if !self.constraints.is_empty() {
+            let world = self.world();
+            let constraints = std::mem::take(&mut self.constraints);
+            if let Err(err) =
+                world
+                    .world_ctx_mut()
+                    .system_order
+                    .insert(world, self.desc.entity, constraints)
+            {
+                unsafe { sys::ecs_delete(world.world_ptr_mut(), self.desc.entity) };
+                self.free_term_strings();
+                return Err(err);
+            }
+            SystemOrder::use_ordered_pipeline(world);
+        }
+        Ok(())
+    }
+
+    fn build_system(&mut self) -> System<'a> {
+        if ChangeMonitor::is_tracked(&self.desc.query) {
+            assert!(
+                !self.desc.multi_threaded,
+                "systems with `Changed` terms can't be multi threaded"
+            );
+            if self.desc.run.is_none() {
+                self.desc.run = Some(run_changed);
+            }
+        }
+        crate::addons::profiler::profile_system(self.world(), &mut self.desc);
+        if !self.conditions.is_empty() {
+            assert!(
+                !self.desc.multi_threaded,
+                "systems with run conditions can't be multi threaded"
+            );
+            system_condition::add_conditions(&mut self.desc, std::mem::take(&mut self.conditions));
+        }
+        let system = System::new(self.world(), self.desc, self.is_instanced);
+        self.free_term_strings();
+        system
+    }
+
+    fn free_term_strings(&mut self) {
+        for string_parts in self.term_builder.str_ptrs_to_free.iter() {
+            unsafe {
+                String::from_raw_parts(
+                    string_parts.ptr as *mut u8,
+                    string_parts.len,
+                    string_parts.capacity,
+                );
+            }
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -342,17 +622,11 @@ where
     /// * C++ API: `node_builder::build`
     #[doc(alias = "node_builder::build")]
     fn build(&mut self) -> Self::BuiltType {
-        let system = System::new(self.world(), self.desc, self.is_instanced);
-        for string_parts in self.term_builder.str_ptrs_to_free.iter() {
-            unsafe {
-                String::from_raw_parts(
-                    string_parts.ptr as *mut u8,
-                    string_parts.len,
-                    string_parts.capacity,
-                );
-            }
+        #[cfg(feature = "flecs_pipeline")]
+        if let Err(err) = self.register_order() {
+            panic!("{err}");
         }
-        system
+        self.build_system()
     }
 }
 
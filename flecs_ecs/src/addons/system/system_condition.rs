@@ -0,0 +1,123 @@
+//! Run conditions, which decide whether a system runs each time it would run.
+//!
+//! A system with conditions is created with [`run_conditional`] as run action, which evaluates the
+//! conditions and then runs the system with its own run action or callback.
+//!
+//! Systems with conditions can't be multi threaded, so the conditions are evaluated once each time
+//! the system runs, on the stage that runs it.
+
+use std::os::raw::c_void;
+
+use crate::core::*;
+use crate::sys;
+
+/// A run condition of a system, see [`SystemBuilder::run_if`].
+pub(crate) type Condition = Box<dyn FnMut(&World) -> bool>;
+
+/// The conditions of a system and the run action they guard.
+struct ConditionalRun {
+    conditions: Vec<Condition>,
+    run: sys::ecs_run_action_t,
+    run_ctx: *mut c_void,
+    run_ctx_free: sys::ecs_ctx_free_t,
+}
+
+/// Guard the run action of the system with the conditions.
+pub(crate) fn add_conditions(desc: &mut sys::ecs_system_desc_t, conditions: Vec<Condition>) {
+    let ctx = Box::new(ConditionalRun {
+        conditions,
+        run: desc.run,
+        run_ctx: desc.run_ctx,
+        run_ctx_free: desc.run_ctx_free,
+    });
+    desc.run = Some(run_conditional);
+    desc.run_ctx = Box::into_raw(ctx) as *mut c_void;
+    desc.run_ctx_free = Some(free_conditional);
+}
+
+/// Returns a condition that is true when the singleton `T` changed since the condition was last
+/// evaluated.
+///
+/// Changes are tracked with a cached query that is deleted with the system. Flecs only caches terms
+/// that match `$this`, so the query matches `T` on any entity, which is only the singleton for
+/// components that are used as resources.
+pub(crate) fn resource_changed<T: ComponentId>(world: &World, system: EntityT) -> Condition {
+    let world_ptr = world.world_ptr_mut();
+    let id = T::id(world);
+
+    let mut desc = sys::ecs_query_desc_t {
+        entity: unsafe {
+            sys::ecs_entity_init(
+                world_ptr,
+                &sys::ecs_entity_desc_t {
+                    parent: system,
+                    ..Default::default()
+                },
+            )
+        },
+        cache_kind: sys::ecs_query_cache_kind_t_EcsQueryCacheAll,
+        ..Default::default()
+    };
+    desc.terms[0].id = id;
+    desc.terms[0].inout = InOutKind::In as i16;
+    let query = unsafe { sys::ecs_query_init(world_ptr, &desc) };
+
+    Box::new(move |world| unsafe {
+        if !sys::ecs_has_id(world.world_ptr_mut(), id, id) {
+            return false;
+        }
+        let changed = sys::ecs_query_changed(query);
+        if changed {
+            // iterating the query marks the changes as seen
+            let mut it = sys::ecs_query_iter(world.world_ptr_mut(), query);
+            while sys::ecs_query_next(&mut it) {}
+        }
+        changed
+    })
+}
+
+unsafe extern "C" fn run_conditional(it: *mut IterT) {
+    let it = unsafe { &mut *it };
+    let ctx = unsafe { &mut *(it.run_ctx as *mut ConditionalRun) };
+    let world = unsafe { WorldRef::from_ptr(it.world) };
+    let has_terms = unsafe { (*it.query).term_count } > 0;
+
+    // all conditions are evaluated, as conditions can track changes
+    let run = ctx
+        .conditions
+        .iter_mut()
+        .fold(true, |run, condition| condition(&world) & run);
+    if !run {
+        // flecs finishes the iterator of systems without terms after running them
+        if has_terms {
+            unsafe { sys::ecs_iter_fini(it) };
+        }
+        return;
+    }
+
+    unsafe { run_system(it, ctx.run, ctx.run_ctx) };
+}
+
+/// Run the system with its run action, or iterate it with its callback when it has none, like
+/// flecs does.
+pub(crate) unsafe fn run_system(it: &mut IterT, run: sys::ecs_run_action_t, run_ctx: *mut c_void) {
+    if let Some(run) = run {
+        it.run_ctx = run_ctx;
+        unsafe { run(it) };
+    } else if let Some(callback) = it.callback {
+        if unsafe { (*it.query).term_count } > 0 {
+            while unsafe { sys::ecs_iter_next(it) } {
+                unsafe { callback(it) };
+            }
+        } else {
+            unsafe { callback(it) };
+        }
+    }
+}
+
+unsafe extern "C" fn free_conditional(ctx: *mut c_void) {
+    let ctx = unsafe { Box::from_raw(ctx as *mut ConditionalRun) };
+    if let Some(free) = ctx.run_ctx_free {
+        unsafe { free(ctx.run_ctx) };
+    }
+}
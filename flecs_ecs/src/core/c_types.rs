@@ -75,6 +75,53 @@ impl From<sys::ecs_inout_kind_t> for InOutKind {
     }
 }
 
+/// Specifies what happens to a component when an entity that owns it is instantiated.
+///
+/// - `Override`: The instance gets its own copy of the component. This is the default.
+/// - `Inherit`: The instance shares the component with the base, until it is overridden.
+/// - `DontInherit`: The component is not added to the instance.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Instantiate {
+    Override,
+    Inherit,
+    DontInherit,
+}
+
+impl Instantiate {
+    /// Returns the id of the trait that is the target of the `(OnInstantiate, *)` pair.
+    pub fn id(self) -> EntityT {
+        match self {
+            Self::Override => ECS_OVERRIDE,
+            Self::Inherit => ECS_INHERIT,
+            Self::DontInherit => ECS_DONT_INHERIT,
+        }
+    }
+}
+
+/// Specifies what happens to entities that use an entity when it is deleted, either as component or
+/// relationship (`OnDelete`) or as relationship target (`OnDeleteTarget`).
+///
+/// - `Remove`: The id is removed from the entities that have it. This is the default.
+/// - `Delete`: The entities that have the id are deleted.
+/// - `Panic`: Deleting the entity while it's in use is an error.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CleanupPolicy {
+    Remove,
+    Delete,
+    Panic,
+}
+
+impl CleanupPolicy {
+    /// Returns the id of the policy that is the target of the `(OnDelete, *)` and `(OnDeleteTarget, *)` pairs.
+    pub fn id(self) -> EntityT {
+        match self {
+            Self::Remove => ECS_REMOVE,
+            Self::Delete => ECS_DELETE,
+            Self::Panic => ECS_PANIC,
+        }
+    }
+}
+
 const EcsInOutDefault: i16 = sys::ecs_inout_kind_t_EcsInOutDefault as i16;
 const EcsInOutNone: i16 = sys::ecs_inout_kind_t_EcsInOutNone as i16;
 const EcsInOut: i16 = sys::ecs_inout_kind_t_EcsInOut as i16;
@@ -261,8 +308,7 @@ pub(crate) const ECS_AND: u64 = 1 << 60;
 // Builtin component ids
 pub(crate) const ECS_COMPONENT: u64 = 1;
 pub(crate) const ECS_IDENTIFIER: u64 = 2;
-pub(crate) const ECS_ITERABLE: u64 = 3;
-pub(crate) const ECS_POLY: u64 = 4;
+pub(crate) const ECS_POLY: u64 = 3;
 
 // Poly target components
 pub(crate) const ECS_QUERY: u64 = 5;
@@ -0,0 +1,213 @@
+//! Change detection for the [`Changed`] query term.
+//!
+//! Queries with this term get a [`ChangeMonitor`] as binding context, which is consulted for each
+//! result while iterating. Flecs detects changes per query, for all the fields the query reads, so
+//! each `Changed<T>` term gets a cached query of its own with only `T` as term. Whether `T` changed
+//! in the table of a result is asked to that query with `ecs_iter_changed`, and once the result
+//! was visited the query iterates the table, which makes flecs record its current state.
+
+use std::ffi::c_void;
+
+use crate::core::*;
+use crate::sys;
+
+/// A `Changed<T>` field of a query, and the query that monitors `T` for it.
+struct ChangedField {
+    field: i32,
+    id: IdT,
+    monitor: EntityT,
+}
+
+/// The state of the `Changed<T>` terms of a query.
+pub(crate) struct ChangeMonitor {
+    world: *mut WorldT,
+    fields: Vec<ChangedField>,
+    /// The monitor queries and tables of the last visited result. Their state is recorded once the
+    /// query advanced, after flecs marked the fields the query writes to as dirty, so writes of the
+    /// query itself are not reported as a change the next time.
+    visited: Vec<(EntityT, *mut TableT)>,
+}
+
+impl ChangeMonitor {
+    /// Filter the results of the query on the data of the current term with component `id` being
+    /// written since the last iteration.
+    pub(crate) fn track<'a>(builder: &mut impl QueryBuilderImpl<'a>, id: IdT) {
+        let field = builder.current_term_index();
+        let world = builder.world().real_world().world_ptr_mut();
+        let monitor = monitor_query(world, id);
+
+        let desc = builder.query_desc_mut();
+        if desc.binding_ctx.is_null() {
+            let monitor = Box::new(ChangeMonitor {
+                world,
+                fields: Vec::new(),
+                visited: Vec::new(),
+            });
+            desc.binding_ctx = Box::into_raw(monitor) as *mut c_void;
+            desc.binding_ctx_free = Some(free_monitor);
+        }
+        let changes = unsafe { &mut *(desc.binding_ctx as *mut ChangeMonitor) };
+        changes.fields.push(ChangedField { field, id, monitor });
+    }
+
+    /// Returns whether the query descriptor has `Changed<T>` terms.
+    pub(crate) fn is_tracked(desc: &sys::ecs_query_desc_t) -> bool {
+        !desc.binding_ctx.is_null()
+    }
+
+    /// Returns the fields of the query descriptor that are tracked.
+    pub(crate) fn key(desc: &sys::ecs_query_desc_t) -> Vec<u64> {
+        let monitor = desc.binding_ctx as *const ChangeMonitor;
+        match unsafe { monitor.as_ref() } {
+            Some(monitor) => monitor
+                .fields
+                .iter()
+                .map(|changed| changed.field as u64)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Free the change monitor of a query descriptor that isn't turned into a query.
+    pub(crate) fn free_desc(desc: &mut sys::ecs_query_desc_t) {
+        if !desc.binding_ctx.is_null() {
+            unsafe { free_monitor(desc.binding_ctx) };
+            desc.binding_ctx = std::ptr::null_mut();
+            desc.binding_ctx_free = None;
+        }
+    }
+
+    unsafe fn of<'q>(query: *const QueryT) -> Option<&'q mut ChangeMonitor> {
+        if query.is_null() {
+            return None;
+        }
+        unsafe { ((*query).binding_ctx as *mut ChangeMonitor).as_mut() }
+    }
+
+    /// Returns the query that monitors the component of a `Changed<T>` field, which is created
+    /// again if it was deleted.
+    fn monitor_of(&mut self, index: usize) -> *mut QueryT {
+        let changed = &mut self.fields[index];
+        if let Some(query) = query_ptr_from_entity(self.world, changed.monitor) {
+            return query.as_ptr();
+        }
+        changed.monitor = monitor_query(self.world, changed.id);
+        query_ptr_from_entity(self.world, changed.monitor)
+            .map_or(std::ptr::null_mut(), |query| query.as_ptr())
+    }
+
+    /// Record the state of the tables of the last visited result.
+    fn sync_visited(&mut self) {
+        for (monitor, table) in std::mem::take(&mut self.visited) {
+            let Some(query) = query_ptr_from_entity(self.world, monitor) else {
+                continue;
+            };
+            let mut it = unsafe { std::mem::zeroed::<IterT>() };
+            // advancing the iterator past a result that isn't skipped records its state
+            if unsafe { sys::ecs_query_has_table(query.as_ptr(), table, &mut it) } {
+                while unsafe { sys::ecs_query_next(&mut it) } {}
+            }
+        }
+    }
+}
+
+/// Create the cached query that monitors writes to the component for a `Changed<T>` term.
+fn monitor_query(world: *mut WorldT, id: IdT) -> EntityT {
+    let mut desc = sys::ecs_query_desc_t {
+        cache_kind: sys::ecs_query_cache_kind_t_EcsQueryCacheAll,
+        ..Default::default()
+    };
+    desc.terms[0].id = id;
+    // flecs only monitors the fields that are read
+    desc.terms[0].inout = InOutKind::In as i16;
+    let query = unsafe { sys::ecs_query_init(world, &desc) };
+    ecs_assert!(
+        !query.is_null(),
+        FlecsErrorCode::InvalidOperation,
+        "failed to create the query that monitors a Changed<T> term"
+    );
+    unsafe { (*query).entity }
+}
+
+/// Progress the iterator of the query to the next result that passes its `Changed<T>` terms, with
+/// `next` as the iterator's next function.
+///
+/// Results that don't pass are skipped and don't mark the fields the query writes to as dirty.
+///
+/// # Safety
+///
+/// `query` must be the query that `it` iterates, `it` must be a query iterator.
+pub(crate) unsafe fn next_result(
+    query: *const QueryT,
+    it: &mut IterT,
+    mut next: impl FnMut(&mut IterT) -> bool,
+) -> bool {
+    let Some(monitor) = (unsafe { ChangeMonitor::of(query) }) else {
+        return next(it);
+    };
+
+    loop {
+        let has_next = next(it);
+        monitor.sync_visited();
+        if !has_next {
+            return false;
+        }
+
+        let mut visit = true;
+        for index in 0..monitor.fields.len() {
+            let field = monitor.fields[index].field;
+            ecs_assert!(
+                unsafe { sys::ecs_field_is_self(it, field) },
+                FlecsErrorCode::InvalidOperation,
+                "Changed<T> terms must match the component on the entity itself"
+            );
+            let query = monitor.monitor_of(index);
+            let mut monitor_it = unsafe { std::mem::zeroed::<IterT>() };
+            if !query.is_null()
+                && unsafe { sys::ecs_query_has_table(query, it.table, &mut monitor_it) }
+            {
+                visit &= unsafe { sys::ecs_iter_changed(&mut monitor_it) };
+                // finishing the iterator without advancing it keeps the state of the table
+                unsafe { sys::ecs_iter_fini(&mut monitor_it) };
+            } else {
+                visit = false;
+            }
+        }
+
+        if visit {
+            monitor.visited = monitor
+                .fields
+                .iter()
+                .map(|changed| (changed.monitor, it.table))
+                .collect();
+            return true;
+        }
+        unsafe { sys::ecs_iter_skip(it) };
+    }
+}
+
+/// Iterate a system with its callback, skipping the results that don't pass its `Changed<T>`
+/// terms.
+pub(crate) unsafe extern "C" fn run_changed(it: *mut IterT) {
+    let it = unsafe { &mut *it };
+    let Some(callback) = it.callback else {
+        return;
+    };
+    let query = it.query;
+    while unsafe { next_result(query, it, |it| sys::ecs_iter_next(it)) } {
+        unsafe { callback(it) };
+    }
+}
+
+unsafe extern "C" fn free_monitor(ctx: *mut c_void) {
+    let monitor = unsafe { Box::from_raw(ctx as *mut ChangeMonitor) };
+    let world = monitor.world;
+    if unsafe { sys::ecs_is_fini(world) } {
+        return;
+    }
+    for changed in &monitor.fields {
+        if unsafe { sys::ecs_is_alive(world, changed.monitor) } {
+            unsafe { sys::ecs_delete(world, changed.monitor) };
+        }
+    }
+}
@@ -15,9 +15,39 @@ where
     if T::IS_ENUM {
         register_enum_data::<T>(world_ptr, id);
     }
+
+    // the traits can refer to other components, whose traits can refer back to this component
+    let world = world.world();
+    store_component_id::<T>(world, id);
+    T::__register_traits(world, id);
     id
 }
 
+/// Store the id of a registered component in the world, so its id is known from then on.
+fn store_component_id<T>(world: WorldRef, id: EntityT)
+where
+    T: ComponentId,
+{
+    if T::IS_GENERIC {
+        world
+            .components_map()
+            .insert(std::any::TypeId::of::<T>(), id);
+    } else {
+        let index = T::index() as usize;
+        let components_array = world.components_array();
+        if components_array.len() <= index {
+            components_array.resize(index + 1, 0);
+        }
+        components_array[index] = id;
+    }
+
+    #[cfg(feature = "flecs_module_dylib")]
+    world
+        .world_ctx_mut()
+        .component_layouts
+        .insert(id, crate::addons::layout_hash::<T>());
+}
+
 #[inline(always)]
 /// attempts to register the component with the world. If it's already registered, it does nothing.
 pub(crate) fn try_register_component<'a, T>(world: impl IntoWorld<'a>) -> EntityT
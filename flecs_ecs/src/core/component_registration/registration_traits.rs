@@ -102,10 +102,14 @@ pub trait ComponentId: Sized + ComponentInfo + 'static {
             }
         } else {
             let world = world.world();
-            let components_map = world.components_map();
-            *(components_map
-                .entry(std::any::TypeId::of::<Self>())
-                .or_insert_with(|| try_register_component::<Self>(world)))
+            let type_id = std::any::TypeId::of::<Self>();
+            if let Some(&id) = world.components_map().get(&type_id) {
+                return id;
+            }
+            // not inserted with the entry api, registration inserts other components
+            let id = try_register_component::<Self>(world);
+            world.components_map().insert(type_id, id);
+            id
         }
     }
 
@@ -143,12 +147,13 @@ pub trait ComponentId: Sized + ComponentInfo + 'static {
             }
         } else {
             let world = world.world();
-            let components_map = world.components_map();
-            *(components_map
-                .entry(std::any::TypeId::of::<Self>())
-                .or_insert_with(|| {
-                    try_register_component_named::<Self::UnderlyingType>(world, name)
-                }))
+            let type_id = std::any::TypeId::of::<Self>();
+            if let Some(&id) = world.components_map().get(&type_id) {
+                return id;
+            }
+            let id = try_register_component_named::<Self::UnderlyingType>(world, name);
+            world.components_map().insert(type_id, id);
+            id
         }
     }
 
@@ -225,22 +230,24 @@ pub trait ComponentId: Sized + ComponentInfo + 'static {
             }
         } else {
             let world = world.world();
-            let components_map = world.components_map();
-            *(components_map
-                .entry(std::any::TypeId::of::<Self>())
-                .or_insert_with(|| {
-                    #[cfg(feature = "flecs_manual_registration")]
-                    {
-                        ecs_assert!(
-                            false,
-                            FlecsErrorCode::InvalidOperation,
-                            "Component {} is not registered with the world before usage",
-                            Self::name()
-                        );
-                    }
+            let type_id = std::any::TypeId::of::<Self>();
+            if let Some(&id) = world.components_map().get(&type_id) {
+                return id;
+            }
+
+            #[cfg(feature = "flecs_manual_registration")]
+            {
+                ecs_assert!(
+                    false,
+                    FlecsErrorCode::InvalidOperation,
+                    "Component {} is not registered with the world before usage",
+                    Self::name()
+                );
+            }
 
-                    try_register_component::<Self>(world)
-                }))
+            let id = try_register_component::<Self>(world);
+            world.components_map().insert(type_id, id);
+            id
         }
     }
 
@@ -248,6 +255,20 @@ pub trait ComponentId: Sized + ComponentInfo + 'static {
     #[doc(hidden)]
     fn __register_lifecycle_hooks(_type_hooks: &mut TypeHooksT) {}
 
+    // Not public API.
+    #[doc(hidden)]
+    fn __register_traits(_world: WorldRef, _id: EntityT) {}
+
+    // Not public API.
+    #[doc(hidden)]
+    fn __module_path() -> Option<&'static str> {
+        None
+    }
+
+    // Not public API.
+    #[doc(hidden)]
+    fn __import_module_deps(_world: &World) {}
+
     // Not public API.
     #[doc(hidden)]
     fn __register_default_hooks(_type_hooks: &mut TypeHooksT) {}
@@ -129,7 +129,107 @@ impl<'a, T: ComponentId> Component<'a, T> {
         }
     }
 
+    /// Set what happens to the component when an entity that owns it is instantiated.
+    ///
+    /// This can also be configured with the `#[flecs(override)]`, `#[flecs(inherit)]` and
+    /// `#[flecs(dont_inherit)]` attributes of the `Component` derive.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy`: the instantiation policy, [`Instantiate::Override`] if not set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Mesh(u32);
+    ///
+    /// let world = World::new();
+    /// world
+    ///     .component::<Mesh>()
+    ///     .on_instantiate(Instantiate::Inherit);
+    ///
+    /// let prefab = world.prefab().set(Mesh(1));
+    /// let inst = world.entity().is_a_id(prefab);
+    /// assert!(inst.has::<Mesh>());
+    /// assert!(!inst.owns::<Mesh>());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `component::add(flecs::OnInstantiate, ...)`
+    pub fn on_instantiate(&mut self, policy: Instantiate) -> &mut Self {
+        self.entity.add_id((ECS_ON_INSTANTIATE, policy.id()));
+        self
+    }
+
+    /// Set what happens to entities that have the component, or a pair with the component as
+    /// relationship, when the component is deleted.
+    ///
+    /// This can also be configured with the `#[flecs(on_delete(...))]` attribute of the
+    /// `Component` derive.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy`: the cleanup policy, [`CleanupPolicy::Remove`] if not set.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::try_destruct`]
+    /// * C++ API: `component::add(flecs::OnDelete, ...)`
+    pub fn on_delete(&mut self, policy: CleanupPolicy) -> &mut Self {
+        self.entity.add_id((ECS_ON_DELETE, policy.id()));
+        self
+    }
+
+    /// Set what happens to entities that have a pair with the component as relationship, when the
+    /// target of the pair is deleted.
+    ///
+    /// This can also be configured with the `#[flecs(on_delete_target(...))]` attribute of the
+    /// `Component` derive.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy`: the cleanup policy, [`CleanupPolicy::Remove`] if not set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Uses;
+    ///
+    /// let world = World::new();
+    /// world
+    ///     .component::<Uses>()
+    ///     .on_delete_target(CleanupPolicy::Panic);
+    ///
+    /// let texture = world.entity();
+    /// let material = world.entity().add_first::<Uses>(texture);
+    ///
+    /// assert!(texture.try_destruct().is_err());
+    ///
+    /// material.destruct();
+    /// assert!(texture.try_destruct().is_ok());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::try_destruct`]
+    /// * C++ API: `component::add(flecs::OnDeleteTarget, ...)`
+    pub fn on_delete_target(&mut self, policy: CleanupPolicy) -> &mut Self {
+        self.entity.add_id((ECS_ON_DELETE_TARGET, policy.id()));
+        self
+    }
+
     /// Register on add hook.
+    /// Replaces a previously registered on add hook.
+    ///
+    /// Hooks must be registered before the component is in use, flecs aborts when a table with
+    /// the component already exists.
     ///
     /// # See also
     ///
@@ -141,14 +241,8 @@ impl<'a, T: ComponentId> Component<'a, T> {
     {
         let mut type_hooks: TypeHooksT = self.get_hooks();
 
-        ecs_assert!(
-            type_hooks.on_add.is_none(),
-            FlecsErrorCode::InvalidOperation,
-            "on_add hook already set for component {}",
-            std::any::type_name::<T>()
-        );
-
         let binding_ctx = Self::get_binding_context(&mut type_hooks);
+        binding_ctx.free_on_add();
         let boxed_func = Box::new(func);
         let static_ref = Box::leak(boxed_func);
         binding_ctx.on_add = Some(static_ref as *mut _ as *mut c_void);
@@ -159,6 +253,10 @@ impl<'a, T: ComponentId> Component<'a, T> {
     }
 
     /// Register on remove hook.
+    /// Replaces a previously registered on remove hook.
+    ///
+    /// Hooks must be registered before the component is in use, flecs aborts when a table with
+    /// the component already exists.
     ///
     /// # See also
     ///
@@ -170,14 +268,8 @@ impl<'a, T: ComponentId> Component<'a, T> {
     {
         let mut type_hooks: TypeHooksT = self.get_hooks();
 
-        ecs_assert!(
-            type_hooks.on_remove.is_none(),
-            FlecsErrorCode::InvalidOperation,
-            "on_remove hook already set for component {}",
-            std::any::type_name::<T>()
-        );
-
         let binding_ctx = Self::get_binding_context(&mut type_hooks);
+        binding_ctx.free_on_remove();
         let boxed_func = Box::new(func);
         let static_ref = Box::leak(boxed_func);
         binding_ctx.on_remove = Some(static_ref as *mut _ as *mut c_void);
@@ -188,6 +280,10 @@ impl<'a, T: ComponentId> Component<'a, T> {
     }
 
     /// Register on set hook.
+    /// Replaces a previously registered on set hook.
+    ///
+    /// Hooks must be registered before the component is in use, flecs aborts when a table with
+    /// the component already exists.
     ///
     /// # See also
     ///
@@ -199,14 +295,8 @@ impl<'a, T: ComponentId> Component<'a, T> {
     {
         let mut type_hooks: TypeHooksT = self.get_hooks();
 
-        ecs_assert!(
-            type_hooks.on_set.is_none(),
-            FlecsErrorCode::InvalidOperation,
-            "on_set hook already set for component {}",
-            std::any::type_name::<T>()
-        );
-
         let binding_ctx = Self::get_binding_context(&mut type_hooks);
+        binding_ctx.free_on_set();
         let boxed_func = Box::new(func);
         let static_ref = Box::leak(boxed_func);
         binding_ctx.on_set = Some(static_ref as *mut _ as *mut c_void);
@@ -216,6 +306,243 @@ impl<'a, T: ComponentId> Component<'a, T> {
         self
     }
 
+    /// Register on replace hook.
+    /// Invoked when `set` overwrites a value the entity already has, with the entity,
+    /// the current value and the value that replaces it.
+    /// [`EntityView::modify`] also invokes the hook, with a copy of the value from before it was
+    /// modified.
+    /// Replaces a previously registered on replace hook.
+    ///
+    /// Hooks must be registered before the component is in use, flecs aborts when a table with
+    /// the component already exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .component::<Health>()
+    ///     .on_replace(|_entity, old, new| {
+    ///         // never heal above the previous value
+    ///         new.0 = new.0.min(old.0);
+    ///     });
+    ///
+    /// let e = world.entity().set(Health(10));
+    /// e.set(Health(20));
+    /// e.get::<&Health>(|health| assert_eq!(health.0, 10));
+    /// ```
+    pub fn on_replace<Func>(&mut self, func: Func) -> &mut Self
+    where
+        Func: FnMut(EntityView, &mut T, &mut T) + 'static,
+    {
+        let mut type_hooks: TypeHooksT = self.get_hooks();
+
+        let binding_ctx = Self::get_binding_context(&mut type_hooks);
+        binding_ctx.free_on_replace();
+        let boxed_func = Box::new(func);
+        let static_ref = Box::leak(boxed_func);
+        binding_ctx.on_replace = Some(static_ref as *mut _ as *mut c_void);
+        binding_ctx.free_on_replace = Some(Self::on_replace_drop::<Func>);
+        binding_ctx.run_on_replace = Some(Self::run_replace::<Func>);
+        unsafe { sys::ecs_set_hooks_id(self.world.world_ptr_mut(), *self.id, &type_hooks) };
+        self
+    }
+
+    /// Register on move hook.
+    /// Invoked with the entity and the value at its new location after the storage moved it, for
+    /// example when the entity changes archetype or another entity in the same table is deleted.
+    /// Useful to keep external structures that point into component storage up to date.
+    /// Replaces a previously registered on move hook.
+    ///
+    /// The storage only invokes move hooks for tables with lifecycle hooks. A component without a
+    /// constructor, destructor or add and remove hooks gets a destructor that only invokes the
+    /// on dtor hook, so its tables use the move hooks as well.
+    ///
+    /// Flecs doesn't pass the entity to move hooks, so it is found by scanning the tables that
+    /// store the component for the moved value. The cost of every move therefore grows with the
+    /// number of tables with `T`, see the `move_hook_tables` benchmark. Prefer the hook for
+    /// components that are stored in few archetypes.
+    ///
+    /// Hooks must be registered before the component is in use, flecs aborts when a table with
+    /// the component already exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity;
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .component::<Position>()
+    ///     .on_move(|entity, pos| println!("{} moved to {:p}", entity.id(), pos));
+    ///
+    /// world
+    ///     .entity()
+    ///     .set(Position { x: 1.0, y: 2.0 })
+    ///     .add::<Velocity>();
+    /// ```
+    pub fn on_move<Func>(&mut self, func: Func) -> &mut Self
+    where
+        Func: FnMut(EntityView, &mut T) + 'static,
+    {
+        let mut type_hooks: TypeHooksT = self.get_hooks();
+        let is_complex = type_hooks.ctor.is_some()
+            || type_hooks.dtor.is_some()
+            || type_hooks.on_add.is_some()
+            || type_hooks.on_remove.is_some();
+
+        let binding_ctx = Self::get_binding_context(&mut type_hooks);
+        binding_ctx.free_on_move();
+        let boxed_func = Box::new(func);
+        let static_ref = Box::leak(boxed_func);
+        binding_ctx.on_move = Some(static_ref as *mut _ as *mut c_void);
+        binding_ctx.free_on_move = Some(Self::on_move_drop::<Func>);
+        binding_ctx.run_on_move = Some(Self::run_on_move::<Func>);
+        binding_ctx.world =
+            unsafe { sys::ecs_get_world(self.world.world_ptr() as *const _) as *mut _ };
+
+        if !is_complex {
+            Self::wrap_dtor(&mut type_hooks);
+        }
+        Self::wrap_move_hooks(&mut type_hooks);
+        unsafe { sys::ecs_set_hooks_id(self.world.world_ptr_mut(), *self.id, &type_hooks) };
+        self
+    }
+
+    /// Register on dtor hook.
+    /// Invoked right before the storage destructs a value, for example when the component is
+    /// removed or the world is deleted. Values that `set` overwrites are reported to the on
+    /// replace hook instead.
+    /// Replaces a previously registered on dtor hook.
+    ///
+    /// Hooks must be registered before the component is in use, flecs aborts when a table with
+    /// the component already exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Handle(u32);
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .component::<Handle>()
+    ///     .on_dtor(|handle| println!("release {}", handle.0));
+    ///
+    /// let e = world.entity().set(Handle(1));
+    /// e.remove::<Handle>();
+    /// ```
+    pub fn on_dtor<Func>(&mut self, func: Func) -> &mut Self
+    where
+        Func: FnMut(&mut T) + 'static,
+    {
+        let mut type_hooks: TypeHooksT = self.get_hooks();
+
+        let binding_ctx = Self::get_binding_context(&mut type_hooks);
+        binding_ctx.free_on_dtor();
+        let boxed_func = Box::new(func);
+        let static_ref = Box::leak(boxed_func);
+        binding_ctx.on_dtor = Some(static_ref as *mut _ as *mut c_void);
+        binding_ctx.free_on_dtor = Some(Self::on_dtor_drop::<Func>);
+        binding_ctx.run_on_dtor = Some(Self::run_on_dtor::<Func>);
+
+        Self::wrap_dtor(&mut type_hooks);
+        // overwritten values are destructed by the move hooks
+        Self::wrap_move_hooks(&mut type_hooks);
+        unsafe { sys::ecs_set_hooks_id(self.world.world_ptr_mut(), *self.id, &type_hooks) };
+        self
+    }
+
+    /// Replace the destructor with one that invokes the on dtor hook first.
+    /// The destructor from before the first call is kept in the binding context.
+    fn wrap_dtor(type_hooks: &mut TypeHooksT) {
+        let original = type_hooks.dtor;
+        let binding_ctx = Self::get_binding_context(type_hooks);
+        binding_ctx.dtor.get_or_insert(original);
+        type_hooks.dtor = Some(Self::run_dtor);
+    }
+
+    /// Replace the move hooks with ones that invoke the on dtor hook for overwritten values and the
+    /// on move hook for moved values. The hooks from before the first call are kept in the binding
+    /// context.
+    fn wrap_move_hooks(type_hooks: &mut TypeHooksT) {
+        let original = MoveHooks {
+            move_ctor: type_hooks.move_ctor,
+            ctor_move_dtor: type_hooks.ctor_move_dtor,
+            move_dtor: type_hooks.move_dtor,
+        };
+        let binding_ctx = Self::get_binding_context(type_hooks);
+        let original = *binding_ctx.move_hooks.get_or_insert(original);
+
+        if original.move_ctor.is_some() {
+            type_hooks.move_ctor = Some(Self::run_move_ctor);
+        }
+        type_hooks.ctor_move_dtor = Some(Self::run_ctor_move_dtor);
+        type_hooks.move_dtor = Some(Self::run_move_dtor);
+    }
+
+    /// Register a constructor, used instead of `Default` when the component is added without a value.
+    /// This makes it possible to `add` components that don't implement `Default`.
+    /// Replaces a previously registered constructor.
+    ///
+    /// Hooks must be registered before the component is in use, flecs aborts when a table with
+    /// the component already exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Timer {
+    ///     remaining: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .component::<Timer>()
+    ///     .on_ctor(|| Timer { remaining: 5.0 });
+    ///
+    /// let e = world.entity().add::<Timer>();
+    /// e.get::<&Timer>(|timer| assert_eq!(timer.remaining, 5.0));
+    /// ```
+    pub fn on_ctor<Func>(&mut self, func: Func) -> &mut Self
+    where
+        Func: FnMut() -> T + 'static,
+    {
+        let mut type_hooks: TypeHooksT = self.get_hooks();
+
+        let binding_ctx = Self::get_binding_context(&mut type_hooks);
+        binding_ctx.free_ctor();
+        let boxed_func = Box::new(func);
+        let static_ref = Box::leak(boxed_func);
+        binding_ctx.ctor = Some(static_ref as *mut _ as *mut c_void);
+        binding_ctx.free_ctor = Some(Self::ctor_drop::<Func>);
+        type_hooks.ctor = Some(Self::run_ctor::<Func>);
+        unsafe { sys::ecs_set_hooks_id(self.world.world_ptr_mut(), *self.id, &type_hooks) };
+        self
+    }
+
     /// Function to free the on add hook.
     unsafe extern "C" fn on_add_drop<Func>(func: *mut c_void)
     where
@@ -249,6 +576,50 @@ impl<'a, T: ComponentId> Component<'a, T> {
         }
     }
 
+    /// Function to free the on replace hook.
+    unsafe extern "C" fn on_replace_drop<Func>(func: *mut c_void)
+    where
+        Func: FnMut(EntityView, &mut T, &mut T) + 'static,
+    {
+        let ptr_func: *mut Func = func as *mut Func;
+        unsafe {
+            ptr::drop_in_place(ptr_func);
+        }
+    }
+
+    /// Function to free the on move hook.
+    unsafe extern "C" fn on_move_drop<Func>(func: *mut c_void)
+    where
+        Func: FnMut(EntityView, &mut T) + 'static,
+    {
+        let ptr_func: *mut Func = func as *mut Func;
+        unsafe {
+            ptr::drop_in_place(ptr_func);
+        }
+    }
+
+    /// Function to free the on dtor hook.
+    unsafe extern "C" fn on_dtor_drop<Func>(func: *mut c_void)
+    where
+        Func: FnMut(&mut T) + 'static,
+    {
+        let ptr_func: *mut Func = func as *mut Func;
+        unsafe {
+            ptr::drop_in_place(ptr_func);
+        }
+    }
+
+    /// Function to free the constructor.
+    unsafe extern "C" fn ctor_drop<Func>(func: *mut c_void)
+    where
+        Func: FnMut() -> T + 'static,
+    {
+        let ptr_func: *mut Func = func as *mut Func;
+        unsafe {
+            ptr::drop_in_place(ptr_func);
+        }
+    }
+
     /// Function to run the on add hook.
     unsafe extern "C" fn run_add<Func>(iter: *mut IterT)
     where
@@ -293,6 +664,204 @@ impl<'a, T: ComponentId> Component<'a, T> {
         let component: *mut T = unsafe { ecs_field::<T>(iter, 0) };
         on_remove(entity, unsafe { &mut *component });
     }
+
+    /// Function to run the on replace hook.
+    unsafe fn run_replace<Func>(
+        func: *mut c_void,
+        world: *mut WorldT,
+        entity: EntityT,
+        old: *mut c_void,
+        new: *mut c_void,
+    ) where
+        Func: FnMut(EntityView, &mut T, &mut T) + 'static,
+    {
+        let on_replace = unsafe { &mut *(func as *mut Func) };
+        let world = unsafe { WorldRef::from_ptr(world) };
+        let entity = EntityView::new_from(world, entity);
+        on_replace(entity, unsafe { &mut *(old as *mut T) }, unsafe {
+            &mut *(new as *mut T)
+        });
+    }
+
+    /// Function to run the constructor.
+    unsafe extern "C" fn run_ctor<Func>(
+        ptr: *mut c_void,
+        count: i32,
+        type_info: *const sys::ecs_type_info_t,
+    ) where
+        Func: FnMut() -> T + 'static,
+    {
+        let ctx: *const ComponentBindingCtx = unsafe { (*type_info).hooks.binding_ctx as *const _ };
+        let ctor = unsafe { (*ctx).ctor.unwrap() };
+        let ctor = unsafe { &mut *(ctor as *mut Func) };
+        let arr = ptr as *mut T;
+        for i in 0..count as usize {
+            unsafe { ptr::write(arr.add(i), ctor()) };
+        }
+    }
+
+    /// Function to run the on move hook.
+    unsafe fn run_on_move<Func>(
+        func: *mut c_void,
+        world: *mut WorldT,
+        entity: EntityT,
+        value: *mut c_void,
+    ) where
+        Func: FnMut(EntityView, &mut T) + 'static,
+    {
+        let on_move = unsafe { &mut *(func as *mut Func) };
+        let world = unsafe { WorldRef::from_ptr(world) };
+        let entity = EntityView::new_from(world, entity);
+        on_move(entity, unsafe { &mut *(value as *mut T) });
+    }
+
+    /// Function to run the on dtor hook.
+    unsafe fn run_on_dtor<Func>(func: *mut c_void, value: *mut c_void)
+    where
+        Func: FnMut(&mut T) + 'static,
+    {
+        let on_dtor = unsafe { &mut *(func as *mut Func) };
+        on_dtor(unsafe { &mut *(value as *mut T) });
+    }
+
+    /// Binding context of the component that the hooks of `type_info` belong to.
+    unsafe fn binding_ctx<'t>(type_info: *const sys::ecs_type_info_t) -> &'t ComponentBindingCtx {
+        unsafe { &*((*type_info).hooks.binding_ctx as *const ComponentBindingCtx) }
+    }
+
+    /// Invoke the on dtor hook, if any, for `count` values.
+    unsafe fn invoke_on_dtor(ctx: &ComponentBindingCtx, ptr: *mut c_void, count: i32) {
+        let (Some(func), Some(run)) = (ctx.on_dtor, ctx.run_on_dtor) else {
+            return;
+        };
+        let arr = ptr as *mut T;
+        for i in 0..count as usize {
+            unsafe { run(func, arr.add(i) as *mut c_void) };
+        }
+    }
+
+    /// Function to run the on dtor hook followed by the destructor that was registered before it.
+    unsafe extern "C" fn run_dtor(
+        ptr: *mut c_void,
+        count: i32,
+        type_info: *const sys::ecs_type_info_t,
+    ) {
+        let ctx = unsafe { Self::binding_ctx(type_info) };
+        unsafe { Self::invoke_on_dtor(ctx, ptr, count) };
+        if let Some(Some(dtor)) = ctx.dtor {
+            unsafe { dtor(ptr, count, type_info) };
+        }
+    }
+
+    /// Function to run the move constructor followed by the on move hook.
+    unsafe extern "C" fn run_move_ctor(
+        dst: *mut c_void,
+        src: *mut c_void,
+        count: i32,
+        type_info: *const sys::ecs_type_info_t,
+    ) {
+        unsafe { Self::run_move(dst, src, count, type_info, |hooks| hooks.move_ctor) };
+    }
+
+    /// Function to run the destructive move constructor followed by the on move hook.
+    unsafe extern "C" fn run_ctor_move_dtor(
+        dst: *mut c_void,
+        src: *mut c_void,
+        count: i32,
+        type_info: *const sys::ecs_type_info_t,
+    ) {
+        unsafe { Self::run_move(dst, src, count, type_info, |hooks| hooks.ctor_move_dtor) };
+    }
+
+    /// Function to run the on dtor hook for the overwritten values, followed by the destructive
+    /// move and the on move hook.
+    unsafe extern "C" fn run_move_dtor(
+        dst: *mut c_void,
+        src: *mut c_void,
+        count: i32,
+        type_info: *const sys::ecs_type_info_t,
+    ) {
+        let ctx = unsafe { Self::binding_ctx(type_info) };
+        unsafe { Self::invoke_on_dtor(ctx, dst, count) };
+        unsafe { Self::run_move(dst, src, count, type_info, |hooks| hooks.move_dtor) };
+    }
+
+    /// Run the move hook that was registered before the hooks wrapped it, or copy the bytes if
+    /// there was none, then invoke the on move hook for every moved value.
+    unsafe fn run_move(
+        dst: *mut c_void,
+        src: *mut c_void,
+        count: i32,
+        type_info: *const sys::ecs_type_info_t,
+        original: impl FnOnce(&MoveHooks) -> sys::ecs_move_t,
+    ) {
+        let ctx = unsafe { Self::binding_ctx(type_info) };
+
+        match ctx.move_hooks.as_ref().and_then(original) {
+            Some(move_hook) => unsafe { move_hook(dst, src, count, type_info) },
+            None => unsafe {
+                ptr::copy_nonoverlapping(src as *const T, dst as *mut T, count as usize);
+            },
+        }
+
+        let (Some(func), Some(run)) = (ctx.on_move, ctx.run_on_move) else {
+            return;
+        };
+        let component = unsafe { (*type_info).component };
+        // while a column grows the values move to a buffer that isn't part of the table yet
+        let Some((entities, row)) = (unsafe {
+            Self::find_row(ctx.world, component, dst)
+                .or_else(|| Self::find_row(ctx.world, component, src))
+        }) else {
+            return;
+        };
+
+        let arr = dst as *mut T;
+        for i in 0..count as usize {
+            let entity = unsafe { *entities.add(row + i) };
+            unsafe { run(func, ctx.world, entity, arr.add(i) as *mut c_void) };
+        }
+    }
+
+    /// Find the table column of `component` that stores the value at `ptr`, returns the entities
+    /// of the table and the row of the value.
+    ///
+    /// This visits every table with the component, which is the cost of the on move hook.
+    unsafe fn find_row(
+        world: *mut WorldT,
+        component: EntityT,
+        ptr: *const c_void,
+    ) -> Option<(*const EntityT, usize)> {
+        let size = std::mem::size_of::<T>();
+        let ptr = ptr as usize;
+
+        for id in [component, ecs_pair(component, ECS_WILDCARD)] {
+            let mut it = unsafe { sys::ecs_each_id(world, id) };
+            while unsafe { sys::ecs_each_next(&mut it) } {
+                let mut index = -1;
+                loop {
+                    index = unsafe {
+                        sys::ecs_search_offset(world, it.table, index + 1, id, ptr::null_mut())
+                    };
+                    if index == -1 {
+                        break;
+                    }
+                    let column = unsafe { sys::ecs_table_type_to_column_index(it.table, index) };
+                    if column == -1 {
+                        continue;
+                    }
+                    let start = unsafe { sys::ecs_table_get_column(it.table, column, 0) } as usize;
+                    let end = start + it.count as usize * size;
+                    if (start..end).contains(&ptr) {
+                        let entities = it.entities;
+                        unsafe { sys::ecs_iter_fini(&mut it) };
+                        return Some((entities, (ptr - start) / size));
+                    }
+                }
+            }
+        }
+        None
+    }
 }
 
 #[cfg(feature = "flecs_meta")]
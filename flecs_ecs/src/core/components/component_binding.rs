@@ -1,33 +1,69 @@
 use std::ffi::c_void;
 
+use crate::core::*;
+use crate::sys;
+
 type EcsCtxFreeT = unsafe extern "C" fn(*mut c_void);
 
+/// Type erased invoker of an `on_replace` hook, receives the hook, world, entity, old and new value.
+pub(crate) type ReplaceHookT =
+    unsafe fn(*mut c_void, *mut WorldT, EntityT, *mut c_void, *mut c_void);
+
+/// Type erased invoker of an `on_move` hook, receives the hook, world, entity and the moved value.
+pub(crate) type MoveHookT = unsafe fn(*mut c_void, *mut WorldT, EntityT, *mut c_void);
+
+/// Type erased invoker of an `on_dtor` hook, receives the hook and the value.
+pub(crate) type DtorHookT = unsafe fn(*mut c_void, *mut c_void);
+
+/// Move hooks of a component as they were before an `on_move` hook wrapped them.
+#[derive(Clone, Copy)]
+pub(crate) struct MoveHooks {
+    pub(crate) move_ctor: sys::ecs_move_t,
+    pub(crate) ctor_move_dtor: sys::ecs_move_t,
+    pub(crate) move_dtor: sys::ecs_move_t,
+}
+
 pub(crate) struct ComponentBindingCtx {
     pub(crate) on_add: Option<*mut c_void>,
     pub(crate) on_remove: Option<*mut c_void>,
     pub(crate) on_set: Option<*mut c_void>,
+    pub(crate) on_replace: Option<*mut c_void>,
+    pub(crate) on_move: Option<*mut c_void>,
+    pub(crate) ctor: Option<*mut c_void>,
+    pub(crate) on_dtor: Option<*mut c_void>,
     pub(crate) free_on_add: Option<EcsCtxFreeT>,
     pub(crate) free_on_remove: Option<EcsCtxFreeT>,
     pub(crate) free_on_set: Option<EcsCtxFreeT>,
+    pub(crate) free_on_replace: Option<EcsCtxFreeT>,
+    pub(crate) free_on_move: Option<EcsCtxFreeT>,
+    pub(crate) free_ctor: Option<EcsCtxFreeT>,
+    pub(crate) free_on_dtor: Option<EcsCtxFreeT>,
+    pub(crate) run_on_replace: Option<ReplaceHookT>,
+    pub(crate) run_on_move: Option<MoveHookT>,
+    pub(crate) run_on_dtor: Option<DtorHookT>,
+    pub(crate) move_hooks: Option<MoveHooks>,
+    /// The destructor from before the hooks wrapped it.
+    pub(crate) dtor: Option<sys::ecs_xtor_t>,
+    /// The world of the component, used by the on move hook to find the entity of a value.
+    pub(crate) world: *mut WorldT,
+}
+
+/// Free a hook closure with its matching free function, if both are set.
+fn free_hook(hook: &mut Option<*mut c_void>, free: &mut Option<EcsCtxFreeT>) {
+    if let (Some(hook), Some(free)) = (hook.take(), free.take()) {
+        unsafe { free(hook) };
+    }
 }
 
 impl Drop for ComponentBindingCtx {
     fn drop(&mut self) {
-        if let Some(on_add) = self.on_add {
-            if let Some(free_on_add) = self.free_on_add {
-                unsafe { free_on_add(on_add) };
-            }
-        }
-        if let Some(on_remove) = self.on_remove {
-            if let Some(free_on_remove) = self.free_on_remove {
-                unsafe { free_on_remove(on_remove) };
-            }
-        }
-        if let Some(on_set) = self.on_set {
-            if let Some(free_on_set) = self.free_on_set {
-                unsafe { free_on_set(on_set) };
-            }
-        }
+        self.free_on_add();
+        self.free_on_remove();
+        self.free_on_set();
+        self.free_on_replace();
+        self.free_on_move();
+        self.free_ctor();
+        self.free_on_dtor();
     }
 }
 
@@ -38,9 +74,23 @@ impl Default for ComponentBindingCtx {
             on_add: None,
             on_remove: None,
             on_set: None,
+            on_replace: None,
+            on_move: None,
+            ctor: None,
+            on_dtor: None,
             free_on_add: None,
             free_on_remove: None,
             free_on_set: None,
+            free_on_replace: None,
+            free_on_move: None,
+            free_ctor: None,
+            free_on_dtor: None,
+            run_on_replace: None,
+            run_on_move: None,
+            run_on_dtor: None,
+            move_hooks: None,
+            dtor: None,
+            world: std::ptr::null_mut(),
         }
     }
 }
@@ -60,6 +110,100 @@ impl ComponentBindingCtx {
             free_on_add,
             free_on_remove,
             free_on_set,
+            ..Default::default()
+        }
+    }
+
+    /// Free the on add hook, used before the hook is replaced.
+    pub(crate) fn free_on_add(&mut self) {
+        free_hook(&mut self.on_add, &mut self.free_on_add);
+    }
+
+    /// Free the on remove hook, used before the hook is replaced.
+    pub(crate) fn free_on_remove(&mut self) {
+        free_hook(&mut self.on_remove, &mut self.free_on_remove);
+    }
+
+    /// Free the on set hook, used before the hook is replaced.
+    pub(crate) fn free_on_set(&mut self) {
+        free_hook(&mut self.on_set, &mut self.free_on_set);
+    }
+
+    /// Free the on replace hook, used before the hook is replaced.
+    pub(crate) fn free_on_replace(&mut self) {
+        free_hook(&mut self.on_replace, &mut self.free_on_replace);
+        self.run_on_replace = None;
+    }
+
+    /// Free the on move hook, used before the hook is replaced.
+    pub(crate) fn free_on_move(&mut self) {
+        free_hook(&mut self.on_move, &mut self.free_on_move);
+        self.run_on_move = None;
+    }
+
+    /// Free the constructor, used before the constructor is replaced.
+    pub(crate) fn free_ctor(&mut self) {
+        free_hook(&mut self.ctor, &mut self.free_ctor);
+    }
+
+    /// Free the on dtor hook, used before the hook is replaced.
+    pub(crate) fn free_on_dtor(&mut self) {
+        free_hook(&mut self.on_dtor, &mut self.free_on_dtor);
+        self.run_on_dtor = None;
+    }
+
+    /// Get the binding context of the component `id`, if it has one.
+    ///
+    /// # Safety
+    ///
+    /// `world` must be a valid world pointer.
+    unsafe fn of<'w>(world: *mut WorldT, id: IdT) -> Option<&'w mut Self> {
+        let hooks = unsafe { sys::ecs_get_hooks_id(world, id) };
+        let ctx = unsafe { hooks.as_ref()?.binding_ctx as *mut ComponentBindingCtx };
+        unsafe { ctx.as_mut() }
+    }
+}
+
+/// An `on_replace` hook registered for a component, see [`Component::on_replace`].
+pub(crate) struct ReplaceHook {
+    func: *mut c_void,
+    run: ReplaceHookT,
+}
+
+impl ReplaceHook {
+    /// Get the `on_replace` hook registered for `id`, if any.
+    ///
+    /// # Safety
+    ///
+    /// `world` must be a valid world pointer.
+    pub(crate) unsafe fn get(world: *mut WorldT, id: IdT) -> Option<Self> {
+        let ctx = unsafe { ComponentBindingCtx::of(world, id)? };
+        Some(Self {
+            func: ctx.on_replace?,
+            run: ctx.run_on_replace?,
+        })
+    }
+
+    /// Invoke the hook with the value currently stored and the value that replaces it.
+    ///
+    /// # Safety
+    ///
+    /// `old` and `new` must point to valid values of the component type the hook was registered for.
+    pub(crate) unsafe fn invoke<T>(
+        &self,
+        world: *mut WorldT,
+        entity: EntityT,
+        old: *mut T,
+        new: &mut T,
+    ) {
+        unsafe {
+            (self.run)(
+                self.func,
+                world,
+                entity,
+                old as *mut c_void,
+                new as *mut T as *mut c_void,
+            );
         }
     }
 }
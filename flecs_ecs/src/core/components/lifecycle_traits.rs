@@ -124,7 +124,11 @@ extern "C" fn ctor<T: Default>(
 ///
 /// * C++ API: `dtor_impl`
 #[doc(alias = "dtor_impl")]
-extern "C" fn dtor<T>(ptr: *mut c_void, count: i32, _type_info: *const sys::ecs_type_info_t) {
+pub(crate) extern "C" fn dtor<T>(
+    ptr: *mut c_void,
+    count: i32,
+    _type_info: *const sys::ecs_type_info_t,
+) {
     ecs_assert!(
         check_type_info::<T>(_type_info),
         FlecsErrorCode::InternalError
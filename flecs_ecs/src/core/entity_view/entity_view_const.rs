@@ -885,6 +885,95 @@ impl<'a> EntityView<'a> {
         }
     }
 
+    /// gets a component the entity owns in a callback, ignoring values inherited from a base entity.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The component to get.
+    ///
+    /// # Returns
+    ///
+    /// - If the callback has ran, which is when the entity owns the component.
+    ///
+    /// # Panics
+    ///
+    /// - Like [`EntityView::get`], changes to the table of the entity in the callback panic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// #[flecs(inherit)]
+    /// struct MaxSpeed(f32);
+    ///
+    /// let world = World::new();
+    ///
+    /// let prefab = world.prefab().set(MaxSpeed(10.0));
+    /// let inst = world.entity().is_a_id(prefab);
+    ///
+    /// assert!(!inst.get_owned::<MaxSpeed>(|_| {}));
+    /// assert!(inst.get_inherited::<MaxSpeed>(|speed| assert_eq!(speed.0, 10.0)));
+    ///
+    /// inst.set(MaxSpeed(20.0));
+    /// assert!(inst.get_owned::<MaxSpeed>(|speed| assert_eq!(speed.0, 20.0)));
+    /// assert!(!inst.get_inherited::<MaxSpeed>(|_| {}));
+    /// ```
+    pub fn get_owned<T>(self, callback: impl FnOnce(&T)) -> bool
+    where
+        T: ComponentId + NotEmptyComponent + FlecsCastType<CastType = T>,
+    {
+        if !self.owns::<T>() {
+            return false;
+        }
+        self.get_source_value(callback)
+    }
+
+    /// gets a component the entity inherits from a base entity in a callback, ignoring values
+    /// the entity owns.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The component to get.
+    ///
+    /// # Returns
+    ///
+    /// - If the callback has ran, which is when the entity inherits the component and doesn't
+    ///   override it.
+    ///
+    /// # Panics
+    ///
+    /// - Like [`EntityView::get`], changes to the table of the entity in the callback panic.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::get_owned`]
+    pub fn get_inherited<T>(self, callback: impl FnOnce(&T)) -> bool
+    where
+        T: ComponentId + NotEmptyComponent + FlecsCastType<CastType = T>,
+    {
+        if self.owns::<T>() {
+            return false;
+        }
+        self.get_source_value(callback)
+    }
+
+    /// Get the component of the entity or its base entity with the read access of [`EntityView::get`].
+    fn get_source_value<T>(self, callback: impl FnOnce(&T)) -> bool
+    where
+        T: ComponentId + NotEmptyComponent + FlecsCastType<CastType = T>,
+    {
+        let mut has_value = false;
+        self.get::<Option<&T>>(|value| {
+            if let Some(value) = value {
+                has_value = true;
+                callback(value);
+            }
+        });
+        has_value
+    }
+
     /// Clones components and/or relationship(s) from an entity and returns it.
     /// each component type must be marked `&`. This helps Rust type checker to determine if it's a relationship.
     /// use `Option` wrapper to indicate if the component is optional.
@@ -2153,7 +2242,7 @@ impl<'a> EntityView<'a> {
     ///
     /// * C++ API: `entity_view::emit`
     #[doc(alias = "entity_view::emit")]
-    pub fn emit<T: ComponentId>(self, event: &T) {
+    pub fn emit<T: EventId>(self, event: &T) {
         self.world().event().target(self).emit(event);
     }
 
@@ -2198,7 +2287,7 @@ impl<'a> EntityView<'a> {
     ///
     /// * C++ API: `entity_view::enqueue`
     #[doc(alias = "entity_view::enqueue")]
-    pub fn enqueue<T: ComponentId>(self, event: T) {
+    pub fn enqueue<T: EventId>(self, event: T) {
         self.world().event().target(self).enqueue(event);
     }
 }
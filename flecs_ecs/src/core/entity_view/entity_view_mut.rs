@@ -1647,6 +1647,60 @@ impl<'a> EntityView<'a> {
         self.modified_id((First::id(self.world), second.into()));
     }
 
+    /// Modify a component in place and signal that it was modified.
+    ///
+    /// When the component has an on replace hook, the hook is invoked with a copy of the value
+    /// from before `func` and the modified value, see [`Component::on_replace`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entity doesn't have the component.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Health(u32);
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .component::<Health>()
+    ///     .on_replace(|_entity, old, new| {
+    ///         // never heal above the previous value
+    ///         new.0 = new.0.min(old.0);
+    ///     });
+    ///
+    /// let e = world.entity().set(Health(10));
+    /// e.modify::<Health>(|health| health.0 = 20);
+    /// e.get::<&Health>(|health| assert_eq!(health.0, 10));
+    /// ```
+    pub fn modify<T>(self, func: impl FnOnce(&mut T)) -> Self
+    where
+        T: ComponentId + NotEmptyComponent + FlecsCastType<CastType = T> + Clone,
+    {
+        let world = self.world.world_ptr_mut();
+        let id = T::id(self.world);
+        let replace_hook = unsafe { ReplaceHook::get(world, id) };
+
+        let mut old = None;
+        self.get::<&mut T>(|value| {
+            if replace_hook.is_some() {
+                old = Some(value.clone());
+            }
+            func(value);
+        });
+
+        if let (Some(hook), Some(mut old)) = (replace_hook, old) {
+            let new = unsafe { sys::ecs_get_mut_id(world, *self.id, id) as *mut T };
+            unsafe { hook.invoke(world, *self.id, &mut old, &mut *new) };
+        }
+        self.modified_id(id);
+        self
+    }
+
     /// Get a reference to a component or pair.
     ///
     /// A reference allows for quick and safe access to a component value, and is
@@ -1753,9 +1807,96 @@ impl<'a> EntityView<'a> {
     ///
     /// # See also
     ///
+    /// * [`EntityView::try_destruct`]
     /// * C++ API: `entity::destruct`
     #[doc(alias = "entity::destruct")]
     pub fn destruct(self) {
         unsafe { sys::ecs_delete(self.world.world_ptr_mut(), *self.id) }
     }
+
+    /// Delete an entity, unless it is in use by an id with the [`CleanupPolicy::Panic`] policy.
+    ///
+    /// Deleting an entity that is in use by such an id with [`EntityView::destruct`] aborts the
+    /// application. This operation checks the entity, and the entities that would be deleted
+    /// along with it such as its children, before deleting it.
+    ///
+    /// An entity with the `Panic` policy for `OnDelete` can't be deleted once it's registered as
+    /// id, which happens when the policy is set.
+    ///
+    /// # Returns
+    ///
+    /// The id that is in use if the entity wasn't deleted.
+    ///
+    /// # See also
+    ///
+    /// * [`Component::on_delete`]
+    /// * [`Component::on_delete_target`]
+    pub fn try_destruct(self) -> Result<(), DeleteError> {
+        if let Some(error) = find_delete_error(self.world, *self.id) {
+            return Err(error);
+        }
+        self.destruct();
+        Ok(())
+    }
+}
+
+/// Returns the first id with the `Panic` cleanup policy that prevents deleting the entity.
+fn find_delete_error(world: WorldRef, entity: EntityT) -> Option<DeleteError> {
+    let world_ptr = world.world_ptr_mut();
+    let each_source = |id: IdT, func: &mut dyn FnMut(EntityT)| unsafe {
+        let mut it = sys::ecs_each_id(world_ptr, id);
+        while sys::ecs_each_next(&mut it) {
+            for i in 0..it.count as usize {
+                func(*it.entities.add(i));
+            }
+        }
+    };
+    let with_policy = |cleanup_trait: EntityT, policy: CleanupPolicy| {
+        let mut relationships = Vec::new();
+        each_source(ecs_pair(cleanup_trait, policy.id()), &mut |rel| {
+            relationships.push(rel);
+        });
+        relationships
+    };
+    let in_use = |id: IdT| unsafe { sys::ecs_count_id(world_ptr, id) } > 0;
+    // flecs refuses to delete an id with the Panic policy as soon as it has an id record, even
+    // when no entity has the id
+    let has_record = |id: IdT| unsafe { sys::ecs_id_get_flags(world_ptr, id) } != 0;
+    let has = |entity: EntityT, id: IdT| unsafe { sys::ecs_has_id(world_ptr, entity, id) };
+
+    let panic_on_target = with_policy(ECS_ON_DELETE_TARGET, CleanupPolicy::Panic);
+    let delete_on_target = with_policy(ECS_ON_DELETE_TARGET, CleanupPolicy::Delete);
+
+    let mut visited = std::collections::HashSet::new();
+    let mut pending = vec![entity];
+    while let Some(entity) = pending.pop() {
+        if !visited.insert(entity) {
+            continue;
+        }
+
+        let uses = [entity, ecs_pair(entity, ECS_WILDCARD)];
+        if has(entity, ecs_pair(ECS_ON_DELETE, ECS_PANIC)) {
+            if let Some(&id) = uses.iter().find(|&&id| has_record(id)) {
+                return Some(DeleteError { entity, id });
+            }
+        }
+        if let Some(id) = panic_on_target
+            .iter()
+            .map(|&rel| ecs_pair(rel, entity))
+            .find(|&id| in_use(id))
+        {
+            return Some(DeleteError { entity, id });
+        }
+
+        // entities that are deleted along with the entity
+        for &rel in &delete_on_target {
+            each_source(ecs_pair(rel, entity), &mut |source| pending.push(source));
+        }
+        if has(entity, ecs_pair(ECS_ON_DELETE, ECS_DELETE)) {
+            for id in uses {
+                each_source(id, &mut |source| pending.push(source));
+            }
+        }
+    }
+    None
 }
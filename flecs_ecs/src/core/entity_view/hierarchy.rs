@@ -0,0 +1,407 @@
+//! Hierarchy utilities built on top of the `ChildOf` relationship.
+//!
+//! Children are returned in the order flecs stores them in, which changes when children are added
+//! or removed components. Entities that have the [`OrderedChildren`] component keep their children
+//! in the order they were inserted in with [`EntityView::insert_child_at`].
+
+use std::collections::{HashSet, VecDeque};
+
+use flecs_ecs_derive::Component;
+
+use crate::core::*;
+use crate::sys;
+
+/// The order of the children of an entity, added by [`EntityView::insert_child_at`].
+///
+/// Children that are not in the list, such as children that were added with `child_of`, come
+/// after the children in the list.
+#[derive(Component, Clone, Default, Debug)]
+pub struct OrderedChildren {
+    children: Vec<Entity>,
+}
+
+impl OrderedChildren {
+    /// Returns the children in order, this may include entities that are no longer a child.
+    pub fn as_slice(&self) -> &[Entity] {
+        &self.children
+    }
+}
+
+/// Controls how a walk over a hierarchy continues after visiting an entity.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Walk {
+    /// Visit the children of the entity.
+    Continue,
+    /// Don't visit the children of the entity, but continue with the other entities.
+    SkipChildren,
+    /// Stop the walk.
+    Stop,
+}
+
+/// Iterator over the parents of an entity, see [`EntityView::ancestors`].
+pub struct Ancestors<'a> {
+    current: EntityView<'a>,
+}
+
+impl<'a> Iterator for Ancestors<'a> {
+    type Item = EntityView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parent = self.current.parent();
+        if parent.id == 0 {
+            return None;
+        }
+        self.current = parent;
+        Some(parent)
+    }
+}
+
+/// Iterator over the children of an entity, see [`EntityView::children`].
+///
+/// The children are collected when the iterator is created, so the hierarchy can be changed while
+/// iterating.
+pub struct Children<'a> {
+    world: WorldRef<'a>,
+    children: std::vec::IntoIter<Entity>,
+}
+
+impl<'a> Iterator for Children<'a> {
+    type Item = EntityView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let child = self.children.next()?;
+        Some(EntityView::new_from(self.world, child))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.children.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Children<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let child = self.children.next_back()?;
+        Some(EntityView::new_from(self.world, child))
+    }
+}
+
+impl ExactSizeIterator for Children<'_> {}
+
+/// Depth first iterator over the descendants of an entity, see [`EntityView::descendants`].
+pub struct Descendants<'a> {
+    stack: Vec<Children<'a>>,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = EntityView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let children = self.stack.last_mut()?;
+            match children.next() {
+                Some(child) => {
+                    self.stack.push(child.children());
+                    return Some(child);
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Hierarchy mixin implementation
+impl<'a> EntityView<'a> {
+    fn is_parent_of(self, child: Entity) -> bool {
+        let world = self.world.world_ptr_mut();
+        unsafe {
+            sys::ecs_is_alive(world, *child)
+                && sys::ecs_has_id(world, *child, ecs_pair(ECS_CHILD_OF, *self.id))
+        }
+    }
+
+    /// Returns an iterator over the children of the entity.
+    ///
+    /// If the entity has [`OrderedChildren`], the children are returned in that order.
+    pub fn children(self) -> Children<'a> {
+        let mut children: Vec<Entity> = Vec::new();
+        // checked up front, registering the component while the entity is locked by get panics
+        if self.has::<OrderedChildren>() {
+            self.get::<&OrderedChildren>(|ordered| {
+                children.extend(
+                    ordered
+                        .children
+                        .iter()
+                        .copied()
+                        .filter(|&child| self.is_parent_of(child)),
+                );
+            });
+        }
+
+        let listed: HashSet<Entity> = children.iter().copied().collect();
+        self.each_child(|child| {
+            if !listed.contains(&child.id) {
+                children.push(child.id);
+            }
+        });
+
+        Children {
+            world: self.world,
+            children: children.into_iter(),
+        }
+    }
+
+    /// Returns the index of the entity in the children of its parent.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the entity has no parent.
+    pub fn child_index(self) -> Option<usize> {
+        let parent = self.parent();
+        if parent.id == 0 {
+            return None;
+        }
+        parent.children().position(|child| child.id == self.id)
+    }
+
+    /// Insert a child at a position in the children of the entity.
+    ///
+    /// The child is added to the entity with the `ChildOf` relationship if it wasn't a child yet, or
+    /// moved to the position if it was. This adds [`OrderedChildren`] to the entity, which keeps the
+    /// order of the children stable.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The position of the child in the children of the entity.
+    /// * `child` - The child to insert.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is larger than the number of other children.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    ///
+    /// let list = world.entity();
+    /// let a = world.entity_named("a");
+    /// let b = world.entity_named("b");
+    /// let c = world.entity_named("c");
+    ///
+    /// list.insert_child_at(0, b)
+    ///     .insert_child_at(0, a)
+    ///     .insert_child_at(2, c);
+    ///
+    /// let names: Vec<_> = list.children().map(|child| child.name()).collect();
+    /// assert_eq!(names, ["a", "b", "c"]);
+    /// assert_eq!(c.child_index(), Some(2));
+    /// ```
+    pub fn insert_child_at(self, index: usize, child: impl Into<Entity>) -> Self {
+        let child = child.into();
+        let mut children: Vec<Entity> = self
+            .children()
+            .map(|other| other.id)
+            .filter(|&other| other != child)
+            .collect();
+        children.insert(index, child);
+
+        EntityView::new_from(self.world, child).child_of_id(self);
+        self.set(OrderedChildren { children })
+    }
+
+    /// Move the entity to a new parent, keeping its name.
+    ///
+    /// If the new parent has [`OrderedChildren`], the entity is added as its last child.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The new parent of the entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new parent already has a different child with the name of the entity, see
+    /// [`EntityView::reparent_keep_name`].
+    pub fn reparent(self, parent: impl Into<Entity>) -> Self {
+        self.reparent_keep_name(parent)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Move the entity to a new parent, keeping its name, unless the new parent already has a
+    /// different child with that name.
+    ///
+    /// If the new parent has [`OrderedChildren`], the entity is added as its last child.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The new parent of the entity.
+    ///
+    /// # Returns
+    ///
+    /// The child with the name of the entity if the entity wasn't moved.
+    pub fn reparent_keep_name(self, parent: impl Into<Entity>) -> Result<Self, NameConflictError> {
+        let parent = EntityView::new_from(self.world, parent.into());
+
+        if let Some(name) = self.get_name() {
+            if let Some(other) = parent.try_lookup(name) {
+                if other.id != self.id {
+                    return Err(NameConflictError {
+                        entity: *self.id,
+                        parent: *parent.id,
+                        other: *other.id,
+                        name: name.to_owned(),
+                    });
+                }
+            }
+        }
+
+        let old_parent = self.parent();
+        if old_parent.id != 0 && old_parent.id != parent.id && old_parent.has::<OrderedChildren>() {
+            old_parent.get::<&mut OrderedChildren>(|ordered| {
+                ordered.children.retain(|&child| child != self.id);
+            });
+        }
+
+        if parent.has::<OrderedChildren>() {
+            let index = parent
+                .children()
+                .filter(|child| child.id != self.id)
+                .count();
+            parent.insert_child_at(index, self);
+            Ok(self)
+        } else {
+            Ok(self.child_of_id(parent))
+        }
+    }
+
+    /// Returns an iterator over the parent of the entity, its parent and so on, up to the root.
+    pub fn ancestors(self) -> Ancestors<'a> {
+        Ancestors { current: self }
+    }
+
+    /// Returns a depth first iterator over the children of the entity, their children and so on.
+    ///
+    /// Children are visited in the order of [`EntityView::children`], before their own children.
+    pub fn descendants(self) -> Descendants<'a> {
+        Descendants {
+            stack: vec![self.children()],
+        }
+    }
+
+    /// Visit the descendants of the entity depth first, in the order of [`EntityView::descendants`].
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function invoked for each descendant, which returns how the walk continues.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    ///
+    /// let root = world.entity();
+    /// let hidden = world.entity_named("hidden").child_of_id(root);
+    /// world.entity_named("hidden_child").child_of_id(hidden);
+    /// world.entity_named("visible").child_of_id(root);
+    ///
+    /// let mut visited = Vec::new();
+    /// root.walk_depth_first(|entity| {
+    ///     visited.push(entity.name());
+    ///     if entity.name() == "hidden" {
+    ///         Walk::SkipChildren
+    ///     } else {
+    ///         Walk::Continue
+    ///     }
+    /// });
+    /// assert!(!visited.contains(&"hidden_child"));
+    /// assert_eq!(visited.len(), 2);
+    /// ```
+    pub fn walk_depth_first(self, mut func: impl FnMut(EntityView<'a>) -> Walk) {
+        let mut stack = vec![self.children()];
+        while let Some(children) = stack.last_mut() {
+            let Some(child) = children.next() else {
+                stack.pop();
+                continue;
+            };
+            match func(child) {
+                Walk::Continue => stack.push(child.children()),
+                Walk::SkipChildren => {}
+                Walk::Stop => return,
+            }
+        }
+    }
+
+    /// Visit the descendants of the entity breadth first, so all entities at a depth are visited
+    /// before the entities at the next depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function invoked for each descendant, which returns how the walk continues.
+    pub fn walk_breadth_first(self, mut func: impl FnMut(EntityView<'a>) -> Walk) {
+        let mut pending: VecDeque<EntityView<'a>> = self.children().collect();
+        while let Some(entity) = pending.pop_front() {
+            match func(entity) {
+                Walk::Continue => pending.extend(entity.children()),
+                Walk::SkipChildren => {}
+                Walk::Stop => return,
+            }
+        }
+    }
+
+    /// Clone the entity and its descendants.
+    ///
+    /// The clone of the entity has the same parent as the entity and no name, the clones of the
+    /// descendants keep their names and the order of the children.
+    ///
+    /// # Returns
+    ///
+    /// The clone of the entity.
+    pub fn clone_subtree(self) -> EntityView<'a> {
+        let (clone, _) = self.duplicate_keep_name();
+        self.clone_children_into(clone);
+        clone
+    }
+
+    // `duplicate` copies the component values but not the name, and flecs runs the remove hooks
+    // of the name on the source while copying, which clears it. Restore the name of the source.
+    fn duplicate_keep_name(self) -> (EntityView<'a>, Option<String>) {
+        let name = self.get_name().map(str::to_owned);
+        let clone = self.duplicate(true);
+        if let Some(name) = &name {
+            self.set_name(name);
+        }
+        (clone, name)
+    }
+
+    fn clone_children_into(self, dest: EntityView<'a>) {
+        let mut children = Vec::new();
+        for child in self.children() {
+            let (clone, name) = child.duplicate_keep_name();
+            clone.child_of_id(dest);
+            if let Some(name) = name {
+                clone.set_name(&name);
+            }
+            child.clone_children_into(clone);
+            children.push(clone.id);
+        }
+
+        if self.has::<OrderedChildren>() {
+            dest.set(OrderedChildren { children });
+        }
+    }
+
+    /// Delete the descendants of the entity, without deleting the entity itself.
+    ///
+    /// Deleting an entity with [`EntityView::destruct`] deletes its descendants as well.
+    pub fn delete_children(self) {
+        unsafe {
+            sys::ecs_delete_with(self.world.world_ptr_mut(), ecs_pair(ECS_CHILD_OF, *self.id));
+        }
+        self.remove::<OrderedChildren>();
+    }
+}
@@ -1,5 +1,7 @@
 mod entity_view_const;
 mod entity_view_impl;
 mod entity_view_mut;
+mod hierarchy;
 
 pub use entity_view_const::*;
+pub use hierarchy::*;
@@ -4,11 +4,123 @@ use std::{alloc::Layout, os::raw::c_void};
 use crate::core::*;
 use crate::sys;
 
+/// Types that are emitted as events, usually implemented with `#[derive(Event)]`.
+///
+/// Events are not components, each event type is identified by an entity that is created the first
+/// time the event is used in a world. The event type identifies the event, so the payload passed to
+/// [`EventBuilder::emit`] always has the type observers receive in [`ObserverBuilder::each_event`].
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Event)]
+/// struct Damage {
+///     amount: u32,
+/// }
+///
+/// #[derive(Component)]
+/// struct Health {
+///     value: u32,
+/// }
+///
+/// let world = World::new();
+///
+/// world
+///     .observer::<Damage, &mut Health>()
+///     .each_event(|_entity, damage, health| {
+///         health.value -= damage.amount;
+///     });
+///
+/// let entity = world.entity().set(Health { value: 100 });
+///
+/// world
+///     .event()
+///     .add::<Health>()
+///     .target(entity)
+///     .emit(&Damage { amount: 10 });
+///
+/// entity.get::<&Health>(|health| assert_eq!(health.value, 90));
+/// ```
+pub trait Event: EventId {}
+
+/// Types that identify an event, implemented for components and for types that derive [`Event`].
+pub trait EventId: Sized + 'static {
+    /// Returns the entity of the event in the world.
+    fn event_id<'a>(world: impl IntoWorld<'a>) -> EntityT;
+}
+
+impl<T: ComponentId> EventId for T {
+    fn event_id<'a>(world: impl IntoWorld<'a>) -> EntityT {
+        T::id(world)
+    }
+}
+
+/// Returns the entity of event type `E`, which is created the first time it is requested.
+///
+/// Used by `#[derive(Event)]`.
+#[doc(hidden)]
+pub fn event_entity<'a, E: 'static>(world: impl IntoWorld<'a>) -> EntityT {
+    let world = world.world().real_world();
+    let world_ctx = world.world_ctx_mut();
+    *world_ctx
+        .events
+        .entry(std::any::TypeId::of::<E>())
+        .or_insert_with(|| unsafe {
+            sys::ecs_entity_init(world.world_ptr_mut(), &Default::default())
+        })
+}
+
+/// Invokes a closure for the event that invoked an observer, see [`Iter::dispatch_event`].
+pub struct EventDispatch<'a> {
+    world: WorldRef<'a>,
+    event: EntityT,
+    dispatched: bool,
+}
+
+impl<'a> EventDispatch<'a> {
+    pub(crate) fn new(world: WorldRef<'a>, event: EntityT) -> Self {
+        Self {
+            world,
+            event,
+            dispatched: false,
+        }
+    }
+
+    /// Invoke `func` if the observer was invoked for event `E`.
+    ///
+    /// # Type parameters
+    ///
+    /// * `E` - The event type
+    pub fn on<E: EventId>(mut self, func: impl FnOnce()) -> Self {
+        if !self.dispatched && self.event == E::event_id(self.world) {
+            self.dispatched = true;
+            func();
+        }
+        self
+    }
+
+    /// Invoke `func` if the observer was invoked for none of the events passed to
+    /// [`EventDispatch::on`].
+    pub fn otherwise(self, func: impl FnOnce()) {
+        if !self.dispatched {
+            func();
+        }
+    }
+
+    /// Returns whether the observer was invoked for one of the events passed to
+    /// [`EventDispatch::on`].
+    pub fn is_dispatched(&self) -> bool {
+        self.dispatched
+    }
+}
+
 /// A strongly-typed interface wrapper around `EventBuilderUntyped` for constructing events with specific data.
 ///
 /// # Type parameters
 ///
-/// * `T` - The type of the event data to set for the event, which must implement `ComponentId`.
+/// * `T` - The type of the event data to set for the event, which must implement `EventId`.
 ///
 /// Ensures the use of appropriate data types for events, enhancing type safety and data integrity.
 /// This design aims to prevent the utilization of incompatible components as event data,
@@ -18,10 +130,11 @@ pub struct EventBuilder<'a, T = ()> {
     pub(crate) desc: sys::ecs_event_desc_t,
     pub(crate) ids: TypeT,
     pub(crate) ids_array: [IdT; sys::FLECS_EVENT_DESC_MAX as usize],
+    pub(crate) bubble: EntityT,
     _phantom: std::marker::PhantomData<T>,
 }
 
-impl<'a, T: ComponentId> EventBuilder<'a, T> {
+impl<'a, T: EventId> EventBuilder<'a, T> {
     /// Create a new typed `EventBuilder`
     ///
     /// # Arguments
@@ -39,9 +152,10 @@ impl<'a, T: ComponentId> EventBuilder<'a, T> {
             desc: Default::default(),
             ids: Default::default(),
             ids_array: Default::default(),
+            bubble: 0,
             _phantom: PhantomData,
         };
-        obj.desc.event = T::id(world);
+        obj.desc.event = T::event_id(world);
         obj
     }
 
@@ -69,6 +183,7 @@ impl<'a, T: ComponentId> EventBuilder<'a, T> {
             desc: Default::default(),
             ids: Default::default(),
             ids_array: Default::default(),
+            bubble: 0,
             _phantom: PhantomData::<()>,
         };
         obj.desc.event = *event.into();
@@ -182,6 +297,69 @@ impl<'a, T: ComponentId> EventBuilder<'a, T> {
         self
     }
 
+    /// Bubble the event up a relationship after emitting it for the target entity.
+    ///
+    /// The event is emitted for the target of the relationship, then for its target and so on,
+    /// until an entity has no target or an observer calls [`Iter::stop_propagation`].
+    /// Only applies to [`EventBuilder::emit`].
+    ///
+    /// # Type parameters
+    ///
+    /// * `R` - The relationship to bubble the event up, such as `flecs::ChildOf`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Event)]
+    /// struct Click;
+    ///
+    /// #[derive(Component)]
+    /// struct Widget {
+    ///     name: &'static str,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// world.observer::<Click, &Widget>().each(|widget| {
+    ///     println!("{} clicked", widget.name);
+    /// });
+    ///
+    /// let window = world.entity().set(Widget { name: "window" });
+    /// let button = world
+    ///     .entity()
+    ///     .child_of_id(window)
+    ///     .set(Widget { name: "button" });
+    ///
+    /// // invokes the observer for the button, then for the window
+    /// world
+    ///     .event()
+    ///     .add::<Widget>()
+    ///     .target(button)
+    ///     .bubble::<flecs::ChildOf>()
+    ///     .emit(&Click);
+    /// ```
+    pub fn bubble<R>(&mut self) -> &mut Self
+    where
+        R: ComponentId,
+    {
+        let world = self.world;
+        self.bubble_id(R::id(world))
+    }
+
+    /// Bubble the event up a relationship after emitting it for the target entity.
+    ///
+    /// See [`EventBuilder::bubble`].
+    ///
+    /// # Arguments
+    ///
+    /// * `relationship` - The relationship to bubble the event up
+    pub fn bubble_id(&mut self, relationship: impl Into<Entity>) -> &mut Self {
+        self.bubble = *relationship.into();
+        self
+    }
+
     pub fn emit(&mut self, data: &T) {
         let ids = &mut self.ids;
         let ids_array = &mut self.ids_array;
@@ -191,7 +369,42 @@ impl<'a, T: ComponentId> EventBuilder<'a, T> {
         desc.const_param = data as *const T as *const c_void;
         desc.ids = ids;
         desc.observable = world.real_world().world_ptr_mut() as *mut c_void;
-        unsafe { sys::ecs_emit(world.world_ptr_mut(), desc) };
+
+        if self.bubble == 0 {
+            unsafe { sys::ecs_emit(world.world_ptr_mut(), desc) };
+            return;
+        }
+
+        ecs_assert!(
+            desc.entity != 0,
+            FlecsErrorCode::InvalidOperation,
+            "bubbling an event requires a target entity"
+        );
+
+        // restored afterwards, observers may bubble events of their own
+        let stage = world.world_ptr();
+        let real_world = world.real_world();
+        let propagation = &real_world.world_ctx().propagation;
+        let stopped = propagation.with(stage, |propagation| {
+            std::mem::replace(&mut propagation.stopped, false)
+        });
+
+        let target = desc.entity;
+        while desc.entity != 0 {
+            // set to the entity by the previous emit
+            desc.table = std::ptr::null_mut();
+            desc.offset = 0;
+            desc.count = 0;
+            unsafe { sys::ecs_emit(world.world_ptr_mut(), desc) };
+            if propagation.with(stage, |propagation| propagation.stopped) {
+                break;
+            }
+            desc.entity =
+                unsafe { sys::ecs_get_target(world.world_ptr_mut(), desc.entity, self.bubble, 0) };
+        }
+
+        desc.entity = target;
+        propagation.with(stage, |propagation| propagation.stopped = stopped);
     }
 
     pub fn enqueue(&mut self, data: T) {
@@ -201,7 +414,7 @@ impl<'a, T: ComponentId> EventBuilder<'a, T> {
         let world = self.world;
         ids.array = ids_array.as_mut_ptr();
 
-        if !T::IS_TAG {
+        if std::mem::size_of::<T>() != 0 {
             desc.param = Box::leak(Box::new(data)) as *mut T as *mut c_void;
         }
 
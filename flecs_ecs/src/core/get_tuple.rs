@@ -62,6 +62,8 @@ pub trait GetTupleTypeOperation {
     type OnlyType: IntoComponentId;
     const IS_OPTION: bool;
     const IS_IMMUTABLE: bool;
+    /// Whether only the presence of the component is checked, without fetching its data.
+    const IS_HAS: bool = false;
 
     fn create_tuple_data<'a>(array_components_data: *mut c_void) -> Self::ActualType<'a>;
 }
@@ -136,6 +138,21 @@ where
     }
 }
 
+impl<T> GetTupleTypeOperation for Has<T>
+where
+    T: ComponentId,
+{
+    type ActualType<'e> = bool;
+    type OnlyType = T;
+    const IS_OPTION: bool = true;
+    const IS_IMMUTABLE: bool = true;
+    const IS_HAS: bool = true;
+
+    fn create_tuple_data<'a>(array_components_data: *mut c_void) -> Self::ActualType<'a> {
+        !array_components_data.is_null()
+    }
+}
+
 #[diagnostic::on_unimplemented(
     message = "`there is a problem with {Self}`. Please double check the signature and if the singular types are not empty components (tags) or the relationship are not made of 2 tags",
     label = "Failure in get signature",
@@ -189,7 +206,14 @@ where
         let id = <A::OnlyType as IntoComponentId>::get_id(world);
         let mut has_all_components = true;
         
-        let component_ptr = if A::OnlyType::IS_ENUM {
+        let component_ptr = if A::IS_HAS {
+            // the data is never read, any non-null pointer marks the component as present
+            if unsafe { sys::ecs_has_id(world_ptr, entity, id) } {
+                std::ptr::NonNull::dangling().as_ptr()
+            } else {
+                std::ptr::null_mut()
+            }
+        } else if A::OnlyType::IS_ENUM {
 
             let target: IdT = unsafe {
                 sys::ecs_get_target(world_ptr, entity, id, 0)
@@ -320,7 +344,14 @@ macro_rules! impl_get_tuple {
                 $(
                     let id = <$t::OnlyType as IntoComponentId>::get_id(world_ref);
 
-                    let component_ptr = if $t::OnlyType::IS_ENUM {
+                    let component_ptr = if $t::IS_HAS {
+                        // the data is never read, any non-null pointer marks the component as present
+                        if unsafe { sys::ecs_has_id(world_ptr, entity, id) } {
+                            std::ptr::NonNull::dangling().as_ptr()
+                        } else {
+                            std::ptr::null_mut()
+                        }
+                    } else if $t::OnlyType::IS_ENUM {
 
                         let target: IdT = unsafe {
                             sys::ecs_get_target(world_ptr, entity, id, 0)
@@ -16,7 +16,7 @@ pub struct Iter<'a, const IS_RUN: bool = true, P = ()> {
 
 impl<'a, const IS_RUN: bool, P> Iter<'a, IS_RUN, P>
 where
-    P: ComponentId,
+    P: EventId,
 {
     pub fn world(&self) -> WorldRef<'a> {
         unsafe { WorldRef::from_ptr(self.iter.world) }
@@ -72,6 +72,50 @@ where
         EntityView::new_from(self.world(), self.iter.event)
     }
 
+    /// Dispatch on the event that invoked the observer, by invoking the closure passed to
+    /// [`EventDispatch::on`] for that event.
+    ///
+    /// For observers of multiple events, or monitors which are invoked with `OnAdd` and
+    /// `OnRemove`, this allows handling each event without comparing ids.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .observer::<flecs::OnAdd, &Position>()
+    ///     .add_event::<flecs::OnRemove>()
+    ///     .each_iter(|it, index, _| {
+    ///         it.dispatch_event()
+    ///             .on::<flecs::OnAdd>(|| println!("added to {}", it.entity(index)))
+    ///             .on::<flecs::OnRemove>(|| println!("removed from {}", it.entity(index)));
+    ///     });
+    ///
+    /// let entity = world.entity().set(Position { x: 1.0, y: 2.0 });
+    /// entity.remove::<Position>();
+    /// ```
+    pub fn dispatch_event(&self) -> EventDispatch<'a> {
+        EventDispatch::new(self.world(), self.iter.event)
+    }
+
+    /// Returns whether the observer was invoked for event `E`.
+    ///
+    /// # Type parameters
+    ///
+    /// * `E` - The event type
+    pub fn is_event<E: EventId>(&self) -> bool {
+        self.iter.event == E::event_id(self.world())
+    }
+
     /// Wrap the event id in the iterator in an `Id` object
     ///
     /// # See also
@@ -258,7 +302,7 @@ where
     #[doc(alias = "iter::param")]
     pub fn param(&self) -> &P {
         ecs_assert!(
-            !P::IS_TAG,
+            std::mem::size_of::<P>() != 0,
             FlecsErrorCode::InvalidParameter,
             "cannot access tag data, no payload provided"
         );
@@ -270,7 +314,7 @@ where
             "Tried to get param on an iterator where it was null."
         );
 
-        if P::IS_TAG {
+        if std::mem::size_of::<P>() == 0 {
             panic!("cannot access tag data, no payload provided");
         }
 
@@ -280,7 +324,7 @@ where
     #[doc(alias = "iter::param")]
     pub fn param_mut(&mut self) -> &mut P {
         ecs_assert!(
-            !P::IS_TAG,
+            std::mem::size_of::<P>() != 0,
             FlecsErrorCode::InvalidParameter,
             "cannot access tag data, no payload provided"
         );
@@ -292,7 +336,7 @@ where
             "Tried to get param on an iterator where it was null."
         );
 
-        if P::IS_TAG {
+        if std::mem::size_of::<P>() == 0 {
             panic!("cannot access tag data, no payload provided");
         }
 
@@ -660,6 +704,27 @@ where
         unsafe { sys::ecs_iter_skip(self.iter) };
     }
 
+    /// Stop the event that invoked the observer from propagating.
+    ///
+    /// Observers aren't invoked anymore for entities the event reaches by traversing up, such as
+    /// the children of the entity for an observer with a `parent()` term, and an event emitted
+    /// with [`EventBuilder::bubble`] doesn't bubble up to the next entity.
+    /// The remaining observers for the current entity are still invoked.
+    ///
+    /// Only ordered observers, see [`ObserverBuilder::priority`], and observers with a term that
+    /// traverses up skip the invocations for a stopped event, other observers are dispatched by
+    /// flecs directly.
+    pub fn stop_propagation(&self) {
+        let stopped = StoppedEvent::new(self.iter);
+        self.real_world()
+            .world_ctx()
+            .propagation
+            .with(self.iter.world, |propagation| {
+                propagation.stopped = true;
+                propagation.stopped_event = Some(stopped);
+            });
+    }
+
     /// # Returns
     ///
     /// Return group id for current table
@@ -731,7 +796,7 @@ where
             let result = {
                 if let Some(next) = self.iter.next {
                     //sets flag invalid
-                    unsafe { next(self.iter) }
+                    unsafe { next_result(self.iter.query, self.iter, |iter| next(iter)) }
                 } else {
                     self.iter.flags &= !sys::EcsIterIsValid;
                     return false;
@@ -775,7 +840,7 @@ pub struct IterIterator<'a, const IS_RUN: bool, P> {
 
 impl<'a, const IS_RUN: bool, P> Iterator for IterIterator<'a, IS_RUN, P>
 where
-    P: ComponentId,
+    P: EventId,
 {
     type Item = usize;
 
@@ -12,7 +12,9 @@ pub struct ArrayElement {
 pub struct ComponentsData<T: Iterable, const LEN: usize> {
     pub array_components: [*mut u8; LEN],
     pub is_ref_array_components: [bool; LEN],
+    pub array_targets: [EntityT; LEN],
     pub is_any_array_a_ref: bool,
+    pub world: *mut WorldT,
     _marker: PhantomData<T>,
 }
 
@@ -28,17 +30,21 @@ impl<T: Iterable, const LEN: usize> ComponentPointers<T> for ComponentsData<T, L
     fn new(iter: &IterT) -> Self {
         let mut array_components = [std::ptr::null::<u8>() as *mut u8; LEN];
         let mut is_ref_array_components = [false; LEN];
+        let mut array_targets = [0; LEN];
 
         let is_any_array_a_ref = T::populate_array_ptrs(
             iter,
             &mut array_components[..],
             &mut is_ref_array_components[..],
+            &mut array_targets[..],
         );
 
         Self {
             array_components,
             is_ref_array_components,
+            array_targets,
             is_any_array_a_ref,
+            world: iter.world,
             _marker: PhantomData::<T>,
         }
     }
@@ -46,24 +52,38 @@ impl<T: Iterable, const LEN: usize> ComponentPointers<T> for ComponentsData<T, L
     fn get_tuple(&mut self, index: usize) -> T::TupleType<'_> {
         if self.is_any_array_a_ref {
             T::create_tuple_with_ref(
+                self.world,
                 &self.array_components[..],
                 &self.is_ref_array_components[..],
+                &self.array_targets[..],
                 index,
             )
         } else {
-            T::create_tuple(&self.array_components[..], index)
+            T::create_tuple(
+                self.world,
+                &self.array_components[..],
+                &self.array_targets[..],
+                index,
+            )
         }
     }
 
     fn get_slice(&mut self, count: usize) -> T::TupleSliceType<'_> {
         if self.is_any_array_a_ref {
             T::create_tuple_slices_with_ref(
+                self.world,
                 &self.array_components[..],
                 &self.is_ref_array_components[..],
+                &self.array_targets[..],
                 count,
             )
         } else {
-            T::create_tuple_slices(&self.array_components[..], count)
+            T::create_tuple_slices(
+                self.world,
+                &self.array_components[..],
+                &self.array_targets[..],
+                count,
+            )
         }
     }
 }
@@ -77,21 +97,47 @@ pub trait IterableTypeOperation {
     type OnlyType: IntoComponentId;
     type OnlyPairType: ComponentId;
     const ONE: i32 = 1;
+    /// Whether the term only wants results in which its component was written since the last
+    /// iteration.
+    const DETECT_CHANGES: bool = false;
 
     fn populate_term(term: &mut sys::ecs_term_t);
-    fn create_tuple_data<'a>(array_components_data: *mut u8, index: usize) -> Self::ActualType<'a>;
+
+    /// Returns the data pointer of the field at `index` for the current iterator result.
+    fn field_ptr(it: &IterT, index: i32) -> *mut u8 {
+        unsafe { ecs_field::<Self::OnlyPairType>(it, index) as *mut u8 }
+    }
+
+    /// Returns the target the field at `index` was matched with for the current iterator result.
+    /// Only fields that expose the target (wildcard pairs) resolve it, the others return 0.
+    fn field_target(_it: &IterT, _index: i32) -> EntityT {
+        0
+    }
+
+    fn create_tuple_data<'a>(
+        world: *mut WorldT,
+        array_components_data: *mut u8,
+        target: EntityT,
+        index: usize,
+    ) -> Self::ActualType<'a>;
     fn create_tuple_with_ref_data<'a>(
+        world: *mut WorldT,
         array_components_data: *mut u8,
         is_ref: bool,
+        target: EntityT,
         index: usize,
     ) -> Self::ActualType<'a>;
     fn create_tuple_slice_data<'a>(
+        world: *mut WorldT,
         array_components_data: *mut u8,
+        target: EntityT,
         count: usize,
     ) -> Self::SliceType<'a>;
     fn create_tuple_slices_with_ref_data<'a>(
+        world: *mut WorldT,
         array_components_data: *mut u8,
         is_ref_array_components: bool,
+        target: EntityT,
         count: usize,
     ) -> Self::SliceType<'a>;
 }
@@ -110,14 +156,21 @@ where
         term.inout = InOutKind::In as i16;
     }
 
-    fn create_tuple_data<'a>(array_components_data: *mut u8, index: usize) -> Self::ActualType<'a> {
+    fn create_tuple_data<'a>(
+        _world: *mut WorldT,
+        array_components_data: *mut u8,
+        _target: EntityT,
+        index: usize,
+    ) -> Self::ActualType<'a> {
         let data_ptr = array_components_data as Self::CastType;
         unsafe { &*data_ptr.add(index) }
     }
 
     fn create_tuple_with_ref_data<'a>(
+        _world: *mut WorldT,
         array_components_data: *mut u8,
         is_ref: bool,
+        _target: EntityT,
         index: usize,
     ) -> Self::ActualType<'a> {
         let data_ptr = array_components_data as Self::CastType;
@@ -131,7 +184,9 @@ where
     }
 
     fn create_tuple_slice_data<'a>(
+        _world: *mut WorldT,
         array_components_data: *mut u8,
+        _target: EntityT,
         count: usize,
     ) -> Self::SliceType<'a> {
         let data_ptr = array_components_data as Self::CastType;
@@ -139,8 +194,10 @@ where
     }
 
     fn create_tuple_slices_with_ref_data<'a>(
+        _world: *mut WorldT,
         array_components_data: *mut u8,
         is_ref_array_components: bool,
+        _target: EntityT,
         count: usize,
     ) -> Self::SliceType<'a> {
         let data_ptr = array_components_data as Self::CastType;
@@ -168,14 +225,21 @@ where
         term.inout = InOutKind::InOut as i16;
     }
 
-    fn create_tuple_data<'a>(array_components_data: *mut u8, index: usize) -> Self::ActualType<'a> {
+    fn create_tuple_data<'a>(
+        _world: *mut WorldT,
+        array_components_data: *mut u8,
+        _target: EntityT,
+        index: usize,
+    ) -> Self::ActualType<'a> {
         let data_ptr = array_components_data as Self::CastType;
         unsafe { &mut *data_ptr.add(index) }
     }
 
     fn create_tuple_with_ref_data<'a>(
+        _world: *mut WorldT,
         array_components_data: *mut u8,
         is_ref: bool,
+        _target: EntityT,
         index: usize,
     ) -> Self::ActualType<'a> {
         let data_ptr = array_components_data as Self::CastType;
@@ -189,7 +253,9 @@ where
     }
 
     fn create_tuple_slice_data<'a>(
+        _world: *mut WorldT,
         array_components_data: *mut u8,
+        _target: EntityT,
         count: usize,
     ) -> Self::SliceType<'a> {
         let data_ptr = array_components_data as Self::CastType;
@@ -197,8 +263,10 @@ where
     }
 
     fn create_tuple_slices_with_ref_data<'a>(
+        _world: *mut WorldT,
         array_components_data: *mut u8,
         is_ref_array_components: bool,
+        _target: EntityT,
         count: usize,
     ) -> Self::SliceType<'a> {
         let data_ptr = array_components_data as Self::CastType;
@@ -227,7 +295,12 @@ where
         term.oper = OperKind::Optional as i16;
     }
 
-    fn create_tuple_data<'a>(array_components_data: *mut u8, index: usize) -> Self::ActualType<'a> {
+    fn create_tuple_data<'a>(
+        _world: *mut WorldT,
+        array_components_data: *mut u8,
+        _target: EntityT,
+        index: usize,
+    ) -> Self::ActualType<'a> {
         let data_ptr = array_components_data as Self::CastType;
         if data_ptr.is_null() {
             None
@@ -237,8 +310,10 @@ where
     }
 
     fn create_tuple_with_ref_data<'a>(
+        _world: *mut WorldT,
         array_components_data: *mut u8,
         is_ref: bool,
+        _target: EntityT,
         index: usize,
     ) -> Self::ActualType<'a> {
         let data_ptr = array_components_data as Self::CastType;
@@ -252,7 +327,9 @@ where
     }
 
     fn create_tuple_slice_data<'a>(
+        _world: *mut WorldT,
         array_components_data: *mut u8,
+        _target: EntityT,
         count: usize,
     ) -> Self::SliceType<'a> {
         let data_ptr = array_components_data as Self::CastType;
@@ -264,8 +341,10 @@ where
     }
 
     fn create_tuple_slices_with_ref_data<'a>(
+        _world: *mut WorldT,
         array_components_data: *mut u8,
         is_ref_array_components: bool,
+        _target: EntityT,
         count: usize,
     ) -> Self::SliceType<'a> {
         let data_ptr = array_components_data as Self::CastType;
@@ -294,7 +373,12 @@ where
         term.oper = OperKind::Optional as i16;
     }
 
-    fn create_tuple_data<'a>(array_components_data: *mut u8, index: usize) -> Self::ActualType<'a> {
+    fn create_tuple_data<'a>(
+        _world: *mut WorldT,
+        array_components_data: *mut u8,
+        _target: EntityT,
+        index: usize,
+    ) -> Self::ActualType<'a> {
         let data_ptr = array_components_data as Self::CastType;
         if data_ptr.is_null() {
             None
@@ -304,8 +388,10 @@ where
     }
 
     fn create_tuple_with_ref_data<'a>(
+        _world: *mut WorldT,
         array_components_data: *mut u8,
         is_ref: bool,
+        _target: EntityT,
         index: usize,
     ) -> Self::ActualType<'a> {
         let data_ptr = array_components_data as Self::CastType;
@@ -319,7 +405,9 @@ where
     }
 
     fn create_tuple_slice_data<'a>(
+        _world: *mut WorldT,
         array_components_data: *mut u8,
+        _target: EntityT,
         count: usize,
     ) -> Self::SliceType<'a> {
         let data_ptr = array_components_data as Self::CastType;
@@ -331,8 +419,10 @@ where
     }
 
     fn create_tuple_slices_with_ref_data<'a>(
+        _world: *mut WorldT,
         array_components_data: *mut u8,
         is_ref_array_components: bool,
+        _target: EntityT,
         count: usize,
     ) -> Self::SliceType<'a> {
         let data_ptr = array_components_data as Self::CastType;
@@ -346,6 +436,508 @@ where
     }
 }
 
+/// Query term that matches a relationship with any target, `(Rel, flecs::Wildcard)`, and yields the
+/// matched target next to the relationship data.
+///
+/// - `PairWildcard<&Rel>` yields `(&Rel, EntityView)`
+/// - `PairWildcard<&mut Rel>` yields `(&mut Rel, EntityView)`
+///
+/// Entities with multiple targets for the relationship are returned once for each target.
+/// Use [`PairTarget`] for tag relationships or when the relationship data is not needed.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Attached {
+///     slot: u32,
+/// }
+///
+/// let world = World::new();
+///
+/// let ship = world.entity_named("ship");
+/// let turret = world.entity().set_first::<Attached>(Attached { slot: 1 }, ship);
+/// turret.child_of_id(ship);
+///
+/// world
+///     .new_query::<(PairWildcard<&Attached>, PairTarget<flecs::ChildOf>)>()
+///     .each(|((attached, target), parent)| {
+///         assert_eq!(attached.slot, 1);
+///         assert_eq!(target, ship);
+///         assert_eq!(parent, ship);
+///     });
+/// ```
+pub struct PairWildcard<T>(PhantomData<T>);
+
+/// Query term that matches a relationship with any target, `(Rel, flecs::Wildcard)`, and only yields the
+/// matched target as an `EntityView`. The relationship data is not accessed, which makes it usable
+/// for tag relationships such as `flecs::ChildOf`.
+pub struct PairTarget<T>(PhantomData<T>);
+
+#[inline(always)]
+fn pair_wildcard_target(it: &IterT, index: i32) -> EntityT {
+    unsafe {
+        let id = sys::ecs_field_id(it, index);
+        if id == 0 || !sys::ecs_id_is_pair(id) {
+            return 0;
+        }
+        sys::ecs_get_alive(it.real_world, *ecs_second(id))
+    }
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+impl<T> IterableTypeOperation for PairWildcard<&T>
+where
+    T: ComponentId + NotEmptyComponent,
+{
+    type CastType = *const T;
+    type ActualType<'w> = (&'w T, EntityView<'w>);
+    type SliceType<'w> = (&'w [T], EntityView<'w>);
+    type OnlyType = (T, flecs::Wildcard);
+    type OnlyPairType = T;
+
+    fn populate_term(term: &mut sys::ecs_term_t) {
+        term.inout = InOutKind::In as i16;
+    }
+
+    fn field_target(it: &IterT, index: i32) -> EntityT {
+        pair_wildcard_target(it, index)
+    }
+
+    fn create_tuple_data<'a>(
+        world: *mut WorldT,
+        array_components_data: *mut u8,
+        target: EntityT,
+        index: usize,
+    ) -> Self::ActualType<'a> {
+        let data_ptr = array_components_data as Self::CastType;
+        unsafe {
+            (
+                &*data_ptr.add(index),
+                EntityView::new_from(WorldRef::from_ptr(world), target),
+            )
+        }
+    }
+
+    fn create_tuple_with_ref_data<'a>(
+        world: *mut WorldT,
+        array_components_data: *mut u8,
+        is_ref: bool,
+        target: EntityT,
+        index: usize,
+    ) -> Self::ActualType<'a> {
+        let data_ptr = array_components_data as Self::CastType;
+        unsafe {
+            let data = if is_ref {
+                &*data_ptr.add(0)
+            } else {
+                &*data_ptr.add(index)
+            };
+            (
+                data,
+                EntityView::new_from(WorldRef::from_ptr(world), target),
+            )
+        }
+    }
+
+    fn create_tuple_slice_data<'a>(
+        world: *mut WorldT,
+        array_components_data: *mut u8,
+        target: EntityT,
+        count: usize,
+    ) -> Self::SliceType<'a> {
+        let data_ptr = array_components_data as Self::CastType;
+        unsafe {
+            (
+                std::slice::from_raw_parts(data_ptr, count),
+                EntityView::new_from(WorldRef::from_ptr(world), target),
+            )
+        }
+    }
+
+    fn create_tuple_slices_with_ref_data<'a>(
+        world: *mut WorldT,
+        array_components_data: *mut u8,
+        is_ref_array_components: bool,
+        target: EntityT,
+        count: usize,
+    ) -> Self::SliceType<'a> {
+        let data_ptr = array_components_data as Self::CastType;
+        unsafe {
+            let data = if is_ref_array_components {
+                std::slice::from_raw_parts(data_ptr, 1)
+            } else {
+                std::slice::from_raw_parts(data_ptr, count)
+            };
+            (
+                data,
+                EntityView::new_from(WorldRef::from_ptr(world), target),
+            )
+        }
+    }
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+impl<T> IterableTypeOperation for PairWildcard<&mut T>
+where
+    T: ComponentId + NotEmptyComponent,
+{
+    type CastType = *mut T;
+    type ActualType<'w> = (&'w mut T, EntityView<'w>);
+    type SliceType<'w> = (&'w mut [T], EntityView<'w>);
+    type OnlyType = (T, flecs::Wildcard);
+    type OnlyPairType = T;
+
+    fn populate_term(term: &mut sys::ecs_term_t) {
+        term.inout = InOutKind::InOut as i16;
+    }
+
+    fn field_target(it: &IterT, index: i32) -> EntityT {
+        pair_wildcard_target(it, index)
+    }
+
+    fn create_tuple_data<'a>(
+        world: *mut WorldT,
+        array_components_data: *mut u8,
+        target: EntityT,
+        index: usize,
+    ) -> Self::ActualType<'a> {
+        let data_ptr = array_components_data as Self::CastType;
+        unsafe {
+            (
+                &mut *data_ptr.add(index),
+                EntityView::new_from(WorldRef::from_ptr(world), target),
+            )
+        }
+    }
+
+    fn create_tuple_with_ref_data<'a>(
+        world: *mut WorldT,
+        array_components_data: *mut u8,
+        is_ref: bool,
+        target: EntityT,
+        index: usize,
+    ) -> Self::ActualType<'a> {
+        let data_ptr = array_components_data as Self::CastType;
+        unsafe {
+            let data = if is_ref {
+                &mut *data_ptr.add(0)
+            } else {
+                &mut *data_ptr.add(index)
+            };
+            (
+                data,
+                EntityView::new_from(WorldRef::from_ptr(world), target),
+            )
+        }
+    }
+
+    fn create_tuple_slice_data<'a>(
+        world: *mut WorldT,
+        array_components_data: *mut u8,
+        target: EntityT,
+        count: usize,
+    ) -> Self::SliceType<'a> {
+        let data_ptr = array_components_data as Self::CastType;
+        unsafe {
+            (
+                std::slice::from_raw_parts_mut(data_ptr, count),
+                EntityView::new_from(WorldRef::from_ptr(world), target),
+            )
+        }
+    }
+
+    fn create_tuple_slices_with_ref_data<'a>(
+        world: *mut WorldT,
+        array_components_data: *mut u8,
+        is_ref_array_components: bool,
+        target: EntityT,
+        count: usize,
+    ) -> Self::SliceType<'a> {
+        let data_ptr = array_components_data as Self::CastType;
+        unsafe {
+            let data = if is_ref_array_components {
+                std::slice::from_raw_parts_mut(data_ptr, 1)
+            } else {
+                std::slice::from_raw_parts_mut(data_ptr, count)
+            };
+            (
+                data,
+                EntityView::new_from(WorldRef::from_ptr(world), target),
+            )
+        }
+    }
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+impl<T> IterableTypeOperation for PairTarget<T>
+where
+    T: ComponentId,
+{
+    type CastType = *const T;
+    type ActualType<'w> = EntityView<'w>;
+    type SliceType<'w> = EntityView<'w>;
+    type OnlyType = (T, flecs::Wildcard);
+    type OnlyPairType = T;
+
+    fn populate_term(term: &mut sys::ecs_term_t) {
+        term.inout = InOutKind::None as i16;
+    }
+
+    fn field_ptr(_it: &IterT, _index: i32) -> *mut u8 {
+        std::ptr::null_mut()
+    }
+
+    fn field_target(it: &IterT, index: i32) -> EntityT {
+        pair_wildcard_target(it, index)
+    }
+
+    fn create_tuple_data<'a>(
+        world: *mut WorldT,
+        _array_components_data: *mut u8,
+        target: EntityT,
+        _index: usize,
+    ) -> Self::ActualType<'a> {
+        EntityView::new_from(unsafe { WorldRef::from_ptr(world) }, target)
+    }
+
+    fn create_tuple_with_ref_data<'a>(
+        world: *mut WorldT,
+        _array_components_data: *mut u8,
+        _is_ref: bool,
+        target: EntityT,
+        _index: usize,
+    ) -> Self::ActualType<'a> {
+        EntityView::new_from(unsafe { WorldRef::from_ptr(world) }, target)
+    }
+
+    fn create_tuple_slice_data<'a>(
+        world: *mut WorldT,
+        _array_components_data: *mut u8,
+        target: EntityT,
+        _count: usize,
+    ) -> Self::SliceType<'a> {
+        EntityView::new_from(unsafe { WorldRef::from_ptr(world) }, target)
+    }
+
+    fn create_tuple_slices_with_ref_data<'a>(
+        world: *mut WorldT,
+        _array_components_data: *mut u8,
+        _is_ref_array_components: bool,
+        target: EntityT,
+        _count: usize,
+    ) -> Self::SliceType<'a> {
+        EntityView::new_from(unsafe { WorldRef::from_ptr(world) }, target)
+    }
+}
+
+/// Query term that reports whether the entity has component `T`, without fetching its data.
+///
+/// The term is optional, so it does not filter out entities without `T`. It yields `true` when
+/// the current row has the component, `false` otherwise.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+/// }
+///
+/// #[derive(Component)]
+/// struct Frozen;
+///
+/// let world = World::new();
+///
+/// world.entity().set(Position { x: 1.0 }).add::<Frozen>();
+/// world.entity().set(Position { x: 2.0 });
+///
+/// let mut frozen = 0;
+/// world
+///     .new_query::<(&Position, Has<Frozen>)>()
+///     .each(|(_pos, is_frozen)| {
+///         if is_frozen {
+///             frozen += 1;
+///         }
+///     });
+/// assert_eq!(frozen, 1);
+/// ```
+pub struct Has<T>(PhantomData<T>);
+
+/// Query term that filters on component `T` and only visits results in which `T` was written
+/// since the last time the query was iterated. It yields `()`, use a separate `&T` term to read the
+/// data.
+///
+/// Flecs tracks writes per table, so all entities of a table are visited when `T` was written for
+/// one of them. Writes are `set` operations, `modified` after `get_mut`, and iterating queries
+/// that access `T` mutably. Writes of the query itself are not reported, and neither are writes to
+/// other components of the query. A table that gained or lost entities also counts as changed, so
+/// entities to which `T` was added are visited as well. `T` must match on the entity itself.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+/// }
+///
+/// #[derive(Component)]
+/// struct Velocity {
+///     x: f32,
+/// }
+///
+/// let world = World::new();
+///
+/// let e = world
+///     .entity()
+///     .set(Position { x: 1.0 })
+///     .set(Velocity { x: 1.0 });
+///
+/// let query = world.new_query::<(&Velocity, Changed<Position>)>();
+///
+/// let mut count = 0;
+/// query.each(|_| count += 1);
+/// assert_eq!(count, 1);
+///
+/// // nothing was written since the last iteration
+/// query.each(|_| count += 1);
+/// assert_eq!(count, 1);
+///
+/// // writes to other components are not reported
+/// e.set(Velocity { x: 2.0 });
+/// query.each(|_| count += 1);
+/// assert_eq!(count, 1);
+///
+/// e.set(Position { x: 2.0 });
+/// query.each(|_| count += 1);
+/// assert_eq!(count, 2);
+/// ```
+pub struct Changed<T>(PhantomData<T>);
+
+impl<T> IterableTypeOperation for Has<T>
+where
+    T: ComponentId,
+{
+    type CastType = *const T;
+    type ActualType<'w> = bool;
+    type SliceType<'w> = bool;
+    type OnlyType = T;
+    type OnlyPairType = T;
+
+    fn populate_term(term: &mut sys::ecs_term_t) {
+        term.inout = InOutKind::None as i16;
+        term.oper = OperKind::Optional as i16;
+    }
+
+    // the data is never read, any non-null pointer marks the field as set for this result.
+    fn field_ptr(it: &IterT, index: i32) -> *mut u8 {
+        if unsafe { sys::ecs_field_is_set(it, index) } {
+            std::ptr::NonNull::dangling().as_ptr()
+        } else {
+            std::ptr::null_mut()
+        }
+    }
+
+    fn create_tuple_data<'a>(
+        _world: *mut WorldT,
+        array_components_data: *mut u8,
+        _target: EntityT,
+        _index: usize,
+    ) -> Self::ActualType<'a> {
+        !array_components_data.is_null()
+    }
+
+    fn create_tuple_with_ref_data<'a>(
+        _world: *mut WorldT,
+        array_components_data: *mut u8,
+        _is_ref: bool,
+        _target: EntityT,
+        _index: usize,
+    ) -> Self::ActualType<'a> {
+        !array_components_data.is_null()
+    }
+
+    fn create_tuple_slice_data<'a>(
+        _world: *mut WorldT,
+        array_components_data: *mut u8,
+        _target: EntityT,
+        _count: usize,
+    ) -> Self::SliceType<'a> {
+        !array_components_data.is_null()
+    }
+
+    fn create_tuple_slices_with_ref_data<'a>(
+        _world: *mut WorldT,
+        array_components_data: *mut u8,
+        _is_ref_array_components: bool,
+        _target: EntityT,
+        _count: usize,
+    ) -> Self::SliceType<'a> {
+        !array_components_data.is_null()
+    }
+}
+
+impl<T> IterableTypeOperation for Changed<T>
+where
+    T: ComponentId + NotEmptyComponent,
+{
+    type CastType = *const T;
+    type ActualType<'w> = ();
+    type SliceType<'w> = ();
+    type OnlyType = T;
+    type OnlyPairType = T;
+    const DETECT_CHANGES: bool = true;
+
+    fn populate_term(term: &mut sys::ecs_term_t) {
+        term.inout = InOutKind::In as i16;
+    }
+
+    fn field_ptr(_it: &IterT, _index: i32) -> *mut u8 {
+        std::ptr::null_mut()
+    }
+
+    fn create_tuple_data<'a>(
+        _world: *mut WorldT,
+        _array_components_data: *mut u8,
+        _target: EntityT,
+        _index: usize,
+    ) -> Self::ActualType<'a> {
+    }
+
+    fn create_tuple_with_ref_data<'a>(
+        _world: *mut WorldT,
+        _array_components_data: *mut u8,
+        _is_ref: bool,
+        _target: EntityT,
+        _index: usize,
+    ) -> Self::ActualType<'a> {
+    }
+
+    fn create_tuple_slice_data<'a>(
+        _world: *mut WorldT,
+        _array_components_data: *mut u8,
+        _target: EntityT,
+        _count: usize,
+    ) -> Self::SliceType<'a> {
+    }
+
+    fn create_tuple_slices_with_ref_data<'a>(
+        _world: *mut WorldT,
+        _array_components_data: *mut u8,
+        _is_ref_array_components: bool,
+        _target: EntityT,
+        _count: usize,
+    ) -> Self::SliceType<'a> {
+    }
+}
+
 pub trait Iterable: Sized {
     type Pointers: ComponentPointers<Self>;
     type TupleType<'a>;
@@ -368,21 +960,40 @@ pub trait Iterable: Sized {
         index: &mut usize,
     );
 
-    fn populate_array_ptrs(it: &IterT, components: &mut [*mut u8], is_ref: &mut [bool]) -> bool;
+    fn populate_array_ptrs(
+        it: &IterT,
+        components: &mut [*mut u8],
+        is_ref: &mut [bool],
+        targets: &mut [EntityT],
+    ) -> bool;
 
-    fn create_tuple(array_components: &[*mut u8], index: usize) -> Self::TupleType<'_>;
+    fn create_tuple<'a>(
+        world: *mut WorldT,
+        array_components: &'a [*mut u8],
+        array_targets: &[EntityT],
+        index: usize,
+    ) -> Self::TupleType<'a>;
 
     fn create_tuple_with_ref<'a>(
+        world: *mut WorldT,
         array_components: &'a [*mut u8],
         is_ref_array_components: &[bool],
+        array_targets: &[EntityT],
         index: usize,
     ) -> Self::TupleType<'a>;
 
-    fn create_tuple_slices(array_components: &[*mut u8], count: usize) -> Self::TupleSliceType<'_>;
+    fn create_tuple_slices<'a>(
+        world: *mut WorldT,
+        array_components: &'a [*mut u8],
+        array_targets: &[EntityT],
+        count: usize,
+    ) -> Self::TupleSliceType<'a>;
 
     fn create_tuple_slices_with_ref<'a>(
+        world: *mut WorldT,
         array_components: &'a [*mut u8],
         is_ref_array_components: &[bool],
+        array_targets: &[EntityT],
         count: usize,
     ) -> Self::TupleSliceType<'a>;
 }
@@ -425,6 +1036,9 @@ where
         filter.with_id(id);
         let term = filter.current_term_mut();
         A::populate_term(term);
+        if A::DETECT_CHANGES {
+            ChangeMonitor::track(filter, id);
+        }
 
     }
 
@@ -444,9 +1058,10 @@ where
         it: &IterT,
         components: &mut [*mut u8],
         is_ref: &mut [bool],
+        targets: &mut [EntityT],
     ) -> bool {
-        components[0] =
-            unsafe { ecs_field::<A::OnlyPairType>(it, 0) as *mut u8 };
+        components[0] = A::field_ptr(it, 0);
+        targets[0] = A::field_target(it, 0);
         is_ref[0] = if !it.sources.is_null() {
             unsafe { *it.sources.add(0) != 0 }
         } else {
@@ -455,36 +1070,55 @@ where
         is_ref[0]
     }
 
-    fn create_tuple(array_components: &[*mut u8], index: usize) -> Self::TupleType<'_> {
-        A::create_tuple_data(array_components[0], index)
+    fn create_tuple<'a>(
+        world: *mut WorldT,
+        array_components: &'a [*mut u8],
+        array_targets: &[EntityT],
+        index: usize,
+    ) -> Self::TupleType<'a> {
+        A::create_tuple_data(world, array_components[0], array_targets[0], index)
 
     }
 
     // TODO since it's only one component, we don't need to check if it's a ref array or not, we can just return the first element of the array
     // I think this is the case for all tuples of size 1
     fn create_tuple_with_ref<'a>(
+        world: *mut WorldT,
         array_components: &'a [*mut u8],
         is_ref_array_components: &[bool],
+        array_targets: &[EntityT],
         index: usize
     ) -> Self::TupleType<'a> {
-        A::create_tuple_with_ref_data(array_components[0], is_ref_array_components[0], index)
+        A::create_tuple_with_ref_data(
+            world,
+            array_components[0],
+            is_ref_array_components[0],
+            array_targets[0],
+            index,
+        )
     }
 
-    fn create_tuple_slices(
-        array_components: &[*mut u8],
+    fn create_tuple_slices<'a>(
+        world: *mut WorldT,
+        array_components: &'a [*mut u8],
+        array_targets: &[EntityT],
         count: usize,
-    ) -> Self::TupleSliceType<'_> {
-        A::create_tuple_slice_data(array_components[0], count)
+    ) -> Self::TupleSliceType<'a> {
+        A::create_tuple_slice_data(world, array_components[0], array_targets[0], count)
     }
 
     fn create_tuple_slices_with_ref<'a>(
+        world: *mut WorldT,
         array_components: &'a [*mut u8],
         is_ref_array_components: &[bool],
+        array_targets: &[EntityT],
         count: usize,
     ) -> Self::TupleSliceType<'a> {
         A::create_tuple_slices_with_ref_data(
+            world,
             array_components[0],
             is_ref_array_components[0],
+            array_targets[0],
             count,
         )
     }
@@ -664,6 +1298,9 @@ macro_rules! impl_iterable {
                     filter.with_id(id);
                     let term = filter.current_term_mut();
                     $t::populate_term(term);
+                    if $t::DETECT_CHANGES {
+                        ChangeMonitor::track(filter, id);
+                    }
 
                 )*
             }
@@ -678,12 +1315,13 @@ macro_rules! impl_iterable {
                 it: &IterT,
                 components: &mut [*mut u8],
                 is_ref: &mut [bool],
+                targets: &mut [EntityT],
             ) -> bool {
                 let mut index = 0;
                 let mut any_ref = false;
                 $(
-                    components[index as usize] =
-                    unsafe { ecs_field::<$t::OnlyPairType>(it, index) as *mut u8 };
+                    components[index as usize] = $t::field_ptr(it, index);
+                    targets[index as usize] = $t::field_target(it, index);
                     is_ref[index as usize] = if !it.sources.is_null() {
                         unsafe { *it.sources.add(index as usize) != 0 }
                     } else {
@@ -696,45 +1334,60 @@ macro_rules! impl_iterable {
             }
 
             #[allow(unused, clippy::unused_unit)]
-            fn create_tuple(array_components: &[*mut u8], index: usize) -> Self::TupleType<'_> {
+            fn create_tuple<'a>(
+                world: *mut WorldT,
+                array_components: &'a [*mut u8],
+                array_targets: &[EntityT],
+                index: usize,
+            ) -> Self::TupleType<'a> {
                 let mut column: isize = -1;
                 ($({
                     column += 1;
-                    $t::create_tuple_data(array_components[column as usize], index)
+                    $t::create_tuple_data(world, array_components[column as usize], array_targets[column as usize], index)
                 },)*)
             }
 
             #[allow(unused, clippy::unused_unit)]
-            fn create_tuple_with_ref<'a>(array_components: &'a [*mut u8], is_ref_array_components: &[bool], index: usize) -> Self::TupleType<'a> {
+            fn create_tuple_with_ref<'a>(
+                world: *mut WorldT,
+                array_components: &'a [*mut u8],
+                is_ref_array_components: &[bool],
+                array_targets: &[EntityT],
+                index: usize,
+            ) -> Self::TupleType<'a> {
                 let mut column: isize = -1;
                 ($({
                     column += 1;
-                    $t::create_tuple_with_ref_data(array_components[column as usize], is_ref_array_components[column as usize], index)
+                    $t::create_tuple_with_ref_data(world, array_components[column as usize], is_ref_array_components[column as usize], array_targets[column as usize], index)
                 },)*)
             }
 
             #[allow(unused, clippy::unused_unit)]
-            fn create_tuple_slices(
-                array_components: &[*mut u8],
+            fn create_tuple_slices<'a>(
+                world: *mut WorldT,
+                array_components: &'a [*mut u8],
+                array_targets: &[EntityT],
                 count: usize,
-            ) -> Self::TupleSliceType<'_> {
+            ) -> Self::TupleSliceType<'a> {
                 let mut column: isize = -1;
                 ($({
                     column += 1;
-                    $t::create_tuple_slice_data(array_components[column as usize], count)
+                    $t::create_tuple_slice_data(world, array_components[column as usize], array_targets[column as usize], count)
                 },)*)
             }
 
             #[allow(unused, clippy::unused_unit)]
             fn create_tuple_slices_with_ref<'a>(
+                world: *mut WorldT,
                 array_components: &'a [*mut u8],
                 is_ref_array_components: &[bool],
+                array_targets: &[EntityT],
                 count: usize,
             ) -> Self::TupleSliceType<'a> {
                 let mut column: isize = -1;
                 ($({
                     column += 1;
-                    $t::create_tuple_slices_with_ref_data(array_components[column as usize], is_ref_array_components[column as usize], count)
+                    $t::create_tuple_slices_with_ref_data(world, array_components[column as usize], is_ref_array_components[column as usize], array_targets[column as usize], count)
                 },)*)
             }
         }
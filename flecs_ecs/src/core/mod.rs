@@ -1,6 +1,7 @@
 pub mod archetype;
 pub mod builder;
 pub mod c_types;
+pub(crate) mod change_detection;
 pub(crate) mod cloned_tuple;
 pub mod component_registration;
 pub mod components;
@@ -17,6 +18,8 @@ pub mod iter_iterable;
 pub mod iterable;
 pub mod observer;
 pub mod observer_builder;
+pub(crate) mod observer_order;
+pub mod prefab;
 pub mod query;
 pub mod query_builder;
 pub mod table;
@@ -28,6 +31,7 @@ pub(crate) mod world_ctx;
 pub use archetype::*;
 pub use builder::*;
 pub use c_types::*;
+pub(crate) use change_detection::*;
 pub(crate) use cloned_tuple::*;
 pub use component_registration::*;
 pub use components::*;
@@ -43,6 +47,8 @@ pub use iter_iterable::*;
 pub use iterable::*;
 pub use observer::*;
 pub use observer_builder::*;
+pub(crate) use observer_order::*;
+pub use prefab::*;
 pub use query::*;
 pub use query_builder::*;
 pub use table::*;
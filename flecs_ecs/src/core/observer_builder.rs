@@ -18,10 +18,12 @@ pub struct ObserverBuilder<'a, P = (), T: Iterable = ()> {
     world: WorldRef<'a>,
     event_count: i32,
     is_instanced: bool,
+    priority: Option<i32>,
+    depends_on: Vec<EntityT>,
     _phantom: std::marker::PhantomData<&'a (T, P)>,
 }
 
-impl<'a, P: ComponentId, T: Iterable> ObserverBuilder<'a, P, T> {
+impl<'a, P: EventId, T: Iterable> ObserverBuilder<'a, P, T> {
     /// Create a new observer builder
     ///
     /// # Arguments
@@ -39,11 +41,13 @@ impl<'a, P: ComponentId, T: Iterable> ObserverBuilder<'a, P, T> {
             term_builder: TermBuilder::default(),
             event_count: 1,
             is_instanced: false,
+            priority: None,
+            depends_on: Vec::new(),
             world: world.world(),
             _phantom: std::marker::PhantomData,
         };
 
-        obj.desc.events[0] = P::id(world.world());
+        obj.desc.events[0] = P::event_id(world.world());
         obj.desc.entity =
             unsafe { sys::ecs_entity_init(world.world_ptr_mut(), &Default::default()) };
 
@@ -71,6 +75,8 @@ impl<'a, P: ComponentId, T: Iterable> ObserverBuilder<'a, P, T> {
             term_builder: TermBuilder::default(),
             event_count: 1,
             is_instanced: false,
+            priority: None,
+            depends_on: Vec::new(),
             world: world.world(),
             _phantom: std::marker::PhantomData,
         };
@@ -81,7 +87,7 @@ impl<'a, P: ComponentId, T: Iterable> ObserverBuilder<'a, P, T> {
             ..default::Default::default()
         };
 
-        obj.desc.events[0] = P::id(world.world());
+        obj.desc.events[0] = P::event_id(world.world());
         obj.desc.entity = unsafe { sys::ecs_entity_init(obj.world_ptr_mut(), &entity_desc) };
 
         T::populate(&mut obj);
@@ -97,6 +103,8 @@ impl<'a, P, T: Iterable> ObserverBuilder<'a, P, T> {
             term_builder: TermBuilder::default(),
             event_count: 0,
             is_instanced: false,
+            priority: None,
+            depends_on: Vec::new(),
             world: world.world(),
             _phantom: std::marker::PhantomData,
         };
@@ -126,6 +134,8 @@ impl<'a, P, T: Iterable> ObserverBuilder<'a, P, T> {
             event_count: 0,
             world: world.world(),
             is_instanced: false,
+            priority: None,
+            depends_on: Vec::new(),
             _phantom: std::marker::PhantomData,
         };
 
@@ -176,11 +186,11 @@ impl<'a, P, T: Iterable> ObserverBuilder<'a, P, T> {
     #[doc(alias = "observer_builder_i::event")]
     pub fn add_event<E>(&mut self) -> &mut ObserverBuilder<(), T>
     where
-        E: ComponentId,
+        E: EventId,
     {
         let event_count = self.event_count as usize;
         self.event_count += 1;
-        let id = E::id(self.world());
+        let id = E::event_id(self.world());
         self.desc.events[event_count] = id;
         // SAFETY: Same layout
         unsafe { std::mem::transmute(self) }
@@ -200,6 +210,161 @@ impl<'a, P, T: Iterable> ObserverBuilder<'a, P, T> {
         self.desc.yield_existing = should_yield;
         self
     }
+
+    /// Set the priority of the observer.
+    ///
+    /// Observers with a priority or dependencies are ordered: for an event they are invoked in order
+    /// of priority, highest first, and after the observers they depend on. Observers with the same
+    /// priority are invoked in the order they were created. The default priority is 0.
+    ///
+    /// Observers that aren't ordered are invoked in no particular order.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The priority of the observer
+    pub fn priority(&mut self, priority: i32) -> &mut Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Invoke the observer after another observer, for the events both observe.
+    ///
+    /// This makes the observer ordered, see [`ObserverBuilder::priority`].
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - The observer to invoke before this one, which must be ordered
+    pub fn depends_on(&mut self, observer: impl Into<Entity>) -> &mut Self {
+        let observer = *observer.into();
+        ecs_assert!(
+            self.world().world_ctx().observer_order.contains(observer),
+            FlecsErrorCode::InvalidParameter,
+            "observers can only depend on observers with a priority or dependencies"
+        );
+        self.depends_on.push(observer);
+        self
+    }
+}
+
+impl<'a, E: Event, T: Iterable> ObserverBuilder<'a, E, T> {
+    /// Each iterator with the event payload.
+    /// The "each" iterator accepts a function that is invoked for each matching entity
+    /// with the entity, the payload the event was emitted with and the components.
+    /// The following function signature is valid:
+    ///  - `func(e: EntityView, event: &E, comp1 : &mut T1, comp2 : &mut T2, ...)`
+    ///
+    /// Only available for observers of a single [`Event`], the payload type is part of the event type.
+    /// Panics if other events were added to the observer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Event)]
+    /// struct Damage {
+    ///     amount: u32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Health {
+    ///     value: u32,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .observer::<Damage, (&mut Health,)>()
+    ///     .each_event(|_entity, damage, (health,)| {
+    ///         health.value -= damage.amount;
+    ///     });
+    ///
+    /// let entity = world.entity().set(Health { value: 100 });
+    /// world
+    ///     .event()
+    ///     .add::<Health>()
+    ///     .target(entity)
+    ///     .emit(&Damage { amount: 10 });
+    ///
+    /// entity.get::<&Health>(|health| assert_eq!(health.value, 90));
+    /// ```
+    pub fn each_event<Func>(&mut self, func: Func) -> Observer<'a>
+    where
+        Func: FnMut(EntityView, &E, T::TupleType<'_>) + 'static,
+    {
+        ecs_assert!(
+            self.event_count == 1,
+            FlecsErrorCode::InvalidOperation,
+            "each_event requires an observer of only event {}",
+            std::any::type_name::<E>()
+        );
+
+        let each_event_func = Box::new(func);
+        let each_event_static_ref = Box::leak(each_event_func);
+
+        self.set_callback_binding_context(each_event_static_ref as *mut _ as *mut c_void);
+        self.set_callback_binding_context_free(Some(Self::free_callback::<Func>));
+        self.set_desc_callback(Some(
+            Self::execute_each_event::<Func> as unsafe extern "C" fn(_),
+        ));
+
+        self.set_instanced(true);
+
+        self.build()
+    }
+
+    /// Callback of the `each_event` functionality
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - The iterator which gets passed in from `C`
+    unsafe extern "C" fn execute_each_event<Func>(iter: *mut IterT)
+    where
+        Func: FnMut(EntityView, &E, T::TupleType<'_>),
+    {
+        let iter = unsafe { &mut *iter };
+        iter.flags |= sys::EcsIterCppEach;
+
+        let each_event = unsafe { &mut *(iter.callback_ctx as *mut Func) };
+
+        // the payload of other events has a different type
+        if iter.event != E::event_id(unsafe { WorldRef::from_ptr(iter.world) }) {
+            return;
+        }
+
+        let param = if std::mem::size_of::<E>() == 0 {
+            std::ptr::NonNull::<E>::dangling().as_ptr() as *const E
+        } else {
+            iter.param as *const E
+        };
+
+        assert!(
+            !param.is_null(),
+            "event {} was emitted without a payload",
+            std::any::type_name::<E>()
+        );
+
+        let event = unsafe { &*param };
+        let mut components_data = T::create_ptrs(&*iter);
+
+        ecs_assert!(
+            iter.count > 0,
+            FlecsErrorCode::InvalidOperation,
+            "no entities returned, use each() without flecs::entity argument",
+        );
+
+        unsafe { sys::ecs_table_lock(iter.world, iter.table) };
+
+        for i in 0..iter.count as usize {
+            let world = unsafe { WorldRef::from_ptr(iter.world) };
+            let entity = EntityView::new_from(world, unsafe { *iter.entities.add(i) });
+            let tuple = components_data.get_tuple(i);
+
+            each_event(entity, event, tuple);
+        }
+
+        unsafe { sys::ecs_table_unlock(iter.world, iter.table) };
+    }
 }
 
 #[doc(hidden)]
@@ -246,7 +411,32 @@ where
     /// * C++ API: `node_builder::build`
     #[doc(alias = "node_builder::build")]
     fn build(&mut self) -> Self::BuiltType {
+        let is_ordered = self.priority.is_some() || !self.depends_on.is_empty();
+        if is_ordered {
+            ecs_assert!(
+                self.desc.run.is_none(),
+                FlecsErrorCode::InvalidOperation,
+                "ordered observers can't have a run callback"
+            );
+        }
+
+        crate::addons::profiler::profile_observer(self.world(), &mut self.desc);
+        let is_wrapped = ObserverRun::is_wrapped(&self.desc, is_ordered);
+        if is_wrapped {
+            ObserverRun::wrap(self.world().real_world().world_ptr_mut(), &mut self.desc);
+        }
         let observer = Observer::new(self.world(), self.desc, self.is_instanced);
+        if is_wrapped {
+            ObserverRun::set_observer(self.world_ptr_mut(), *observer.id());
+        }
+        if is_ordered {
+            self.world().world_ctx_mut().observer_order.insert(
+                self.world_ptr_mut(),
+                *observer.id(),
+                self.priority.unwrap_or(0),
+                &self.depends_on,
+            );
+        }
         for string_parts in self.term_builder.str_ptrs_to_free.iter() {
             unsafe {
                 String::from_raw_parts(
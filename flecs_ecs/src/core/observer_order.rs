@@ -0,0 +1,408 @@
+//! Dispatch of the observers created by an [`ObserverBuilder`].
+//!
+//! Ordered observers and observers that traverse up are created with [`run_observer`] as run
+//! action, which wraps the run action of the observer if it has one. It skips the invocations for
+//! an event whose propagation was stopped with [`Iter::stop_propagation`], and invokes ordered
+//! observers in a deterministic order. Other observers are dispatched by flecs.
+//!
+//! Flecs invokes the observers of an event in no particular order. When flecs invokes an ordered
+//! observer, the ordered observers that have to run before it and match the same event are
+//! invoked first, with the default run action of flecs and the iterator flecs created for the
+//! event.
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+
+use crate::core::*;
+use crate::sys;
+
+/// Identifies the entities an event is dispatched to in a single invocation of observers.
+#[derive(Clone, Copy, Default, PartialEq)]
+struct EventBatch {
+    event_cur: i32,
+    event_id: IdT,
+    table: usize,
+    offset: i32,
+    count: i32,
+}
+
+impl EventBatch {
+    fn new(it: &IterT) -> Self {
+        Self {
+            event_cur: it.event_cur,
+            event_id: it.event_id,
+            table: it.table as usize,
+            offset: it.offset,
+            count: it.count,
+        }
+    }
+}
+
+/// An observer that is invoked in a deterministic order relative to other ordered observers.
+struct OrderedObserver {
+    entity: EntityT,
+    priority: i32,
+    depends_on: Vec<EntityT>,
+    last_batch: EventBatch,
+}
+
+/// Ordered observers of a world, per event and sorted in invocation order.
+#[derive(Default)]
+pub(crate) struct ObserverOrder {
+    observers: HashMap<EntityT, Vec<OrderedObserver>, fxhash::FxBuildHasher>,
+}
+
+impl ObserverOrder {
+    /// Register an observer that was created with [`run_observer`] as run action.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dependencies of the observer are cyclic.
+    pub(crate) fn insert(
+        &mut self,
+        world: *mut WorldT,
+        observer: EntityT,
+        priority: i32,
+        depends_on: &[EntityT],
+    ) {
+        let o = unsafe { &*sys::ecs_observer_get(world, observer) };
+        for &event in &o.events[..o.event_count as usize] {
+            let observers = self.observers.entry(event).or_default();
+            observers.push(OrderedObserver {
+                entity: observer,
+                priority,
+                depends_on: depends_on.to_vec(),
+                last_batch: EventBatch::default(),
+            });
+            sort_observers(observers);
+        }
+    }
+
+    /// Unregister a deleted observer.
+    fn remove(&mut self, observer: EntityT) {
+        self.observers.retain(|_, observers| {
+            observers.retain(|o| o.entity != observer);
+            !observers.is_empty()
+        });
+    }
+
+    /// Returns whether the observer is ordered for any of its events.
+    pub(crate) fn contains(&self, observer: EntityT) -> bool {
+        self.observers
+            .values()
+            .any(|observers| observers.iter().any(|o| o.entity == observer))
+    }
+
+    fn get_mut(&mut self, event: EntityT, observer: EntityT) -> Option<&mut OrderedObserver> {
+        self.observers
+            .get_mut(&event)?
+            .iter_mut()
+            .find(|o| o.entity == observer)
+    }
+
+    /// Mark the observer as invoked for the batch, returns false if it already was.
+    fn claim(&mut self, event: EntityT, observer: EntityT, batch: EventBatch) -> bool {
+        let Some(o) = self.get_mut(event, observer) else {
+            return false;
+        };
+        if o.last_batch == batch {
+            return false;
+        }
+        o.last_batch = batch;
+        true
+    }
+}
+
+/// Sort observers on priority, highest first, while keeping dependencies before their dependents.
+/// Observers with the same priority keep the order they were registered in.
+fn sort_observers(observers: &mut Vec<OrderedObserver>) {
+    let mut pending = std::mem::take(observers);
+    while !pending.is_empty() {
+        let next = pending
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| {
+                o.depends_on
+                    .iter()
+                    .all(|dep| !pending.iter().any(|p| p.entity == *dep))
+            })
+            .max_by_key(|(index, o)| (o.priority, std::cmp::Reverse(*index)))
+            .map(|(index, _)| index)
+            .unwrap_or_else(|| panic!("cyclic dependency between ordered observers"));
+        observers.push(pending.remove(next));
+    }
+}
+
+/// The run action of an observer created by an [`ObserverBuilder`].
+pub(crate) struct ObserverRun {
+    world: *mut WorldT,
+    /// Set once the observer is created.
+    observer: EntityT,
+    run: sys::ecs_run_action_t,
+    run_ctx: *mut c_void,
+    run_ctx_free: sys::ecs_ctx_free_t,
+}
+
+impl ObserverRun {
+    /// Returns whether an observer with the descriptor is created with [`run_observer`] as run
+    /// action, which is the case if it is ordered or has a term that traverses up.
+    pub(crate) fn is_wrapped(desc: &sys::ecs_observer_desc_t, is_ordered: bool) -> bool {
+        is_ordered
+            || desc
+                .query
+                .terms
+                .iter()
+                .any(|term| term.src.id & ECS_UP != 0)
+    }
+
+    /// Wrap the run action of the observer with [`run_observer`].
+    pub(crate) fn wrap(world: *mut WorldT, desc: &mut sys::ecs_observer_desc_t) {
+        let ctx = Box::new(Self {
+            world,
+            observer: 0,
+            run: desc.run,
+            run_ctx: desc.run_ctx,
+            run_ctx_free: desc.run_ctx_free,
+        });
+        desc.run = Some(run_observer);
+        desc.run_ctx = Box::into_raw(ctx) as *mut c_void;
+        desc.run_ctx_free = Some(free_observer_run);
+    }
+
+    /// Record the entity of the observer created with a wrapped run action.
+    pub(crate) fn set_observer(world: *mut WorldT, observer: EntityT) {
+        unsafe {
+            let o = &*sys::ecs_observer_get(world, observer);
+            (*(o.run_ctx as *mut Self)).observer = observer;
+        }
+    }
+}
+
+unsafe extern "C" fn free_observer_run(ctx: *mut c_void) {
+    let ctx = unsafe { Box::from_raw(ctx as *mut ObserverRun) };
+    if ctx.observer != 0 {
+        let world = unsafe { WorldRef::from_ptr(ctx.world) };
+        world.world_ctx_mut().observer_order.remove(ctx.observer);
+    }
+    if let Some(free) = ctx.run_ctx_free {
+        unsafe { free(ctx.run_ctx) };
+    }
+}
+
+/// An event an observer stopped from propagating, see [`Iter::stop_propagation`].
+pub(crate) struct StoppedEvent {
+    event: EntityT,
+    event_id: IdT,
+    event_cur: i32,
+    sources: Vec<EntityT>,
+}
+
+impl StoppedEvent {
+    pub(crate) fn new(it: &IterT) -> Self {
+        let source = source(it);
+        let sources = if source != 0 {
+            vec![source]
+        } else if it.entities.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(it.entities, it.count as usize) }.to_vec()
+        };
+        Self {
+            event: it.event,
+            event_id: it.event_id,
+            event_cur: it.event_cur,
+            sources,
+        }
+    }
+}
+
+/// The propagation state of the events emitted in a stage, see [`Iter::stop_propagation`].
+#[derive(Default)]
+pub(crate) struct EventPropagation {
+    /// Set by an observer to stop an event from bubbling to the next entity.
+    pub(crate) stopped: bool,
+    /// Set by an observer to stop an event from propagating to the entities that inherit from its
+    /// source.
+    pub(crate) stopped_event: Option<StoppedEvent>,
+}
+
+/// The [`EventPropagation`] of each stage of a world.
+#[derive(Default)]
+pub(crate) struct StagePropagation {
+    stages: std::sync::Mutex<Vec<EventPropagation>>,
+}
+
+impl StagePropagation {
+    /// Access the propagation state of the stage, or of the main stage if `stage` is a world.
+    pub(crate) fn with<R>(
+        &self,
+        stage: *const WorldT,
+        func: impl FnOnce(&mut EventPropagation) -> R,
+    ) -> R {
+        let id = unsafe { sys::ecs_stage_get_id(stage) } as usize;
+        let mut stages = self.stages.lock().unwrap_or_else(|err| err.into_inner());
+        if stages.len() <= id {
+            stages.resize_with(id + 1, Default::default);
+        }
+        func(&mut stages[id])
+    }
+}
+
+/// Returns the entity the event of the iterator traversed up from, or 0 if it wasn't propagated.
+fn source(it: &IterT) -> EntityT {
+    if it.sources.is_null() {
+        0
+    } else {
+        unsafe { *it.sources }
+    }
+}
+
+/// Returns whether the invocation propagates an event that an observer stopped from propagating.
+///
+/// Flecs numbers each table an event propagates to as a new event, the stopped event is forgotten
+/// once an event emitted after it is invoked for the entities it was emitted for.
+fn is_propagation_stopped(world: WorldRef, it: &IterT) -> bool {
+    world.world_ctx().propagation.with(it.world, |propagation| {
+        let Some(stopped) = &propagation.stopped_event else {
+            return false;
+        };
+        let source = source(it);
+        if source == 0 {
+            if it.event_cur > stopped.event_cur {
+                propagation.stopped_event = None;
+            }
+            return false;
+        }
+        it.event == stopped.event
+            && it.event_id == stopped.event_id
+            && it.event_cur > stopped.event_cur
+            && stopped.sources.contains(&source)
+    })
+}
+
+/// Run action of the observers created by an [`ObserverBuilder`].
+pub(crate) unsafe extern "C" fn run_observer(it: *mut IterT) {
+    let it = unsafe { &mut *it };
+    let ctx = unsafe { &*(it.run_ctx as *const ObserverRun) };
+    let world = unsafe { WorldRef::from_ptr(it.real_world) };
+    if is_propagation_stopped(world, it) {
+        return;
+    }
+
+    if let Some(run) = ctx.run {
+        it.run_ctx = ctx.run_ctx;
+        unsafe { run(it) };
+    } else if world
+        .world_ctx_mut()
+        .observer_order
+        .get_mut(it.event, ctx.observer)
+        .is_some()
+    {
+        unsafe { run_ordered_observer(world, ctx.observer, it) };
+    } else {
+        // also while yielding existing entities, before the observer is registered
+        if it.callback.is_none() {
+            it.callback = unsafe { (*(it.ctx as *const sys::ecs_observer_t)).callback };
+        }
+        unsafe { sys::ecs_observer_default_run_action(it) };
+    }
+}
+
+/// Returns the index of the term of the query that is triggered by the event of the iterator.
+fn trigger_term(query: &QueryT, it: &IterT) -> Option<usize> {
+    let propagated = source(it) != 0;
+    query.terms[..query.term_count as usize]
+        .iter()
+        .position(|term| {
+            let src = term.src.id;
+            term.inout != InOutKind::Filter as i16
+                && src & ECS_IS_ENTITY == 0
+                && src & if propagated { ECS_UP } else { ECS_SELF } != 0
+                && unsafe { sys::ecs_id_match(it.event_id, term.id) }
+        })
+}
+
+/// Invoke an ordered observer that flecs didn't invoke yet for the event of the iterator.
+/// Returns false if the observer doesn't match the event.
+unsafe fn invoke_early(world: *mut WorldT, observer: EntityT, it: &IterT) -> bool {
+    unsafe {
+        if sys::ecs_has_id(world, observer, flecs::Disabled::ID) {
+            return false;
+        }
+        let o = sys::ecs_observer_get(world, observer);
+        let query = (*o).query;
+        let Some(term_index) = trigger_term(&*query, it) else {
+            return false;
+        };
+
+        // multi term observers match the query when flecs runs them, single term observers are
+        // only invoked for the tables flecs would invoke them for
+        if (*query).term_count == 1 {
+            let mut range = sys::ecs_table_range_t {
+                table: it.table,
+                offset: it.offset,
+                count: it.count,
+            };
+            let mut match_it: IterT = std::mem::zeroed();
+            if !sys::ecs_query_has_range(query, &mut range, &mut match_it) {
+                return false;
+            }
+            sys::ecs_iter_fini(&mut match_it);
+        }
+
+        // set up like flecs does for observers with a run action
+        let mut observer_it = *it;
+        observer_it.system = (*o).entity;
+        observer_it.ctx = o as *mut c_void;
+        observer_it.callback_ctx = (*o).callback_ctx;
+        observer_it.run_ctx = (*o).run_ctx;
+        observer_it.callback = (*o).callback;
+        observer_it.query = query;
+        observer_it.term_index = term_index as i32;
+        sys::ecs_observer_default_run_action(&mut observer_it)
+    }
+}
+
+/// Invokes the ordered observers for the same event that come before the observer and weren't
+/// invoked yet for this batch of entities, then the observer itself.
+unsafe fn run_ordered_observer(world: WorldRef, observer: EntityT, it: &mut IterT) {
+    let world_ptr = world.world_ptr_mut();
+    let batch = EventBatch::new(it);
+    let order = &mut world.world_ctx_mut().observer_order;
+    if !order.claim(it.event, observer, batch) {
+        return;
+    }
+
+    // collected up front, observers may register other observers when invoked
+    let before: Vec<EntityT> = order
+        .observers
+        .get(&it.event)
+        .map(|observers| {
+            observers
+                .iter()
+                .take_while(|other| other.entity != observer)
+                .filter(|other| other.last_batch != batch)
+                .map(|other| other.entity)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for other in before {
+        if is_propagation_stopped(world, it) {
+            return;
+        }
+        if !unsafe { sys::ecs_is_alive(world_ptr, other) } {
+            continue;
+        }
+        if unsafe { invoke_early(world_ptr, other, it) } {
+            world
+                .world_ctx_mut()
+                .observer_order
+                .claim(it.event, other, batch);
+        }
+    }
+
+    if !is_propagation_stopped(world, it) {
+        unsafe { sys::ecs_observer_default_run_action(it) };
+    }
+}
@@ -0,0 +1,177 @@
+//! Prefab hierarchies described by types, see [`Prefab`].
+
+use std::{marker::PhantomData, ops::Deref};
+
+use crate::core::*;
+
+/// A type that describes the components and slots of a prefab.
+///
+/// Usually implemented with `#[derive(Prefab)]`: fields are components that are set on the
+/// prefab, fields marked with `#[slot]` are child prefabs that are registered as slot of the
+/// prefab. The derive generates an instance type named after the prefab with `Instance`
+/// appended, that holds the instance entity and the instances of the slots, so nested slots are
+/// reachable from the instance of the root prefab. Instance types dereference to their entity.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Power(f32);
+///
+/// #[derive(Component)]
+/// struct Seats(u32);
+///
+/// #[derive(Prefab)]
+/// struct Engine {
+///     power: Power,
+/// }
+///
+/// #[derive(Prefab)]
+/// struct Pilot;
+///
+/// #[derive(Prefab)]
+/// struct Cockpit {
+///     seats: Seats,
+///     #[slot]
+///     pilot: Pilot,
+/// }
+///
+/// #[derive(Prefab)]
+/// struct Spaceship {
+///     #[slot]
+///     engine: Engine,
+///     #[slot]
+///     cockpit: Cockpit,
+/// }
+///
+/// let world = World::new();
+///
+/// let spaceship = world.prefab_from_named(
+///     "Spaceship",
+///     Spaceship {
+///         engine: Engine { power: Power(10.0) },
+///         cockpit: Cockpit {
+///             seats: Seats(2),
+///             pilot: Pilot,
+///         },
+///     },
+/// );
+///
+/// let inst = spaceship.instantiate_named("my_spaceship");
+/// assert_eq!(inst.engine.path().unwrap(), "::my_spaceship::engine");
+/// assert!(inst.engine.has::<Power>());
+/// assert_eq!(inst.cockpit.map::<&Seats, _>(|seats| seats.0), 2);
+/// assert_eq!(
+///     inst.cockpit.pilot.path().unwrap(),
+///     "::my_spaceship::cockpit::pilot"
+/// );
+/// ```
+pub trait Prefab: Sized {
+    /// The instantiated entities of the prefab.
+    type Instance<'a>: Copy;
+
+    /// Add the components and slots to the prefab entity.
+    fn build(self, prefab: EntityView<'_>);
+
+    /// Resolve the slots of an instance of the prefab.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefab` - The prefab entity
+    /// * `instance` - The entity that is an instance of the prefab
+    ///
+    /// # Returns
+    ///
+    /// The instance, or `None` if one of the slots isn't instantiated for the entity.
+    fn instance<'a>(prefab: EntityView<'a>, instance: EntityView<'a>)
+        -> Option<Self::Instance<'a>>;
+}
+
+/// Resolve the instance of the slot named `name` of the prefab, used by `#[derive(Prefab)]`.
+#[doc(hidden)]
+pub fn slot_instance<'a, T: Prefab>(
+    prefab: EntityView<'a>,
+    instance: EntityView<'a>,
+    name: &str,
+) -> Option<T::Instance<'a>> {
+    let slot = EntityView::new_from(prefab.world(), *prefab.try_lookup(name)?.id());
+    let target = instance.target_id(slot, 0);
+    if *target.id() == 0 {
+        return None;
+    }
+    T::instance(slot, target)
+}
+
+/// A prefab entity that was built from a [`Prefab`] type.
+pub struct TypedPrefab<'a, T: Prefab> {
+    pub entity: EntityView<'a>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: Prefab> Clone for TypedPrefab<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T: Prefab> Copy for TypedPrefab<'a, T> {}
+
+impl<'a, T: Prefab> Deref for TypedPrefab<'a, T> {
+    type Target = EntityView<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.entity
+    }
+}
+
+impl<'a, T: Prefab> TypedPrefab<'a, T> {
+    /// Build a prefab from its description.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - The prefab entity
+    /// * `prefab` - The components and slots of the prefab
+    pub fn new(entity: EntityView<'a>, prefab: T) -> Self {
+        entity.add_id(ECS_PREFAB);
+        prefab.build(entity);
+        Self {
+            entity,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create an instance of the prefab.
+    pub fn instantiate(&self) -> T::Instance<'a> {
+        self.instance(EntityView::new(self.entity.world()).is_a_id(self.entity))
+            .expect("slots are instantiated with the prefab")
+    }
+
+    /// Create a named instance of the prefab.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the instance
+    pub fn instantiate_named(&self, name: &str) -> T::Instance<'a> {
+        self.instance(EntityView::new_named(self.entity.world(), name).is_a_id(self.entity))
+            .expect("slots are instantiated with the prefab")
+    }
+
+    /// Resolve the slots of an existing instance of the prefab.
+    ///
+    /// # Arguments
+    ///
+    /// * `instance` - The entity that is an instance of the prefab
+    ///
+    /// # Returns
+    ///
+    /// The instance, or `None` if the entity isn't an instance of the prefab.
+    pub fn instance(&self, instance: impl Into<Entity>) -> Option<T::Instance<'a>> {
+        let instance = EntityView::new_from(self.entity.world(), instance.into());
+        if !instance.has_id((ECS_IS_A, *self.entity.id())) {
+            return None;
+        }
+        T::instance(self.entity, instance)
+    }
+}
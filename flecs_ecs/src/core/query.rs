@@ -5,6 +5,7 @@ use std::{marker::PhantomData, os::raw::c_void, ptr::NonNull};
 
 use sys::ecs_get_alive;
 
+use crate::core::internals::*;
 use crate::core::*;
 use crate::sys;
 
@@ -49,11 +50,20 @@ where
         // when they are either named or cached, such as system, cached queries and named queries. These queries have to be either explicitly
         // deleted with the .destruct() method, or will be deleted when the
         // world is deleted.
+        // Handles of shared queries are reference counted, but don't own the query either.
         unsafe {
-            if self.query.as_ref().entity == 0
-                && sys::flecs_poly_release_(self.query.as_ptr() as *mut c_void) == 0
+            let entity = self.query.as_ref().entity;
+            if entity == 0 {
+                if sys::flecs_poly_release_(self.query.as_ptr() as *mut c_void) == 0 {
+                    sys::ecs_query_fini(self.query.as_ptr());
+                }
+            } else if self
+                .world()
+                .world_ctx()
+                .shared_query_entities
+                .contains(&entity)
             {
-                sys::ecs_query_fini(self.query.as_ptr());
+                sys::flecs_poly_release_(self.query.as_ptr() as *mut c_void);
             }
         }
     }
@@ -178,26 +188,42 @@ where
         world: impl IntoWorld<'a>,
         entity: impl Into<Entity>,
     ) -> Option<Query<()>> {
-        let world_ptr = world.world_ptr_mut();
         let entity = *entity.into();
-        unsafe {
-            if ecs_get_alive(world_ptr, entity) != 0 {
-                let query_poly = sys::ecs_get_id(
-                    world_ptr,
-                    entity,
-                    ecs_pair(flecs::Poly::ID, flecs::Query::ID),
-                );
-
-                if !query_poly.is_null() {
-                    sys::flecs_poly_claim_(query_poly as *mut c_void);
-                    let query = NonNull::new_unchecked(query_poly as *mut QueryT);
-                    let new_query = Query::<()>::new_from(query);
-                    new_query.world().world_ctx_mut().inc_query_ref_count();
-                    return Some(new_query);
-                }
+        query_ptr_from_entity(world.world_ptr_mut(), entity)
+            .map(|query| unsafe { Query::<()>::new_from(query) })
+    }
+
+    /// Get the shared cached query for the given components, creating it if it doesn't exist yet.
+    ///
+    /// Shared queries are stored in a per world registry keyed on their normalized term list.
+    /// The query is associated with an entity and stays alive until it is destructed or the
+    /// world is deleted. Each handle claims a reference, see [`Query::reference_count`].
+    pub(crate) fn new_shared(world: &'_ World) -> Self {
+        let mut builder = QueryBuilder::<T>::new(world);
+        let key = shared_query_key(builder.query_desc());
+        let world_ptr = world.world_ptr_mut();
+
+        if let Some(&entity) = world.world_ctx().shared_queries.get(&key) {
+            if let Some(query) = query_ptr_from_entity(world_ptr, entity) {
+                ChangeMonitor::free_desc(builder.query_desc_mut());
+                return unsafe { Query::<T>::new_from(query) };
             }
-            None
+            // the shared query was destructed, create a new one
+            let world_ctx = world.world_ctx_mut();
+            world_ctx.shared_queries.remove(&key);
+            world_ctx.shared_query_entities.remove(&entity);
         }
+
+        builder.set_cached();
+        builder.query_desc_mut().entity =
+            unsafe { sys::ecs_entity_init(world_ptr, &Default::default()) };
+        let query = builder.build();
+
+        let entity = unsafe { query.query.as_ref().entity };
+        let world_ctx = world.world_ctx_mut();
+        world_ctx.shared_queries.insert(key, entity);
+        world_ctx.shared_query_entities.insert(entity);
+        query
     }
 
     /// Free the query
@@ -215,12 +241,21 @@ where
             "destruct() should only be called on queries associated with entities"
         );
 
-        if unsafe { (*self.query.as_ptr()).entity } != 0 {
-            if unsafe { sys::flecs_poly_release_(self.query.as_ptr() as *mut c_void) } > 0 {
+        let entity = unsafe { (*self.query.as_ptr()).entity };
+        if entity != 0 {
+            // the handle is consumed here, `Drop` must not release the query a second time
+            let world = self.world();
+            let world_ctx = world.world_ctx_mut();
+            world_ctx.dec_query_ref_count();
+            world_ctx.shared_query_entities.remove(&entity);
+            let query = self.query;
+            std::mem::forget(self);
+
+            if unsafe { sys::flecs_poly_release_(query.as_ptr() as *mut c_void) } > 0 {
                 panic!("The code base still has lingering references to `Query` objects. This is a bug in the user code. 
                 Please ensure that all `Query` objects are out of scope that are a clone/copy of the current one.");
             }
-            unsafe { sys::ecs_query_fini(self.query.as_ptr()) };
+            unsafe { sys::ecs_query_fini(query.as_ptr()) };
         }
     }
 
@@ -303,3 +338,193 @@ where
         }
     }
 }
+
+/// Get the query bound to an entity, if the entity is alive and has one.
+pub(crate) fn query_ptr_from_entity(
+    world: *mut WorldT,
+    entity: EntityT,
+) -> Option<NonNull<QueryT>> {
+    unsafe {
+        if ecs_get_alive(world, entity) == 0 {
+            return None;
+        }
+
+        let poly = sys::ecs_get_id(world, entity, ecs_pair(flecs::Poly::ID, flecs::Query::ID))
+            as *const sys::EcsPoly;
+        poly.as_ref()
+            .and_then(|poly| NonNull::new(poly.poly as *mut QueryT))
+    }
+}
+
+/// Build the registry key of a shared query from the terms in its descriptor.
+fn shared_query_key(desc: &sys::ecs_query_desc_t) -> Box<[u64]> {
+    let mut key = vec![desc.flags as u64, desc.cache_kind as u64];
+    for term in desc.terms.iter() {
+        if term.id == 0 && term.first.id == 0 && term.first.name.is_null() {
+            break;
+        }
+
+        key.extend_from_slice(&[
+            term.id,
+            term.src.id,
+            term.first.id,
+            term.second.id,
+            term.trav,
+            term.inout as u64,
+            term.oper as u64,
+        ]);
+
+        for name in [term.src.name, term.first.name, term.second.name] {
+            if !name.is_null() {
+                let name = unsafe { std::ffi::CStr::from_ptr(name) };
+                key.extend(name.to_bytes().iter().map(|&byte| byte as u64));
+            }
+            key.push(u64::MAX);
+        }
+    }
+    // `Changed<T>` terms only differ from other terms in their binding context
+    key.extend(ChangeMonitor::key(desc));
+    key.into_boxed_slice()
+}
+
+/// The query DSL rejects terms that would otherwise only fail at runtime, or silently do the wrong
+/// thing. The terms below are accepted:
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+/// }
+///
+/// #[derive(Component)]
+/// struct Tag;
+///
+/// let world = World::new();
+/// let query = query!(world, [inout] &mut Position, [in] &Position(up), [filter] Tag).build();
+/// ```
+///
+/// Access annotations that contradict the reference kind:
+///
+/// ```compile_fail
+/// # use flecs_ecs::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Position {
+/// #     x: f32,
+/// # }
+/// #
+/// # #[derive(Component)]
+/// # struct Tag;
+/// #
+/// # let world = World::new();
+/// query!(world, [in] &mut Position).build();
+/// ```
+///
+/// ```compile_fail
+/// # use flecs_ecs::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Position {
+/// #     x: f32,
+/// # }
+/// #
+/// # #[derive(Component)]
+/// # struct Tag;
+/// #
+/// # let world = World::new();
+/// query!(world, [inout] &Position).build();
+/// ```
+///
+/// Static terms that can't be accessed:
+///
+/// ```compile_fail
+/// # use flecs_ecs::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Position {
+/// #     x: f32,
+/// # }
+/// #
+/// # #[derive(Component)]
+/// # struct Tag;
+/// #
+/// # let world = World::new();
+/// query!(world, [out] &mut Position).build();
+/// ```
+///
+/// ```compile_fail
+/// # use flecs_ecs::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Position {
+/// #     x: f32,
+/// # }
+/// #
+/// # #[derive(Component)]
+/// # struct Tag;
+/// #
+/// # let world = World::new();
+/// query!(world, [none] &Position).build();
+/// ```
+///
+/// A component that is accessed mutably more than once on the same source:
+///
+/// ```compile_fail
+/// # use flecs_ecs::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Position {
+/// #     x: f32,
+/// # }
+/// #
+/// # #[derive(Component)]
+/// # struct Tag;
+/// #
+/// # let world = World::new();
+/// query!(world, &mut Position, &mut Position).build();
+/// ```
+///
+/// A component that is accessed mutably and immutably on the same source:
+///
+/// ```compile_fail
+/// # use flecs_ecs::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Position {
+/// #     x: f32,
+/// # }
+/// #
+/// # #[derive(Component)]
+/// # struct Tag;
+/// #
+/// # let world = World::new();
+/// query!(world, &mut Position, &Position).build();
+/// ```
+///
+/// More terms than a query supports:
+///
+/// ```compile_fail
+/// # use flecs_ecs::prelude::*;
+/// #
+/// # #[derive(Component)]
+/// # struct Position {
+/// #     x: f32,
+/// # }
+/// #
+/// # #[derive(Component)]
+/// # struct Tag;
+/// #
+/// # let world = World::new();
+/// query!(
+///     world,
+///     Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag,
+///     Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag,
+///     Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag,
+///     Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag, Tag
+/// )
+/// .build();
+/// ```
+#[cfg(doctest)]
+mod query_dsl_validation {}
@@ -10,6 +10,54 @@ impl std::fmt::Display for InvalidStrFromId {
     }
 }
 
+/// Error returned when deleting an entity is refused because it is in use by an id with the
+/// [`CleanupPolicy::Panic`](crate::core::CleanupPolicy::Panic) policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeleteError {
+    /// The entity that would have been deleted, either the entity itself or an entity that is
+    /// deleted along with it, such as a child.
+    pub entity: u64,
+    /// The id that is in use, the entity itself or a pair with the entity as relationship or target.
+    pub id: u64,
+}
+
+impl std::fmt::Display for DeleteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "can't delete entity {} while id {} with the Panic cleanup policy is in use",
+            self.entity, self.id
+        )
+    }
+}
+
+impl std::error::Error for DeleteError {}
+
+/// Error returned when moving an entity to a parent that already has a child with its name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameConflictError {
+    /// The entity that would have been moved.
+    pub entity: u64,
+    /// The parent the entity would have been moved to.
+    pub parent: u64,
+    /// The child of the parent with the name of the entity.
+    pub other: u64,
+    /// The name of the entity.
+    pub name: String,
+}
+
+impl std::fmt::Display for NameConflictError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "can't move entity {} to parent {}, its child {} is already named {}",
+            self.entity, self.parent, self.other, self.name
+        )
+    }
+}
+
+impl std::error::Error for NameConflictError {}
+
 /// Enum representing the error codes that can be used by `ecs_asserts` and `ecs_abort`
 pub enum FlecsErrorCode {
     InvalidOperation,
@@ -199,7 +199,7 @@ pub fn ecs_record_to_row(row: u32) -> i32 {
 /// * `entity`: The ID of the entity.
 /// * `value`: The value to set for the component.
 /// * `id`: The ID of the component type.
-pub(crate) fn set_helper<T: ComponentId>(world: *mut WorldT, entity: u64, value: T, id: u64) {
+pub(crate) fn set_helper<T: ComponentId>(world: *mut WorldT, entity: u64, mut value: T, id: u64) {
     ecs_assert!(
         std::mem::size_of::<T>() != 0,
         FlecsErrorCode::InvalidParameter,
@@ -209,11 +209,19 @@ pub(crate) fn set_helper<T: ComponentId>(world: *mut WorldT, entity: u64, value:
 
     let mut is_new = false;
     unsafe {
+        let replace_hook = ReplaceHook::get(world, id);
+
         if sys::ecs_is_deferred(world) {
+            // the value in the command buffer is a copy of the current value if the entity has it
+            let replace_hook = replace_hook.filter(|_| sys::ecs_has_id(world, entity, id));
+
             if T::NEEDS_DROP {
                 if T::IMPLS_DEFAULT {
                     //use set batching //faster performance, no panic possible
                     let comp = sys::ecs_ensure_modified_id(world, entity, id) as *mut T;
+                    if let Some(hook) = &replace_hook {
+                        hook.invoke(world, entity, comp, &mut value);
+                    }
                     //SAFETY: ecs_ensure_modified_id will default initialize the component
                     std::ptr::drop_in_place(comp);
                     std::ptr::write(comp, value);
@@ -222,6 +230,9 @@ pub(crate) fn set_helper<T: ComponentId>(world: *mut WorldT, entity: u64, value:
                     if sys::ecs_has_id(world, entity, id) {
                         //use set batching //faster performance, no panic possible since it's already present
                         let comp = sys::ecs_ensure_modified_id(world, entity, id) as *mut T;
+                        if let Some(hook) = &replace_hook {
+                            hook.invoke(world, entity, comp, &mut value);
+                        }
                         //SAFETY: ecs_ensure_modified_id will default initialize the component
                         std::ptr::drop_in_place(comp);
                         std::ptr::write(comp, value);
@@ -233,6 +244,9 @@ pub(crate) fn set_helper<T: ComponentId>(world: *mut WorldT, entity: u64, value:
                     let ptr = sys::ecs_emplace_id(world, entity, id, &mut is_new) as *mut T;
 
                     if !is_new {
+                        if let Some(hook) = &replace_hook {
+                            hook.invoke(world, entity, ptr, &mut value);
+                        }
                         std::ptr::drop_in_place(ptr);
                     }
                     std::ptr::write(ptr, value);
@@ -241,6 +255,9 @@ pub(crate) fn set_helper<T: ComponentId>(world: *mut WorldT, entity: u64, value:
             } else {
                 //if not needs drop, use set batching, faster performance
                 let comp = sys::ecs_ensure_modified_id(world, entity, id) as *mut T;
+                if let Some(hook) = &replace_hook {
+                    hook.invoke(world, entity, comp, &mut value);
+                }
                 std::ptr::drop_in_place(comp);
                 std::ptr::write(comp, value);
             }
@@ -250,6 +267,9 @@ pub(crate) fn set_helper<T: ComponentId>(world: *mut WorldT, entity: u64, value:
             let ptr = sys::ecs_emplace_id(world, entity, id, &mut is_new) as *mut T;
 
             if !is_new {
+                if let Some(hook) = &replace_hook {
+                    hook.invoke(world, entity, ptr, &mut value);
+                }
                 std::ptr::drop_in_place(ptr);
             }
             std::ptr::write(ptr, value);
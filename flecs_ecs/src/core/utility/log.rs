@@ -1,3 +1,5 @@
+use std::ffi::CString;
+
 use crate::sys;
 
 /// Sets the logging level to the specified value.
@@ -52,3 +54,20 @@ pub fn enable_timedelta_logging(enabled: bool) {
         sys::ecs_log_enable_timedelta(enabled);
     }
 }
+
+/// Logs a warning with the flecs logging API, like the `ecs_warn` macro of flecs.
+#[track_caller]
+pub(crate) fn log_warning(message: &str) {
+    let location = std::panic::Location::caller();
+    let file = CString::new(location.file()).unwrap_or_default();
+    let message = CString::new(message).unwrap_or_default();
+    unsafe {
+        sys::ecs_log_(
+            -2,
+            file.as_ptr(),
+            location.line() as i32,
+            c"%s".as_ptr(),
+            message.as_ptr(),
+        );
+    }
+}
@@ -15,6 +15,12 @@ pub trait IterOperations {
 
     #[doc(hidden)]
     fn query_ptr(&self) -> *const QueryT;
+
+    /// Progress the iterator to the next result that passes the `Changed<T>` terms of the query.
+    #[doc(hidden)]
+    fn next_result(&self, iter: &mut IterT) -> bool {
+        unsafe { next_result(self.query_ptr(), iter, |iter| self.iter_next(iter)) }
+    }
 }
 
 pub trait IterAPI<P, T>: IterOperations
@@ -46,7 +52,7 @@ where
             iter.flags |= sys::EcsIterIsInstanced;
             iter.flags |= sys::EcsIterCppEach;
 
-            while self.iter_next(&mut iter) {
+            while self.next_result(&mut iter) {
                 let mut components_data = T::create_ptrs(&iter);
                 let iter_count = {
                     if iter.count == 0 && iter.table.is_null() {
@@ -86,7 +92,7 @@ where
             iter.flags |= sys::EcsIterIsInstanced;
             iter.flags |= sys::EcsIterCppEach;
 
-            while self.iter_next(&mut iter) {
+            while self.next_result(&mut iter) {
                 let mut components_data = T::create_ptrs(&iter);
                 let iter_count = {
                     if iter.count == 0 && iter.table.is_null() {
@@ -123,7 +129,7 @@ where
 
     fn each_iter(&self, mut func: impl FnMut(Iter<false, P>, usize, T::TupleType<'_>))
     where
-        P: ComponentId,
+        P: EventId,
     {
         unsafe {
             let world = self.world_ptr_mut();
@@ -131,7 +137,7 @@ where
             iter.flags |= sys::EcsIterIsInstanced;
             iter.flags |= sys::EcsIterCppEach;
 
-            while self.iter_next(&mut iter) {
+            while self.next_result(&mut iter) {
                 let mut components_data = T::create_ptrs(&iter);
                 let iter_count = {
                     if iter.count == 0 && iter.table.is_null() {
@@ -177,7 +183,7 @@ where
             let mut entity: Option<EntityView> = None;
             let world = self.world_ptr_mut();
 
-            while self.iter_next(&mut iter) {
+            while self.next_result(&mut iter) {
                 let mut components_data = T::create_ptrs(&iter);
                 let iter_count = iter.count as usize;
 
@@ -223,7 +229,7 @@ where
             let mut entity_result: Option<EntityView> = None;
             let world = self.world_ptr_mut();
 
-            while self.iter_next(&mut iter) {
+            while self.next_result(&mut iter) {
                 let mut components_data = T::create_ptrs(&iter);
                 let iter_count = iter.count as usize;
 
@@ -267,14 +273,14 @@ where
         mut func: impl FnMut(Iter<false, P>, usize, T::TupleType<'_>) -> bool,
     ) -> Option<EntityView<'_>>
     where
-        P: ComponentId,
+        P: EventId,
     {
         unsafe {
             let mut iter = self.retrieve_iter();
             let mut entity_result: Option<EntityView> = None;
             let world = self.world_ptr_mut();
 
-            while self.iter_next(&mut iter) {
+            while self.next_result(&mut iter) {
                 let mut components_data = T::create_ptrs(&iter);
                 let iter_count = {
                     if iter.count == 0 {
@@ -377,13 +383,13 @@ where
     /// ```
     fn run_iter(&self, mut func: impl FnMut(Iter<false, P>, T::TupleSliceType<'_>))
     where
-        P: ComponentId,
+        P: EventId,
     {
         unsafe {
             let mut iter = self.retrieve_iter();
             let world = self.world_ptr_mut();
 
-            while self.iter_next(&mut iter) {
+            while self.next_result(&mut iter) {
                 let mut components_data = T::create_ptrs(&iter);
                 let iter_count = iter.count as usize;
 
@@ -479,7 +485,7 @@ where
     #[doc(alias = "iterable::run")]
     fn run(&self, mut func: impl FnMut(Iter<true, P>))
     where
-        P: ComponentId,
+        P: EventId,
     {
         let mut iter = self.retrieve_iter();
         let mut iter_t = unsafe { Iter::new(&mut iter) };
@@ -584,7 +590,7 @@ where
     #[doc(alias = "iterable::run")]
     fn run_each<FuncEach>(&self, mut func: impl FnMut(Iter<true, P>), mut func_each: FuncEach)
     where
-        P: ComponentId,
+        P: EventId,
         FuncEach: FnMut(T::TupleType<'_>),
     {
         let mut iter = self.retrieve_iter();
@@ -684,7 +690,7 @@ where
         mut func: impl FnMut(Iter<true, P>),
         mut func_each: FuncEachEntity,
     ) where
-        P: ComponentId,
+        P: EventId,
         FuncEachEntity: FnMut(EntityView, T::TupleType<'_>),
     {
         let mut iter = self.retrieve_iter();
@@ -29,7 +29,7 @@ pub mod private {
     pub trait internal_ReactorAPI<'a, P, T>
     where
         T: Iterable,
-        P: ComponentId,
+        P: EventId,
     {
         fn set_instanced(&mut self, instanced: bool);
 
@@ -5,7 +5,7 @@ use crate::core::*;
 pub trait ReactorAPI<'a, P, T>: Builder<'a> + private::internal_ReactorAPI<'a, P, T>
 where
     T: Iterable,
-    P: ComponentId,
+    P: EventId,
 {
     /// Set context
     ///
@@ -573,7 +573,7 @@ macro_rules! implement_reactor_api {
         impl<'a, P, T> internal_ReactorAPI<'a, P, T> for $type
         where
             T: Iterable,
-            P: ComponentId,
+            P: EventId,
         {
             fn set_instanced(&mut self, instanced: bool) {
                 self.is_instanced = instanced;
@@ -627,7 +627,7 @@ macro_rules! implement_reactor_api {
         impl<'a, P, T> ReactorAPI<'a, P, T> for $type
         where
             T: Iterable,
-            P: ComponentId,
+            P: EventId,
         {
             fn set_context(&mut self, context: *mut c_void) -> &mut Self {
                 self.desc.ctx = context;
@@ -75,7 +75,12 @@ impl Drop for World {
                     panic!("The code base still has lingering references to `Query` objects. This is a bug in the user code. 
                     Please ensure that all `Query` objects are out of scope before the world is destroyed.");
                 }
+                // the hooks of components of libraries run until the world is destroyed
+                #[cfg(feature = "flecs_module_dylib")]
+                let dylibs = std::mem::take(&mut self.world_ctx_mut().dylibs);
                 unsafe { sys::ecs_fini(self.raw_world.as_ptr()) };
+                #[cfg(feature = "flecs_module_dylib")]
+                drop(dylibs);
             }
         }
     }
@@ -2991,6 +2996,33 @@ impl World {
         result.add::<T>();
         result
     }
+
+    /// Creates a prefab from a type that describes its components and slots
+    ///
+    /// # Arguments
+    ///
+    /// * `prefab` - The components and slots of the prefab.
+    ///
+    /// # Returns
+    ///
+    /// The prefab, which can be used to create instances with their slots resolved.
+    pub fn prefab_from<T: Prefab>(&self, prefab: T) -> TypedPrefab<'_, T> {
+        TypedPrefab::new(EntityView::new(self), prefab)
+    }
+
+    /// Creates a named prefab from a type that describes its components and slots
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to use for the new prefab.
+    /// * `prefab` - The components and slots of the prefab.
+    ///
+    /// # Returns
+    ///
+    /// The prefab, which can be used to create instances with their slots resolved.
+    pub fn prefab_from_named<'a, T: Prefab>(&'a self, name: &str, prefab: T) -> TypedPrefab<'a, T> {
+        TypedPrefab::new(EntityView::new_named(self, name), prefab)
+    }
 }
 /// Id mixin implementation
 impl World {
@@ -3275,7 +3307,7 @@ impl World {
     ///
     /// * C++ API: `world::event`
     #[doc(alias = "world::event")]
-    pub fn event<T: ComponentId>(&self) -> EventBuilder<T> {
+    pub fn event<T: EventId>(&self) -> EventBuilder<T> {
         EventBuilder::<T>::new(self)
     }
 }
@@ -3315,7 +3347,7 @@ impl World {
     ///
     /// * C++ API: `world::observer`
     #[doc(alias = "world::observer")]
-    pub fn observer<Event: ComponentId, Components>(&self) -> ObserverBuilder<Event, Components>
+    pub fn observer<Event: EventId, Components>(&self) -> ObserverBuilder<Event, Components>
     where
         Components: Iterable,
     {
@@ -3352,7 +3384,7 @@ impl World {
     ///
     /// * C++ API: `world::observer`
     #[doc(alias = "world::observer")]
-    pub fn observer_named<'a, Event: ComponentId, Components>(
+    pub fn observer_named<'a, Event: EventId, Components>(
         &'a self,
         name: &str,
     ) -> ObserverBuilder<'a, Event, Components>
@@ -3407,6 +3439,43 @@ impl World {
         QueryBuilder::<Components>::new_named(self, name).build()
     }
 
+    /// Get a shared cached query, creating it on first use.
+    ///
+    /// Queries with the same normalized term list share a single cached query entity,
+    /// which avoids duplicate caches and matching overhead when several modules declare
+    /// the same query. Every returned handle adds a reference to the shared query. The handles
+    /// of a query with `Changed<T>` terms share which changes were visited.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `Components` - The components to match on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// let q1 = world.query_shared::<&Position>();
+    /// let q2 = world.query_shared::<&Position>();
+    ///
+    /// assert_eq!(q1.entity(), q2.entity());
+    /// assert_eq!(q1.reference_count(), 2);
+    /// ```
+    pub fn query_shared<Components>(&self) -> Query<Components>
+    where
+        Components: Iterable,
+    {
+        Query::<Components>::new_shared(self)
+    }
+
     /// Create a new query builder.
     ///
     /// # Type Parameters
@@ -3680,8 +3749,10 @@ impl World {
     #[doc(alias = "world::set_pipeline")]
     #[inline(always)]
     pub fn set_pipeline(&self, pipeline: impl Into<Entity>) {
+        let pipeline = *pipeline.into();
+        crate::addons::pipeline::SystemOrder::warn_if_unordered(self.into(), pipeline);
         unsafe {
-            sys::ecs_set_pipeline(self.raw_world.as_ptr(), *pipeline.into());
+            sys::ecs_set_pipeline(self.raw_world.as_ptr(), pipeline);
         }
     }
 
@@ -3700,9 +3771,7 @@ impl World {
     where
         Pipeline: ComponentType<Struct> + ComponentId,
     {
-        unsafe {
-            sys::ecs_set_pipeline(self.raw_world.as_ptr(), Pipeline::id(self));
-        }
+        self.set_pipeline(Pipeline::id(self));
     }
 
     /// Get the current pipeline.
@@ -1,10 +1,39 @@
-use super::{FlecsArray, FlecsIdMap, World};
+use super::{FlecsArray, FlecsIdMap, ObserverOrder, StagePropagation, World};
 use crate::sys;
 
+/// Shared cached queries, keyed on their normalized term list and mapped to the query entity.
+pub(crate) type SharedQueryMap = std::collections::HashMap<Box<[u64]>, u64, fxhash::FxBuildHasher>;
+
+/// The entities of the shared cached queries, whose handles are reference counted.
+pub(crate) type SharedQueryEntities = std::collections::HashSet<u64, fxhash::FxBuildHasher>;
+
 pub(crate) struct WorldCtx {
     query_ref_count: i32,
     pub(crate) components: FlecsIdMap,
     pub(crate) components_array: FlecsArray,
+    /// The entities of the event types that aren't components, see [`Event`](super::Event).
+    pub(crate) events: FlecsIdMap,
+    pub(crate) shared_queries: SharedQueryMap,
+    pub(crate) shared_query_entities: SharedQueryEntities,
+    pub(crate) observer_order: ObserverOrder,
+    #[cfg(feature = "flecs_pipeline")]
+    pub(crate) system_order: crate::addons::pipeline::SystemOrder,
+    /// The entities of the queries that match the systems of phases that are run outside of the
+    /// pipeline, per phase.
+    #[cfg(feature = "flecs_pipeline")]
+    pub(crate) phase_queries: std::collections::HashMap<u64, u64, fxhash::FxBuildHasher>,
+    /// The events observers stopped from propagating, per stage.
+    pub(crate) propagation: StagePropagation,
+    pub(crate) profiler: Option<std::sync::Arc<crate::addons::Profiler>>,
+    /// The modules whose dependencies are being imported, to detect cyclic dependencies.
+    #[cfg(feature = "flecs_module")]
+    pub(crate) importing_modules: Vec<(std::any::TypeId, &'static str)>,
+    /// The layout hashes of the registered components, see [`layout_hash`](crate::addons::layout_hash).
+    #[cfg(feature = "flecs_module_dylib")]
+    pub(crate) component_layouts: std::collections::HashMap<u64, u64, fxhash::FxBuildHasher>,
+    /// The libraries of the modules imported with [`World::import_dylib`], with their module entity.
+    #[cfg(feature = "flecs_module_dylib")]
+    pub(crate) dylibs: Vec<(u64, libloading::Library)>,
 }
 
 impl WorldCtx {
@@ -13,6 +42,22 @@ impl WorldCtx {
             query_ref_count: 0,
             components: Default::default(),
             components_array: vec![0; 2000],
+            events: Default::default(),
+            shared_queries: Default::default(),
+            shared_query_entities: Default::default(),
+            observer_order: Default::default(),
+            #[cfg(feature = "flecs_pipeline")]
+            system_order: Default::default(),
+            #[cfg(feature = "flecs_pipeline")]
+            phase_queries: Default::default(),
+            propagation: Default::default(),
+            profiler: None,
+            #[cfg(feature = "flecs_module")]
+            importing_modules: Vec::new(),
+            #[cfg(feature = "flecs_module_dylib")]
+            component_layouts: Default::default(),
+            #[cfg(feature = "flecs_module_dylib")]
+            dylibs: Vec::new(),
         }
     }
 
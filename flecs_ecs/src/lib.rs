@@ -13,6 +13,9 @@ pub mod core;
 
 pub mod addons;
 
+#[cfg(feature = "testing")]
+pub mod testing;
+
 /// this is to allow using the proc macro's inside lib itself that implements its own traits.
 extern crate self as flecs_ecs;
 
@@ -0,0 +1,359 @@
+//! Helpers to test worlds deterministically.
+//!
+//! A [`TestWorld`] steps the world with a fixed delta time, so tests don't depend on the time
+//! that passes between frames. The state of components is captured with [`Snapshot`], which
+//! formats deterministically so it can be compared with `insta::assert_snapshot!`, and can be
+//! diffed with another snapshot to assert what a number of frames changed.
+//!
+//! This module is only available with the `testing` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use flecs_ecs::prelude::*;
+//! use flecs_ecs::assert_component_eq;
+//! use flecs_ecs::testing::TestWorld;
+//!
+//! #[derive(Component, Debug, PartialEq)]
+//! struct Position {
+//!     x: i32,
+//! }
+//!
+//! let world = TestWorld::new();
+//! let e = world.entity_named("e").set(Position { x: 0 });
+//! world.system::<&mut Position>().each(|pos| pos.x += 1);
+//!
+//! let before = world.snapshot::<Position>();
+//! world.step(3);
+//! assert_component_eq!(e, Position { x: 3 });
+//!
+//! let diff = before.diff(&world.snapshot::<Position>());
+//! assert_eq!(
+//!     diff.to_string(),
+//!     "~ \"::e\" Position: \"Position { x: 0 }\" -> \"Position { x: 3 }\"\n"
+//! );
+//! ```
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+
+use crate::core::*;
+use crate::sys;
+
+/// A world that is stepped with a fixed delta time.
+pub struct TestWorld {
+    world: World,
+    delta_time: FTime,
+    frame: Cell<u64>,
+}
+
+impl Default for TestWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for TestWorld {
+    type Target = World;
+
+    fn deref(&self) -> &Self::Target {
+        &self.world
+    }
+}
+
+impl TestWorld {
+    /// The default delta time, of a frame at 60 frames per second.
+    pub const DEFAULT_DELTA_TIME: FTime = 1.0 / 60.0;
+
+    /// Create a new world, which is stepped with [`TestWorld::DEFAULT_DELTA_TIME`].
+    pub fn new() -> Self {
+        Self::from_world(World::new())
+    }
+
+    /// Wrap an existing world, which is stepped with [`TestWorld::DEFAULT_DELTA_TIME`].
+    pub fn from_world(world: World) -> Self {
+        Self {
+            world,
+            delta_time: Self::DEFAULT_DELTA_TIME,
+            frame: Cell::new(0),
+        }
+    }
+
+    /// Set the delta time of each step.
+    pub fn with_delta_time(mut self, delta_time: FTime) -> Self {
+        self.delta_time = delta_time;
+        self
+    }
+
+    /// Returns the delta time of each step.
+    pub fn delta_time(&self) -> FTime {
+        self.delta_time
+    }
+
+    /// Returns the number of frames that were stepped.
+    pub fn frame(&self) -> u64 {
+        self.frame.get()
+    }
+
+    /// Progress the world `frames` times with the fixed delta time.
+    ///
+    /// Stops early when the world is asked to quit.
+    pub fn step(&self, frames: u32) -> &Self {
+        for _ in 0..frames {
+            self.frame.set(self.frame.get() + 1);
+            if !self.world.progress_time(self.delta_time) {
+                break;
+            }
+        }
+        self
+    }
+
+    /// Capture the value of the component `T` of all entities that have it.
+    pub fn snapshot<T>(&self) -> Snapshot
+    where
+        T: ComponentId + NotEmptyComponent + Debug,
+    {
+        Snapshot::new().capture::<T>(&self.world)
+    }
+
+    /// Returns the wrapped world.
+    pub fn into_inner(self) -> World {
+        self.world
+    }
+}
+
+/// The values of components of entities, formatted with their `Debug` implementation.
+///
+/// Entities are sorted on their id and components on their name, so the snapshot of a world that
+/// is built the same way is the same on every run. Entities are named after their path, or `#id`
+/// when they have no name.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Snapshot {
+    entities: BTreeMap<Entity, SnapshotEntity>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SnapshotEntity {
+    name: String,
+    components: BTreeMap<String, String>,
+}
+
+impl Snapshot {
+    /// Create an empty snapshot.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Capture the value of the component `T` of all entities that have it.
+    pub fn capture<T>(mut self, world: &World) -> Self
+    where
+        T: ComponentId + NotEmptyComponent + Debug,
+    {
+        let id = T::id(world);
+        let component = world.component::<T>().name().to_string();
+        world.query::<()>().with_id(id).build().each_entity(|e, _| {
+            let value = unsafe { sys::ecs_get_id(world.world_ptr(), *e.id(), id) } as *const T;
+            let Some(value) = (unsafe { value.as_ref() }) else {
+                return;
+            };
+            self.entities
+                .entry(e.id())
+                .or_insert_with(|| SnapshotEntity {
+                    name: entity_name(e),
+                    components: BTreeMap::new(),
+                })
+                .components
+                .insert(component.clone(), format!("{value:?}"));
+        });
+        self
+    }
+
+    /// Returns the number of entities in the snapshot.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns whether the snapshot has no entities.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Returns the formatted value of a component of an entity.
+    pub fn get(&self, entity: impl Into<Entity>, component: &str) -> Option<&str> {
+        self.entities
+            .get(&entity.into())?
+            .components
+            .get(component)
+            .map(String::as_str)
+    }
+
+    /// Returns the changes from this snapshot to the `after` snapshot.
+    pub fn diff(&self, after: &Snapshot) -> SnapshotDiff {
+        let mut changes = Vec::new();
+        let mut entities: Vec<Entity> = self.entities.keys().copied().collect();
+        entities.extend(
+            after
+                .entities
+                .keys()
+                .filter(|e| !self.entities.contains_key(e)),
+        );
+        entities.sort_unstable();
+
+        let empty = BTreeMap::new();
+        for entity in entities {
+            let before_entity = self.entities.get(&entity);
+            let after_entity = after.entities.get(&entity);
+            let name = after_entity.or(before_entity).unwrap().name.clone();
+            let before = before_entity.map_or(&empty, |e| &e.components);
+            let after = after_entity.map_or(&empty, |e| &e.components);
+
+            let mut components: Vec<&String> = before.keys().chain(after.keys()).collect();
+            components.sort_unstable();
+            components.dedup();
+            for component in components {
+                let change = match (before.get(component), after.get(component)) {
+                    (Some(before), Some(after)) if before == after => continue,
+                    (Some(before), Some(after)) => SnapshotChange::Changed {
+                        before: before.clone(),
+                        after: after.clone(),
+                    },
+                    (None, Some(value)) => SnapshotChange::Added(value.clone()),
+                    (Some(value), None) => SnapshotChange::Removed(value.clone()),
+                    (None, None) => continue,
+                };
+                changes.push((name.clone(), component.clone(), change));
+            }
+        }
+        SnapshotDiff { changes }
+    }
+}
+
+/// Formats the snapshot as YAML, with a mapping of components for each entity.
+impl Display for Snapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for entity in self.entities.values() {
+            writeln!(f, "{:?}:", entity.name)?;
+            for (component, value) in &entity.components {
+                writeln!(f, "  {component}: {value:?}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A change of a component between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotChange {
+    /// The component was added, with its value.
+    Added(String),
+    /// The component was removed, with its last value.
+    Removed(String),
+    /// The value of the component changed.
+    Changed { before: String, after: String },
+}
+
+/// The changes between two snapshots, see [`Snapshot::diff`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SnapshotDiff {
+    /// The entity, component and change, sorted like the snapshots.
+    pub changes: Vec<(String, String, SnapshotChange)>,
+}
+
+impl SnapshotDiff {
+    /// Returns whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Formats a change per line, prefixed with `+` for added, `-` for removed and `~` for changed
+/// components.
+impl Display for SnapshotDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (entity, component, change) in &self.changes {
+            match change {
+                SnapshotChange::Added(value) => {
+                    writeln!(f, "+ {entity:?} {component}: {value:?}")?;
+                }
+                SnapshotChange::Removed(value) => {
+                    writeln!(f, "- {entity:?} {component}: {value:?}")?;
+                }
+                SnapshotChange::Changed { before, after } => {
+                    writeln!(f, "~ {entity:?} {component}: {before:?} -> {after:?}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn entity_name(entity: EntityView) -> String {
+    match entity.get_name() {
+        Some(_) => entity.path().unwrap_or_default(),
+        None => format!("#{}", entity.id()),
+    }
+}
+
+/// Compare a component of an entity with the expected value, used by [`assert_component_eq!`].
+#[doc(hidden)]
+pub fn check_component_eq<T>(entity: EntityView, expected: &T) -> Result<(), String>
+where
+    T: ComponentId + NotEmptyComponent + PartialEq + Debug,
+{
+    let world = entity.world();
+    let component = world.component::<T>().name().to_string();
+    let value =
+        unsafe { sys::ecs_get_id(world.world_ptr(), *entity.id(), T::id(world)) } as *const T;
+    match unsafe { value.as_ref() } {
+        Some(value) if value == expected => Ok(()),
+        Some(value) => Err(format!(
+            "component {component} of entity {} is {value:?}, expected {expected:?}",
+            entity_name(entity)
+        )),
+        None => Err(format!(
+            "entity {} doesn't have component {component}, expected {expected:?}",
+            entity_name(entity)
+        )),
+    }
+}
+
+/// Assert that a component of an entity is equal to the expected value.
+///
+/// The component is the type of the expected value, which must implement `PartialEq` and `Debug`.
+/// The entity is an [`EntityView`](crate::core::EntityView). A message can be added like with
+/// `assert_eq!`.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+/// use flecs_ecs::assert_component_eq;
+///
+/// #[derive(Component, Debug, PartialEq)]
+/// struct Health(u32);
+///
+/// let world = World::new();
+/// let e = world.entity().set(Health(100));
+///
+/// assert_component_eq!(e, Health(100));
+/// assert_component_eq!(e, Health(100), "after {} frames", 0);
+/// ```
+#[macro_export]
+macro_rules! assert_component_eq {
+    ($entity:expr, $expected:expr $(,)?) => {
+        if let ::std::result::Result::Err(message) =
+            $crate::testing::check_component_eq($entity, &$expected)
+        {
+            ::std::panic!("assertion failed: {}", message);
+        }
+    };
+    ($entity:expr, $expected:expr, $($arg:tt)+) => {
+        if let ::std::result::Result::Err(message) =
+            $crate::testing::check_component_eq($entity, &$expected)
+        {
+            ::std::panic!("assertion failed: {}: {}", message, ::std::format_args!($($arg)+));
+        }
+    };
+}
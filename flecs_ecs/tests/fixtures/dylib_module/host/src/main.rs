@@ -0,0 +1,26 @@
+use flecs_ecs::prelude::*;
+use flecs_ecs::Position;
+
+fn main() {
+    let plugin = std::env::args().nth(1).expect("path of the plugin");
+
+    let world = World::new();
+    let e = world.entity().set(Position { x: 0, y: 0 });
+
+    let module = unsafe { world.import_dylib(&plugin) }.unwrap();
+    assert!(world
+        .entity_from_id(module.entity())
+        .has::<flecs::EcsModule>());
+    world.progress();
+    e.get::<&Position>(|pos| assert_eq!(pos.x, 1));
+
+    assert!(unsafe { world.unload_dylib(module) });
+    world.progress();
+    e.get::<&Position>(|pos| assert_eq!(pos.x, 1));
+
+    // the library stays loaded until the world is destroyed
+    let module = unsafe { world.import_dylib(&plugin) }.unwrap();
+    drop(module);
+    world.progress();
+    e.get::<&Position>(|pos| assert_eq!(pos.x, 2));
+}
@@ -0,0 +1,15 @@
+use flecs_ecs::prelude::*;
+use flecs_ecs::Position;
+
+#[derive(Component)]
+struct PluginModule;
+
+impl Module for PluginModule {
+    fn module(world: &World) {
+        world
+            .system_named::<&mut Position>("Step")
+            .each(|pos| pos.x += 1);
+    }
+}
+
+flecs_ecs::flecs_module_export!(PluginModule, Position);
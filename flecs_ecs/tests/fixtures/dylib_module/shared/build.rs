@@ -0,0 +1,9 @@
+// rustc only exports the Rust symbols of a dylib, export the symbols of flecs as well so the host
+// and the plugin call the flecs of this library.
+fn main() {
+    let map = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("flecs.map");
+    println!(
+        "cargo:rustc-link-arg=-Wl,--version-script={}",
+        map.display()
+    );
+}
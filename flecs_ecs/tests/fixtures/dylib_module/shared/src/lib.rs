@@ -0,0 +1,9 @@
+pub use flecs_ecs::*;
+
+use flecs_ecs::prelude::*;
+
+#[derive(Component, Debug)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
@@ -0,0 +1,102 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+use flecs_ecs::addons::app::AppError;
+
+#[derive(Component, Clone, Default)]
+struct Frames(i32);
+
+fn count_frames(world: &World) {
+    world.set(Frames(0));
+    world
+        .system::<&mut Frames>()
+        .term_at(0)
+        .singleton()
+        .each(|frames| {
+            frames.0 += 1;
+        });
+}
+
+#[test]
+fn app_on_init_frames() {
+    let world = World::new();
+
+    let mut init_calls = 0;
+    let result = world
+        .app()
+        .set_frames(3)
+        .set_target_fps(0.0)
+        .on_init(|world| {
+            init_calls += 1;
+            count_frames(world);
+        })
+        .run();
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(init_calls, 1);
+    world.get::<&Frames>(|frames| assert_eq!(frames.0, 3));
+    assert!(world.should_quit());
+}
+
+#[test]
+fn app_run_action() {
+    let world = World::new();
+
+    let result = world
+        .app()
+        .on_init(count_frames)
+        .set_run_action(|runner| {
+            let world = runner.world();
+            while runner.frame() {
+                if world.cloned::<&Frames>().0 == 5 {
+                    return 2;
+                }
+            }
+            0
+        })
+        .run();
+
+    assert_eq!(result, Err(AppError { code: 2 }));
+    world.get::<&Frames>(|frames| assert_eq!(frames.0, 5));
+    // a custom run action doesn't quit the world, so it can keep running
+    assert!(!world.should_quit());
+    world.progress();
+    world.get::<&Frames>(|frames| assert_eq!(frames.0, 6));
+}
+
+#[test]
+fn app_system_quit() {
+    let world = World::new();
+
+    world.set(Frames(0));
+    world
+        .system::<&mut Frames>()
+        .term_at(0)
+        .singleton()
+        .each_iter(|it, _, frames| {
+            frames.0 += 1;
+            if frames.0 == 2 {
+                it.world().quit();
+            }
+        });
+
+    let result = world.app().set_target_fps(0.0).run();
+
+    assert_eq!(result, Ok(()));
+    world.get::<&Frames>(|frames| assert_eq!(frames.0, 2));
+    assert!(world.should_quit());
+}
+
+#[test]
+fn app_context() {
+    let world = World::new();
+
+    let mut value = 10;
+    let ctx = &mut value as *mut i32 as *mut std::ffi::c_void;
+    let result = world
+        .app()
+        .context(ctx)
+        .set_run_action(|runner| unsafe { *(runner.context() as *const i32) })
+        .run();
+
+    assert_eq!(result, Err(AppError { code: 10 }));
+}
@@ -87,3 +87,448 @@ fn temp_test_hook() {
         assert_eq!(unsafe { COUNT_SET_POS }, 3);
     }
 }
+
+#[test]
+fn component_on_replace_hook() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let replaced = Rc::new(RefCell::new(Vec::new()));
+    let world = World::new();
+
+    let log = replaced.clone();
+    world
+        .component::<Position>()
+        .on_replace(move |_e, old: &mut Position, new: &mut Position| {
+            log.borrow_mut().push((old.x, new.x));
+            new.y = old.y;
+        });
+
+    let e = world.entity().set(Position { x: 1, y: 2 });
+    assert!(replaced.borrow().is_empty());
+
+    e.set(Position { x: 3, y: 0 });
+    assert_eq!(*replaced.borrow(), vec![(1, 3)]);
+    e.get::<&Position>(|p| {
+        assert_eq!(p.x, 3);
+        assert_eq!(p.y, 2);
+    });
+
+    world.defer_begin();
+    e.set(Position { x: 5, y: 0 });
+    world.defer_end();
+    assert_eq!(*replaced.borrow(), vec![(1, 3), (3, 5)]);
+}
+
+#[test]
+fn component_replace_hook() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let first = Rc::new(Cell::new(0));
+    let second = Rc::new(Cell::new(0));
+    let world = World::new();
+
+    let count = first.clone();
+    world
+        .component::<Position>()
+        .on_set(move |_e, _p| count.set(count.get() + 1));
+    let count = second.clone();
+    world
+        .component::<Position>()
+        .on_set(move |_e, _p| count.set(count.get() + 1));
+
+    world.entity().set(Position { x: 1, y: 2 });
+    assert_eq!(first.get(), 0);
+    assert_eq!(second.get(), 1);
+}
+
+#[test]
+fn component_on_replace_hook_modified() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Component, Clone)]
+    struct Health(u32);
+
+    let replaced = Rc::new(RefCell::new(Vec::new()));
+    let world = World::new();
+
+    let log = replaced.clone();
+    world
+        .component::<Health>()
+        .on_replace(move |e, old: &mut Health, new: &mut Health| {
+            log.borrow_mut().push((e.id(), old.0, new.0));
+        });
+
+    let e = world.entity().set(Health(10));
+    e.modify::<Health>(|health| health.0 = 5);
+    assert_eq!(*replaced.borrow(), vec![(e.id(), 10, 5)]);
+
+    // a mutable get doesn't keep the old value, so `modified` has nothing to report
+    e.get::<&mut Health>(|health| health.0 = 3);
+    e.modified::<Health>();
+    assert_eq!(replaced.borrow().len(), 1);
+
+    e.modify::<Health>(|health| health.0 = 1);
+    assert_eq!(*replaced.borrow(), vec![(e.id(), 10, 5), (e.id(), 3, 1)]);
+}
+
+#[test]
+fn component_on_replace_hook_pair() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let replaced = Rc::new(RefCell::new(Vec::new()));
+    let world = World::new();
+
+    let log = replaced.clone();
+    world
+        .component::<Position>()
+        .on_replace(move |_e, old: &mut Position, new: &mut Position| {
+            log.borrow_mut().push((old.x, new.x));
+        });
+
+    let tgt = world.entity();
+    let e = world.entity().set_first(Position { x: 1, y: 2 }, tgt);
+    e.set_first(Position { x: 3, y: 4 }, tgt);
+    assert_eq!(*replaced.borrow(), vec![(1, 3)]);
+
+    let id = world.id_from_id((world.component::<Position>().id(), tgt));
+    e.set_id(Position { x: 5, y: 6 }, id);
+    assert_eq!(*replaced.borrow(), vec![(1, 3), (3, 5)]);
+}
+
+#[test]
+fn component_on_move_hook() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Component)]
+    struct Indexed {
+        id: u32,
+    }
+
+    let moved = Rc::new(RefCell::new(Vec::new()));
+    let world = World::new();
+
+    let log = moved.clone();
+    world
+        .component::<Indexed>()
+        .on_move(move |e, value: &mut Indexed| log.borrow_mut().push((e.id(), value.id)));
+
+    let e1 = world.entity().set(Indexed { id: 1 });
+    let e2 = world.entity().set(Indexed { id: 2 });
+    assert!(moved.borrow().is_empty());
+
+    // changing archetype moves the value to another table,
+    // and the last value of the old table into the row that was freed
+    e1.add::<Position>();
+    assert_eq!(*moved.borrow(), vec![(e1.id(), 1), (e2.id(), 2)]);
+    e1.get::<&Indexed>(|value| assert_eq!(value.id, 1));
+
+    let e3 = world.entity().set(Indexed { id: 3 }).add::<Position>();
+    moved.borrow_mut().clear();
+
+    // deleting an entity that isn't last in the table moves the last value into its row
+    e1.destruct();
+    assert_eq!(*moved.borrow(), vec![(e3.id(), 3)]);
+    e3.get::<&Indexed>(|value| assert_eq!(value.id, 3));
+}
+
+#[test]
+fn component_on_move_hook_grow() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Component)]
+    struct Indexed {
+        id: u32,
+    }
+
+    let moved = Rc::new(RefCell::new(Vec::new()));
+    let world = World::new();
+
+    let log = moved.clone();
+    world
+        .component::<Indexed>()
+        .on_move(move |e, value: &mut Indexed| log.borrow_mut().push((e.id(), value.id)));
+
+    // growing the column moves the values to a new buffer
+    let entities: Vec<_> = (0..64)
+        .map(|id| world.entity().set(Indexed { id }))
+        .collect();
+    assert!(!moved.borrow().is_empty());
+    for (entity, id) in moved.borrow().iter() {
+        assert_eq!(*entity, entities[*id as usize].id());
+    }
+}
+
+#[test]
+fn component_on_dtor_hook() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Component)]
+    struct Resource {
+        id: u32,
+        dropped: Rc<RefCell<Vec<u32>>>,
+    }
+
+    impl Drop for Resource {
+        fn drop(&mut self) {
+            self.dropped.borrow_mut().push(self.id);
+        }
+    }
+
+    let destructed = Rc::new(RefCell::new(Vec::new()));
+    let dropped = Rc::new(RefCell::new(Vec::new()));
+    {
+        let world = World::new();
+
+        let log = destructed.clone();
+        world
+            .component::<Resource>()
+            .on_dtor(move |value: &mut Resource| log.borrow_mut().push(value.id));
+
+        let resource = |id| Resource {
+            id,
+            dropped: dropped.clone(),
+        };
+
+        world.entity().set(resource(1));
+        let e2 = world.entity().set(resource(2));
+        let e3 = world.entity().set(resource(3));
+
+        e3.remove::<Resource>();
+        assert_eq!(*destructed.borrow(), vec![3]);
+
+        e2.destruct();
+        assert_eq!(*destructed.borrow(), vec![3, 2]);
+    }
+
+    assert_eq!(*destructed.borrow(), vec![3, 2, 1]);
+    // the destructor of the component still runs after the hook
+    assert_eq!(*dropped.borrow(), vec![3, 2, 1]);
+}
+
+#[test]
+fn component_on_dtor_hook_plain_type() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // no constructor, destructor or add/remove hooks
+    #[derive(Component)]
+    struct Indexed {
+        id: u32,
+    }
+
+    let destructed = Rc::new(RefCell::new(Vec::new()));
+    let moved = Rc::new(RefCell::new(Vec::new()));
+    let world = World::new();
+
+    let log = moved.clone();
+    world
+        .component::<Indexed>()
+        .on_move(move |e, value: &mut Indexed| log.borrow_mut().push((e.id(), value.id)));
+    let log = destructed.clone();
+    world
+        .component::<Indexed>()
+        .on_dtor(move |value: &mut Indexed| log.borrow_mut().push(value.id));
+
+    let e1 = world.entity().set(Indexed { id: 1 });
+    let e2 = world.entity().set(Indexed { id: 2 });
+
+    e1.add::<Position>();
+    assert_eq!(*moved.borrow(), vec![(e1.id(), 1), (e2.id(), 2)]);
+    assert!(destructed.borrow().is_empty());
+
+    e1.remove::<Indexed>();
+    assert_eq!(*destructed.borrow(), vec![1]);
+    e2.get::<&Indexed>(|value| assert_eq!(value.id, 2));
+}
+
+#[test]
+fn component_on_ctor_hook() {
+    #[derive(Component)]
+    struct Timer {
+        remaining: i32,
+    }
+
+    let world = World::new();
+
+    world
+        .component::<Timer>()
+        .on_ctor(|| Timer { remaining: 5 });
+
+    let e = world.entity().add::<Timer>();
+    e.get::<&Timer>(|timer| assert_eq!(timer.remaining, 5));
+}
+
+#[derive(Component)]
+#[flecs(exclusive)]
+struct Platoon;
+
+#[derive(Component)]
+#[flecs(symmetric)]
+struct TradesWith;
+
+#[derive(Component)]
+#[flecs(transitive, reflexive, acyclic)]
+struct LocatedIn;
+
+#[derive(Component)]
+#[flecs(with(Platoon), can_toggle)]
+struct Squad;
+
+#[derive(Component)]
+#[flecs(one_of(Self))]
+struct Color;
+
+#[derive(Component)]
+#[flecs(with(Leg))]
+struct Table;
+
+#[derive(Component)]
+#[flecs(with(Table))]
+struct Leg;
+
+#[test]
+fn component_traits_attribute() {
+    let world = World::new();
+
+    let platoon = world.component::<Platoon>();
+    assert!(platoon.has::<flecs::Exclusive>());
+    assert!(world.component::<TradesWith>().has::<flecs::Symmetric>());
+
+    let located_in = world.component::<LocatedIn>();
+    assert!(located_in.has::<flecs::Transitive>());
+    assert!(located_in.has::<flecs::Reflexive>());
+    assert!(located_in.has::<flecs::Acyclic>());
+
+    let squad = world.component::<Squad>();
+    assert!(squad.has_id((flecs::With::ID, platoon.id())));
+    assert!(squad.has::<flecs::CanToggle>());
+
+    let color = world.component::<Color>();
+    assert!(color.has_id((flecs::OneOf::ID, color.id())));
+}
+
+#[test]
+fn component_traits_attribute_with_cycle() {
+    let world = World::new();
+
+    // registering a component registers its traits once its id is known
+    let table = world.component::<Table>();
+    let leg = world.component::<Leg>();
+    assert!(table.has_id((flecs::With::ID, leg.id())));
+    assert!(leg.has_id((flecs::With::ID, table.id())));
+    assert_eq!(world.component::<Table>().id(), table.id());
+}
+
+#[test]
+fn component_traits_attribute_every_world() {
+    for _ in 0..2 {
+        let world = World::new();
+
+        let platoon_1 = world.entity();
+        let platoon_2 = world.entity();
+        let unit = world
+            .entity()
+            .add_first::<Platoon>(platoon_1)
+            .add_first::<Platoon>(platoon_2);
+        assert!(!unit.has_first::<Platoon>(platoon_1));
+        assert!(unit.has_first::<Platoon>(platoon_2));
+
+        let player = world.entity();
+        world.entity().add_first::<TradesWith>(player);
+        assert!(player.target::<TradesWith>(0).is_valid());
+
+        let unit = world.entity().add::<Squad>();
+        assert!(unit.has::<Platoon>());
+    }
+}
+
+#[derive(Component)]
+#[flecs(on_delete_target(delete))]
+struct OwnedBy;
+
+#[derive(Component)]
+#[flecs(on_delete_target(panic))]
+struct References;
+
+#[derive(Component)]
+#[flecs(on_delete(panic))]
+struct Asset;
+
+#[test]
+fn component_on_delete_policy() {
+    let world = World::new();
+
+    world
+        .component::<Position>()
+        .on_delete(CleanupPolicy::Delete);
+    let e = world.entity().set(Position { x: 1, y: 2 });
+
+    world.component::<Position>().destruct();
+    assert!(!e.is_alive());
+}
+
+#[test]
+fn component_on_delete_target_policy() {
+    let world = World::new();
+
+    let parent = world.entity();
+    let owned = world.entity().add_first::<OwnedBy>(parent);
+    let other = world.entity().add_first::<References>(world.entity());
+
+    assert!(world
+        .component::<OwnedBy>()
+        .has_id((flecs::OnDeleteTarget::ID, flecs::Delete::ID)));
+
+    parent.destruct();
+    assert!(!owned.is_alive());
+    assert!(other.is_alive());
+}
+
+#[test]
+fn component_try_destruct_panic_policy() {
+    let world = World::new();
+
+    let texture = world.entity();
+    let material = world.entity().add_first::<References>(texture);
+
+    let error = texture.try_destruct().unwrap_err();
+    assert_eq!(error.entity, texture.id());
+    assert_eq!(error.id, *world.id_first::<References>(texture).id());
+    assert!(texture.is_alive());
+
+    material.remove_first::<References>(texture);
+    assert!(texture.try_destruct().is_ok());
+    assert!(!texture.is_alive());
+}
+
+#[test]
+fn component_try_destruct_panic_policy_component() {
+    let world = World::new();
+
+    let asset = world.component::<Asset>().entity();
+    assert!(asset.try_destruct().is_err());
+    assert!(asset.is_alive());
+}
+
+#[test]
+fn component_try_destruct_panic_policy_children() {
+    let world = World::new();
+
+    let folder = world.entity();
+    let owned = world.entity().add_first::<OwnedBy>(folder);
+    let texture = world.entity().child_of_id(owned);
+    world.entity().add_first::<References>(texture);
+
+    let error = folder.try_destruct().unwrap_err();
+    assert_eq!(error.entity, texture.id());
+    assert!(folder.is_alive());
+    assert!(owned.is_alive());
+    assert!(texture.is_alive());
+}
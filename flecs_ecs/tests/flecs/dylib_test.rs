@@ -0,0 +1,183 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+
+#[derive(Component)]
+struct PluginModule;
+
+impl Module for PluginModule {
+    fn module(world: &World) {
+        world
+            .system_named::<&mut Position>("Step")
+            .each(|pos| pos.x += 1);
+    }
+}
+
+flecs_ecs::flecs_module_export!(PluginModule, Position, Velocity);
+
+fn plugin_export() -> ModuleExport {
+    unsafe { *flecs_module_export() }
+}
+
+#[test]
+fn dylib_export_verify_import() {
+    let world = World::new();
+    world.component::<Position>();
+
+    let export = plugin_export();
+    assert_eq!(export.component_count, 2);
+    assert!(unsafe { export.verify(&world) }.is_ok());
+
+    let module = unsafe { (export.import)(world.ptr_mut()) };
+    assert_eq!(module, *world.component::<PluginModule>().entity.id());
+    assert!(unsafe { (export.unload)(world.ptr_mut()) });
+    assert!(!unsafe { (export.unload)(world.ptr_mut()) });
+}
+
+fn verify_components(world: &World, layouts: &[ComponentLayout]) -> Result<(), DylibError> {
+    let export = ModuleExport {
+        components: layouts.as_ptr(),
+        component_count: layouts.len(),
+        ..plugin_export()
+    };
+    unsafe { export.verify(world) }
+}
+
+#[test]
+fn dylib_export_layout_mismatch() {
+    let world = World::new();
+    world.component::<Position>();
+
+    let mut layouts = [ComponentLayout::of::<Position>()];
+    layouts[0].hash ^= 1;
+
+    match verify_components(&world, &layouts) {
+        Err(DylibError::LayoutMismatch { component }) => {
+            assert!(component.ends_with("Position"));
+        }
+        other => panic!("unexpected result {other:?}"),
+    }
+}
+
+#[derive(Component)]
+struct Untyped {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn dylib_export_unregistered_components() {
+    let world = World::new();
+
+    // components that aren't registered yet are registered by the module
+    let layout = ComponentLayout::of::<Untyped>();
+    assert!(verify_components(&world, &[layout]).is_ok());
+
+    // registered without its type, only its size and alignment are known
+    unsafe {
+        let entity_desc = flecs_ecs::sys::ecs_entity_desc_t {
+            symbol: layout.symbol,
+            ..Default::default()
+        };
+        let entity = flecs_ecs::sys::ecs_entity_init(world.ptr_mut(), &entity_desc);
+        let component_desc = flecs_ecs::sys::ecs_component_desc_t {
+            _canary: 0,
+            entity,
+            type_: flecs_ecs::sys::ecs_type_info_t {
+                size: 8,
+                alignment: 4,
+                hooks: Default::default(),
+                component: 0,
+                name: std::ptr::null(),
+            },
+        };
+        flecs_ecs::sys::ecs_component_init(world.ptr_mut(), &component_desc);
+    }
+    assert!(verify_components(&world, &[layout]).is_ok());
+
+    let mut layouts = [layout];
+    layouts[0].alignment = 8;
+    assert!(matches!(
+        verify_components(&world, &layouts),
+        Err(DylibError::LayoutMismatch { .. })
+    ));
+}
+
+#[test]
+fn dylib_export_version_mismatch() {
+    let world = World::new();
+
+    let export = ModuleExport {
+        abi_version: MODULE_ABI_VERSION + 1,
+        ..plugin_export()
+    };
+    assert!(matches!(
+        unsafe { export.verify(&world) },
+        Err(DylibError::AbiMismatch { .. })
+    ));
+
+    let export = ModuleExport {
+        flecs_version: [0, 0, 0],
+        ..plugin_export()
+    };
+    assert!(matches!(
+        unsafe { export.verify(&world) },
+        Err(DylibError::FlecsVersionMismatch { .. })
+    ));
+}
+
+#[test]
+fn dylib_import_errors() {
+    let world = World::new();
+
+    assert!(matches!(
+        unsafe { world.import_dylib("does_not_exist.so") },
+        Err(DylibError::Load(_))
+    ));
+
+    #[cfg(target_os = "linux")]
+    assert!(matches!(
+        unsafe { world.import_dylib("libc.so.6") },
+        Err(DylibError::MissingExport)
+    ));
+}
+
+/// Builds the host and plugin of `tests/fixtures/dylib_module`, which link against the same copy
+/// of `flecs_ecs`, and runs the host, which imports and unloads the plugin.
+#[cfg(target_os = "linux")]
+#[test]
+fn dylib_import_unload_plugin() {
+    use std::path::Path;
+    use std::process::Command;
+
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/dylib_module");
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("dylib_module");
+    let cargo = env!("CARGO");
+
+    let status = Command::new(cargo)
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(fixture.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .env("RUSTFLAGS", "-C prefer-dynamic")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    // the host links against the standard library and flecs_ecs dynamically
+    let rustc = Path::new(cargo).with_file_name("rustc");
+    let output = Command::new(rustc)
+        .args(["--print", "target-libdir"])
+        .output()
+        .unwrap();
+    let std_dir = String::from_utf8(output.stdout).unwrap();
+    let out_dir = target_dir.join("debug");
+    let library_path = format!("{}:{}", out_dir.join("deps").display(), std_dir.trim());
+
+    let status = Command::new(out_dir.join("flecs_host"))
+        .arg(out_dir.join("libflecs_plugin.so"))
+        .env("LD_LIBRARY_PATH", library_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
@@ -0,0 +1,164 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+
+#[derive(Event)]
+struct Hit {
+    damage: i32,
+}
+
+#[test]
+fn events_read_since_last_read() {
+    let world = World::new();
+
+    let mut reader = world.event_reader::<Hit>();
+    world.send_event(Hit { damage: 1 });
+    world.send_event(Hit { damage: 2 });
+
+    let mut read = Vec::new();
+    reader.read(&world, |hit| read.push(hit.damage));
+    assert_eq!(read, [1, 2]);
+
+    world.send_event(Hit { damage: 3 });
+    assert_eq!(reader.len(&world), 1);
+
+    read.clear();
+    reader.read(&world, |hit| read.push(hit.damage));
+    assert_eq!(read, [3]);
+    assert!(reader.is_empty(&world));
+}
+
+#[test]
+fn events_cleared_after_two_frames() {
+    let world = World::new();
+
+    let reader = world.event_reader::<Hit>();
+    world
+        .event_writer::<Hit>()
+        .send_batch([Hit { damage: 1 }, Hit { damage: 2 }]);
+
+    world.progress();
+    world.send_event(Hit { damage: 3 });
+    assert_eq!(reader.len(&world), 3);
+
+    world.progress();
+    assert_eq!(reader.len(&world), 1);
+
+    // a new reader starts with the events still in the queue
+    let mut late = world.event_reader::<Hit>();
+    let mut read = Vec::new();
+    late.read(&world, |hit| read.push(hit.damage));
+    assert_eq!(read, [3]);
+
+    world.progress();
+    assert!(reader.is_empty(&world));
+    world.get::<&Events<Hit>>(|events| assert!(events.is_empty()));
+}
+
+#[test]
+fn events_read_in_system() {
+    #[derive(Component, Default)]
+    struct Total(i32);
+
+    let world = create_world_with_flags::<Total>();
+    world.add_events::<Hit>();
+
+    world
+        .system_named::<&Position>("send")
+        .each_entity(|e, pos| {
+            e.world().send_event(Hit { damage: pos.x });
+        });
+
+    let mut reader = world.event_reader::<Hit>();
+    world.system_named::<()>("read").run(move |it| {
+        let world = it.world();
+        reader.read(&world, |hit| {
+            world.get::<&mut Total>(|total| total.0 += hit.damage);
+        });
+    });
+
+    world.entity().set(Position { x: 1, y: 0 });
+    world.entity().set(Position { x: 2, y: 0 });
+
+    world.progress();
+    world.get::<&Total>(|total| assert_eq!(total.0, 3));
+
+    // every event is read once
+    world.progress();
+    world.get::<&Total>(|total| assert_eq!(total.0, 6));
+}
+
+#[test]
+fn events_send_while_reading() {
+    let world = World::new();
+
+    let mut reader = world.event_reader::<Hit>();
+    world.send_event(Hit { damage: 1 });
+    world.send_event(Hit { damage: 2 });
+
+    let mut read = Vec::new();
+    reader.read(&world, |hit| {
+        read.push(hit.damage);
+        world.send_event(Hit {
+            damage: hit.damage * 10,
+        });
+    });
+    assert_eq!(read, [1, 2]);
+    world.get::<&Events<Hit>>(|events| assert_eq!(events.len(), 4));
+
+    read.clear();
+    reader.read(&world, |hit| read.push(hit.damage));
+    assert_eq!(read, [10, 20]);
+}
+
+#[test]
+fn events_readers_are_independent() {
+    let world = World::new();
+
+    let mut first = world.event_reader::<Hit>();
+    let mut second = world.event_reader::<Hit>();
+    world.send_event(Hit { damage: 1 });
+
+    first.read(&world, |_| {});
+    assert!(first.is_empty(&world));
+    assert_eq!(second.len(&world), 1);
+
+    second.clear(&world);
+    assert!(second.is_empty(&world));
+}
+
+#[test]
+fn events_read_per_system() {
+    #[derive(Component, Default)]
+    struct Totals(i32, i32);
+
+    let world = create_world_with_flags::<Totals>();
+    world.add_events::<Hit>();
+
+    world.system_named::<()>("first").run(|it| {
+        let world = it.world();
+        it.read_events::<Hit>(|hit| {
+            world.get::<&mut Totals>(|totals| totals.0 += hit.damage);
+        });
+    });
+
+    let second = world.system_named::<()>("second").run(|it| {
+        let world = it.world();
+        it.read_events::<Hit>(|hit| {
+            world.get::<&mut Totals>(|totals| totals.1 += hit.damage);
+        });
+    });
+
+    world.send_event(Hit { damage: 1 });
+    world.progress();
+    world.get::<&Totals>(|totals| assert_eq!((totals.0, totals.1), (1, 1)));
+
+    // each system reads an event once
+    world.send_event(Hit { damage: 2 });
+    world.progress();
+    world.get::<&Totals>(|totals| assert_eq!((totals.0, totals.1), (3, 3)));
+
+    second.destruct();
+    world.send_event(Hit { damage: 4 });
+    world.progress();
+    world.get::<&Totals>(|totals| assert_eq!((totals.0, totals.1), (7, 3)));
+}
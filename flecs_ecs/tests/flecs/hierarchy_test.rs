@@ -0,0 +1,194 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+
+fn names<'a>(entities: impl IntoIterator<Item = EntityView<'a>>) -> Vec<String> {
+    entities.into_iter().map(|e| e.name().to_string()).collect()
+}
+
+#[test]
+fn hierarchy_insert_child_at() {
+    let world = World::new();
+
+    let parent = world.entity();
+    let a = world.entity_named("a");
+    let b = world.entity_named("b");
+    let c = world.entity_named("c");
+
+    parent
+        .insert_child_at(0, c)
+        .insert_child_at(0, a)
+        .insert_child_at(1, b);
+    assert_eq!(names(parent.children()), ["a", "b", "c"]);
+    assert!(b.has_id((flecs::ChildOf::ID, parent.id())));
+
+    // moving a child keeps the order when children change tables
+    parent.insert_child_at(0, c);
+    a.set(Position { x: 1, y: 2 });
+    assert_eq!(names(parent.children()), ["c", "a", "b"]);
+    assert_eq!(a.child_index(), Some(1));
+    assert_eq!(parent.child_index(), None);
+}
+
+#[test]
+fn hierarchy_ordered_children_unlisted() {
+    let world = World::new();
+
+    let parent = world.entity();
+    let a = world.entity_named("a").child_of_id(parent);
+    let b = world.entity_named("b");
+    parent.insert_child_at(0, b);
+    assert_eq!(names(parent.children()), ["b", "a"]);
+
+    world.entity_named("c").child_of_id(parent);
+    a.destruct();
+    assert_eq!(names(parent.children()), ["b", "c"]);
+}
+
+#[test]
+fn hierarchy_reparent() {
+    let world = World::new();
+
+    let old_parent = world.entity_named("old");
+    let new_parent = world.entity_named("new");
+    let child = world.entity_named("child");
+    old_parent.insert_child_at(0, child);
+    new_parent.insert_child_at(0, world.entity_named("first"));
+
+    child.reparent(new_parent);
+    assert_eq!(child.path().unwrap(), "::new::child");
+    assert_eq!(old_parent.children().len(), 0);
+    assert_eq!(names(new_parent.children()), ["first", "child"]);
+    old_parent.get::<&OrderedChildren>(|ordered| assert!(ordered.as_slice().is_empty()));
+
+    let unordered = world.entity_named("unordered");
+    child.reparent(unordered);
+    assert_eq!(names(unordered.children()), ["child"]);
+    assert_eq!(names(new_parent.children()), ["first"]);
+}
+
+#[test]
+fn hierarchy_reparent_keep_name() {
+    let world = World::new();
+
+    let parent = world.entity_named("parent");
+    let other = world.entity_named("child").child_of_id(parent);
+    let child = world.entity_named("child");
+
+    let err = child.reparent_keep_name(parent).unwrap_err();
+    assert_eq!(err.entity, *child.id());
+    assert_eq!(err.other, *other.id());
+    assert_eq!(err.name, "child");
+    assert_eq!(child.parent().id(), 0);
+    assert_eq!(names(parent.children()), ["child"]);
+
+    // moving to the parent it already has is not a conflict
+    assert_eq!(other.reparent_keep_name(parent), Ok(other));
+    let renamed = world.entity_named("renamed");
+    assert_eq!(renamed.reparent_keep_name(parent), Ok(renamed));
+    assert_eq!(names(parent.children().rev()), ["renamed", "child"]);
+}
+
+#[test]
+#[should_panic]
+fn hierarchy_reparent_name_conflict() {
+    let world = World::new();
+
+    let parent = world.entity();
+    world.entity_named("child").child_of_id(parent);
+    world.entity_named("child").reparent(parent);
+}
+
+#[test]
+fn hierarchy_ancestors_descendants() {
+    let world = World::new();
+
+    let root = world.entity_named("root");
+    let a = world.entity_named("a").child_of_id(root);
+    let b = world.entity_named("b");
+    root.insert_child_at(1, b);
+    let a1 = world.entity_named("a1").child_of_id(a);
+    world.entity_named("b1").child_of_id(b);
+
+    assert_eq!(names(a1.ancestors()), ["a", "root"]);
+    assert_eq!(names(root.ancestors()), Vec::<String>::new());
+    assert_eq!(names(root.descendants()), ["a", "a1", "b", "b1"]);
+    assert_eq!(names(a.descendants()), ["a1"]);
+}
+
+#[test]
+fn hierarchy_walk() {
+    let world = World::new();
+
+    let root = world.entity();
+    let a = world.entity_named("a");
+    let b = world.entity_named("b");
+    root.insert_child_at(0, a).insert_child_at(1, b);
+    world.entity_named("a1").child_of_id(a);
+    world.entity_named("b1").child_of_id(b);
+    world.entity_named("b2").child_of_id(b);
+
+    let mut visited = Vec::new();
+    root.walk_breadth_first(|e| {
+        visited.push(e.name().to_string());
+        Walk::Continue
+    });
+    assert_eq!(visited[..2], ["a", "b"]);
+    assert_eq!(visited.len(), 5);
+
+    visited.clear();
+    root.walk_depth_first(|e| {
+        visited.push(e.name().to_string());
+        if e.name() == "a" {
+            Walk::SkipChildren
+        } else if e.name() == "b1" || e.name() == "b2" {
+            Walk::Stop
+        } else {
+            Walk::Continue
+        }
+    });
+    assert_eq!(visited.len(), 3);
+    assert_eq!(visited[..2], ["a", "b"]);
+}
+
+#[test]
+fn hierarchy_clone_subtree() {
+    let world = World::new();
+
+    let root = world.entity_named("root");
+    let scene = world.entity_named("scene").child_of_id(root);
+    let b = world.entity_named("b").set(Position { x: 1, y: 2 });
+    let a = world.entity_named("a");
+    scene.insert_child_at(0, a).insert_child_at(1, b);
+    world.entity_named("a1").child_of_id(a);
+
+    let clone = scene.clone_subtree();
+    assert_ne!(clone, scene);
+    assert_eq!(clone.parent(), root);
+    assert_eq!(names(clone.descendants()), ["a", "a1", "b"]);
+    assert!(clone
+        .descendants()
+        .all(|e| e.ancestors().any(|p| p == clone)));
+
+    let b_clone = clone.lookup("b");
+    assert_ne!(b_clone, b);
+    assert_eq!(b_clone.map::<&Position, _>(|p| (p.x, p.y)), (1, 2));
+    assert_eq!(b.map::<&Position, _>(|p| (p.x, p.y)), (1, 2));
+    assert_eq!(scene.name(), "scene");
+    assert_eq!(names(scene.descendants()), ["a", "a1", "b"]);
+}
+
+#[test]
+fn hierarchy_delete_children() {
+    let world = World::new();
+
+    let parent = world.entity();
+    let a = world.entity();
+    parent.insert_child_at(0, a);
+    let a1 = world.entity().child_of_id(a);
+
+    parent.delete_children();
+    assert!(parent.is_alive());
+    assert!(!a.is_alive());
+    assert!(!a1.is_alive());
+    assert!(!parent.has::<OrderedChildren>());
+}
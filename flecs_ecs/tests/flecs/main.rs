@@ -5,12 +5,27 @@
 
 pub mod common_test;
 
+mod app_test;
 mod clone_default_impl_test;
 mod component_test;
+#[cfg(feature = "flecs_module_dylib")]
+mod dylib_test;
 mod entity_test;
 mod enum_test;
 mod eq_test;
+mod events_test;
+mod hierarchy_test;
 mod is_ref_test;
+mod module_test;
+mod observer_test;
+mod pipeline_test;
+mod prefab_test;
+mod profiler_test;
 mod query_builder_test;
+mod query_dsl_test;
 mod query_test;
+mod state_test;
+mod system_test;
+#[cfg(feature = "testing")]
+mod testing_test;
 mod world_test;
@@ -0,0 +1,128 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+
+#[derive(Component, Default)]
+struct ImportOrder(Vec<&'static str>);
+
+#[derive(Component)]
+#[flecs(module = "game.transform")]
+struct TransformModule;
+
+impl Module for TransformModule {
+    fn module(world: &World) {
+        world.get::<&mut ImportOrder>(|order| order.0.push("transform"));
+        world
+            .system_named::<&mut Position>("Clamp")
+            .each(|pos| pos.x = pos.x.min(100));
+    }
+}
+
+#[derive(Component)]
+#[flecs(import(TransformModule))]
+struct PhysicsModule;
+
+impl Module for PhysicsModule {
+    fn module(world: &World) {
+        world.get::<&mut ImportOrder>(|order| order.0.push("physics"));
+        world
+            .system_named::<(&mut Position, &Velocity)>("Move")
+            .each(|(pos, vel)| {
+                pos.x += vel.x;
+                pos.y += vel.y;
+            });
+        world
+            .observer_named::<flecs::OnAdd, &Velocity>("OnAddVelocity")
+            .each_entity(|e, _| {
+                e.add::<Mass>();
+            });
+    }
+}
+
+#[derive(Component)]
+#[flecs(import(PhysicsModule), import(TransformModule))]
+struct GameModule;
+
+impl Module for GameModule {
+    fn module(world: &World) {
+        world.get::<&mut ImportOrder>(|order| order.0.push("game"));
+    }
+}
+
+#[derive(Component)]
+#[flecs(import(CycleBModule))]
+struct CycleAModule;
+
+impl Module for CycleAModule {
+    fn module(_world: &World) {}
+}
+
+#[derive(Component)]
+#[flecs(import(CycleAModule))]
+struct CycleBModule;
+
+impl Module for CycleBModule {
+    fn module(_world: &World) {}
+}
+
+#[test]
+fn module_deps_imported_in_order() {
+    let world = World::new();
+    world.set(ImportOrder::default());
+
+    world.import::<GameModule>();
+    world.import::<GameModule>();
+
+    world.get::<&ImportOrder>(|order| assert_eq!(order.0, ["transform", "physics", "game"]));
+    assert!(world.component::<PhysicsModule>().has::<flecs::EcsModule>());
+}
+
+#[test]
+fn module_path_override() {
+    let world = World::new();
+    world.set(ImportOrder::default());
+
+    let module = world.import::<TransformModule>();
+    assert_eq!(module.path().unwrap(), "::game::transform");
+    assert_eq!(world.lookup("game::transform::Clamp").parent(), module);
+}
+
+#[test]
+fn module_unload() {
+    let world = World::new();
+    world.set(ImportOrder::default());
+
+    let physics = world.import::<PhysicsModule>();
+    let transform = world.component::<TransformModule>().entity;
+    let system = physics.lookup("Move");
+
+    let e = world
+        .entity()
+        .set(Position { x: 0, y: 0 })
+        .set(Velocity { x: 1, y: 2 });
+    assert!(e.has::<Mass>());
+    world.progress();
+
+    assert!(world.unload::<PhysicsModule>());
+    assert!(!world.unload::<PhysicsModule>());
+    assert!(!physics.is_alive());
+    assert!(transform.is_alive());
+    assert!(!system.is_alive());
+
+    world.progress();
+    e.get::<&Position>(|pos| assert_eq!((pos.x, pos.y), (1, 2)));
+    let e2 = world.entity().set(Velocity { x: 1, y: 1 });
+    assert!(!e2.has::<Mass>());
+
+    // the module can be imported again, its dependencies are only imported once
+    world.import::<PhysicsModule>();
+    world.progress();
+    e.get::<&Position>(|pos| assert_eq!((pos.x, pos.y), (2, 4)));
+    world.get::<&ImportOrder>(|order| assert_eq!(order.0, ["transform", "physics", "physics"]));
+}
+
+#[test]
+#[should_panic(expected = "cyclic module dependency")]
+fn module_deps_cycle() {
+    let world = World::new();
+    world.import::<CycleAModule>();
+}
@@ -0,0 +1,464 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+
+#[derive(Event)]
+struct Damage {
+    amount: i32,
+}
+
+#[derive(Event)]
+struct Ping;
+
+#[test]
+fn observer_each_event_payload() {
+    let world = World::new();
+
+    world
+        .observer::<Damage, (&mut Position,)>()
+        .each_event(|_e, damage, (p,)| {
+            p.x -= damage.amount;
+        });
+
+    let e1 = world.entity().set(Position { x: 100, y: 0 });
+    let e2 = world.entity().set(Position { x: 50, y: 0 });
+
+    world
+        .event()
+        .add::<Position>()
+        .target(e1)
+        .emit(&Damage { amount: 10 });
+    e1.get::<&Position>(|p| assert_eq!(p.x, 90));
+    e2.get::<&Position>(|p| assert_eq!(p.x, 50));
+
+    world
+        .event()
+        .add::<Position>()
+        .target(e2)
+        .emit(&Damage { amount: 5 });
+    e2.get::<&Position>(|p| assert_eq!(p.x, 45));
+}
+
+#[test]
+fn observer_each_event_tag_event() {
+    #[derive(Component, Default)]
+    struct Count(u32);
+
+    let world = create_world_with_flags::<Count>();
+
+    world
+        .observer::<Ping, &Position>()
+        .each_event(|e, _ping: &Ping, _p| {
+            e.world().get::<&mut Count>(|count| count.0 += 1);
+        });
+
+    let e = world.entity().set(Position { x: 1, y: 2 });
+    for _ in 0..2 {
+        world.event().add::<Position>().target(e).emit(&Ping);
+    }
+
+    world.get::<&Count>(|count| assert_eq!(count.0, 2));
+}
+
+#[derive(Component, Default)]
+struct Invoked(Vec<&'static str>);
+
+fn invoked(world: &World) -> Vec<&'static str> {
+    world.map::<&Invoked, _>(|invoked| invoked.0.clone())
+}
+
+#[test]
+fn observer_priority_order() {
+    let world = create_world_with_flags::<Invoked>();
+
+    for (name, priority) in [("low", -1), ("high", 10), ("default", 0), ("mid", 5)] {
+        world
+            .observer::<flecs::OnSet, &Position>()
+            .priority(priority)
+            .each_entity(move |e, _| {
+                e.world()
+                    .get::<&mut Invoked>(|invoked| invoked.0.push(name));
+            });
+    }
+
+    world.entity().set(Position { x: 1, y: 2 });
+    assert_eq!(invoked(&world), ["high", "mid", "default", "low"]);
+}
+
+#[test]
+fn observer_priority_same_priority_in_creation_order() {
+    let world = create_world_with_flags::<Invoked>();
+
+    for name in ["a", "b", "c", "d", "e"] {
+        world
+            .observer::<flecs::OnAdd, &Position>()
+            .priority(0)
+            .each_entity(move |e, _| {
+                e.world()
+                    .get::<&mut Invoked>(|invoked| invoked.0.push(name));
+            });
+    }
+
+    world.entity().set(Position { x: 1, y: 2 });
+    world.entity().set(Position { x: 3, y: 4 });
+    assert_eq!(
+        invoked(&world),
+        ["a", "b", "c", "d", "e", "a", "b", "c", "d", "e"]
+    );
+}
+
+#[test]
+fn observer_depends_on() {
+    let world = create_world_with_flags::<Invoked>();
+
+    let first = world
+        .observer::<flecs::OnSet, &Position>()
+        .priority(0)
+        .each_entity(|e, _| {
+            e.world()
+                .get::<&mut Invoked>(|invoked| invoked.0.push("first"));
+        });
+
+    // runs after first despite its higher priority
+    world
+        .observer::<flecs::OnSet, (&Position, &Velocity)>()
+        .priority(10)
+        .depends_on(first.id())
+        .each_entity(|e, _| {
+            e.world()
+                .get::<&mut Invoked>(|invoked| invoked.0.push("second"));
+        });
+
+    world
+        .observer::<flecs::OnSet, &Position>()
+        .priority(5)
+        .each_entity(|e, _| {
+            e.world()
+                .get::<&mut Invoked>(|invoked| invoked.0.push("other"));
+        });
+
+    world
+        .entity()
+        .set(Velocity { x: 1, y: 1 })
+        .set(Position { x: 1, y: 2 });
+    assert_eq!(invoked(&world), ["other", "first", "second"]);
+}
+
+#[test]
+#[should_panic]
+fn observer_depends_on_unordered_observer() {
+    let world = World::new();
+
+    let unordered = world.observer::<flecs::OnSet, &Position>().each(|_| {});
+
+    world
+        .observer::<flecs::OnSet, &Position>()
+        .depends_on(unordered.id());
+}
+
+#[test]
+fn observer_propagate_childof() {
+    let world = create_world_with_flags::<Invoked>();
+
+    world
+        .observer::<flecs::OnSet, (&Position, &Position)>()
+        .term_at(1)
+        .parent()
+        .each_entity(|e, (pos, parent_pos)| {
+            assert_eq!(pos.x, 10);
+            assert_eq!(parent_pos.x, 1);
+            let name = e.get_name().unwrap_or_default();
+            e.world().get::<&mut Invoked>(|invoked| {
+                invoked.0.push(if name == "a" { "a" } else { "b" });
+            });
+        });
+
+    let parent = world.entity();
+    world
+        .entity_named("a")
+        .child_of_id(parent)
+        .set(Position { x: 10, y: 0 });
+    world
+        .entity_named("b")
+        .child_of_id(parent)
+        .set(Position { x: 10, y: 0 })
+        .set(Velocity { x: 0, y: 0 });
+    world.entity_named("c").child_of_id(parent);
+    assert!(invoked(&world).is_empty());
+
+    // propagated to the children of parent with a Position
+    parent.set(Position { x: 1, y: 0 });
+    let mut children = invoked(&world);
+    children.sort();
+    assert_eq!(children, ["a", "b"]);
+}
+
+#[test]
+fn observer_bubble_childof() {
+    let world = create_world_with_flags::<Invoked>();
+
+    world.observer::<Ping, &Position>().each_entity(|e, _| {
+        let name = match e.get_name() {
+            Some("window") => "window",
+            Some("panel") => "panel",
+            _ => "button",
+        };
+        e.world()
+            .get::<&mut Invoked>(|invoked| invoked.0.push(name));
+    });
+
+    let window = world.entity_named("window").set(Position { x: 0, y: 0 });
+    let panel = world
+        .entity_named("panel")
+        .child_of_id(window)
+        .set(Position { x: 0, y: 0 });
+    let button = world
+        .entity_named("button")
+        .child_of_id(panel)
+        .set(Position { x: 0, y: 0 });
+
+    world
+        .event()
+        .add::<Position>()
+        .target(button)
+        .bubble::<flecs::ChildOf>()
+        .emit(&Ping);
+    assert_eq!(invoked(&world), ["button", "panel", "window"]);
+}
+
+#[test]
+fn observer_stop_propagation() {
+    let world = create_world_with_flags::<Invoked>();
+
+    world
+        .observer::<Ping, &Position>()
+        .priority(1)
+        .each_iter(|it, index, _| {
+            let e = it.entity(index);
+            if e.has::<Velocity>() {
+                it.stop_propagation();
+            }
+            e.world()
+                .get::<&mut Invoked>(|invoked| invoked.0.push("first"));
+        });
+
+    // still invoked for the entity that stopped propagation
+    world
+        .observer::<Ping, &Position>()
+        .priority(0)
+        .each_entity(|e, _| {
+            e.world()
+                .get::<&mut Invoked>(|invoked| invoked.0.push("second"));
+        });
+
+    let window = world.entity().set(Position { x: 0, y: 0 });
+    let panel = world
+        .entity()
+        .child_of_id(window)
+        .set(Position { x: 0, y: 0 })
+        .set(Velocity { x: 0, y: 0 });
+    let button = world
+        .entity()
+        .child_of_id(panel)
+        .set(Position { x: 0, y: 0 });
+
+    world
+        .event()
+        .add::<Position>()
+        .target(button)
+        .bubble::<flecs::ChildOf>()
+        .emit(&Ping);
+    assert_eq!(invoked(&world), ["first", "second", "first", "second"]);
+
+    // propagation is not stopped for the next event
+    world.get::<&mut Invoked>(|invoked| invoked.0.clear());
+    world
+        .event()
+        .add::<Position>()
+        .target(window)
+        .bubble::<flecs::ChildOf>()
+        .emit(&Ping);
+    assert_eq!(invoked(&world), ["first", "second"]);
+}
+
+#[test]
+fn observer_stop_propagation_up() {
+    let world = create_world_with_flags::<Invoked>();
+
+    world
+        .observer::<flecs::OnSet, &Position>()
+        .priority(1)
+        .each_iter(|it, index, _| {
+            if it.entity(index).has::<Velocity>() {
+                it.stop_propagation();
+            }
+        });
+
+    world
+        .observer::<flecs::OnSet, (&Position, &Position)>()
+        .term_at(1)
+        .parent()
+        .each_entity(|e, _| {
+            let name = e.get_name().unwrap_or_default();
+            e.world().get::<&mut Invoked>(|invoked| {
+                invoked.0.push(if name == "a" { "a" } else { "b" });
+            });
+        });
+
+    let parent = world.entity();
+    world
+        .entity_named("a")
+        .child_of_id(parent)
+        .set(Position { x: 10, y: 0 });
+    let other = world.entity();
+    world
+        .entity_named("b")
+        .child_of_id(other)
+        .set(Position { x: 10, y: 0 });
+
+    // not propagated to the children of the parent that stopped propagation
+    parent.set(Velocity { x: 0, y: 0 });
+    parent.set(Position { x: 1, y: 0 });
+    assert!(invoked(&world).is_empty());
+
+    other.set(Position { x: 1, y: 0 });
+    assert_eq!(invoked(&world), ["b"]);
+}
+
+#[test]
+fn observer_native_dispatch() {
+    let world = World::new();
+
+    let run_action = |observer: Observer| unsafe {
+        (*flecs_ecs::sys::ecs_observer_get(world.ptr_mut(), *observer.id())).run
+    };
+
+    // dispatched by flecs, it isn't affected by stopped propagation
+    let plain = world.observer::<flecs::OnSet, &Position>().each(|_| {});
+    assert!(run_action(plain).is_none());
+
+    let ordered = world
+        .observer::<flecs::OnSet, &Position>()
+        .priority(1)
+        .each(|_| {});
+    assert!(run_action(ordered).is_some());
+
+    let up = world
+        .observer::<flecs::OnSet, &Position>()
+        .term_at(0)
+        .parent()
+        .each(|_| {});
+    assert!(run_action(up).is_some());
+}
+
+#[test]
+fn observer_ordered_deleted() {
+    let world = create_world_with_flags::<Invoked>();
+
+    let first = world
+        .observer::<flecs::OnSet, &Position>()
+        .priority(1)
+        .each_entity(|e, _| {
+            e.world()
+                .get::<&mut Invoked>(|invoked| invoked.0.push("first"));
+        });
+
+    world
+        .observer::<flecs::OnSet, &Position>()
+        .priority(0)
+        .each_entity(|e, _| {
+            e.world()
+                .get::<&mut Invoked>(|invoked| invoked.0.push("second"));
+        });
+
+    first.destruct();
+    world.entity().set(Position { x: 1, y: 2 });
+    assert_eq!(invoked(&world), ["second"]);
+}
+
+#[test]
+#[should_panic]
+fn observer_depends_on_deleted_observer() {
+    let world = World::new();
+
+    let first = world
+        .observer::<flecs::OnSet, &Position>()
+        .priority(0)
+        .each(|_| {});
+    let id = first.id();
+    first.destruct();
+
+    world.observer::<flecs::OnSet, &Position>().depends_on(id);
+}
+
+#[test]
+fn observer_dispatch_event_monitor() {
+    let world = create_world_with_flags::<Invoked>();
+
+    world
+        .observer::<flecs::Monitor, (&Position, &Velocity)>()
+        .each_iter(|it, _, _| {
+            let push = |kind| {
+                it.world()
+                    .get::<&mut Invoked>(|invoked| invoked.0.push(kind));
+            };
+            it.dispatch_event()
+                .on::<flecs::OnAdd>(|| push("enter"))
+                .on::<flecs::OnRemove>(|| push("leave"))
+                .otherwise(|| push("other"));
+        });
+
+    let e = world.entity().set(Position { x: 1, y: 2 });
+    assert!(invoked(&world).is_empty());
+
+    e.set(Velocity { x: 1, y: 1 });
+    e.remove::<Position>();
+    assert_eq!(invoked(&world), ["enter", "leave"]);
+}
+
+#[test]
+fn observer_dispatch_event_multiple_events() {
+    let world = create_world_with_flags::<Invoked>();
+
+    world
+        .observer::<flecs::OnSet, &Position>()
+        .add_event::<Ping>()
+        .add_event::<flecs::OnRemove>()
+        .each_iter(|it, _, _| {
+            let mut kind = "other";
+            let dispatch = it
+                .dispatch_event()
+                .on::<Ping>(|| kind = "ping")
+                .on::<flecs::OnSet>(|| kind = "set");
+            if !dispatch.is_dispatched() && it.is_event::<flecs::OnRemove>() {
+                kind = "remove";
+            }
+            assert_eq!(it.is_event::<Ping>(), kind == "ping");
+            it.world()
+                .get::<&mut Invoked>(|invoked| invoked.0.push(kind));
+        });
+
+    let e = world.entity().set(Position { x: 1, y: 2 });
+    world.event().add::<Position>().target(e).emit(&Ping);
+    e.remove::<Position>();
+    assert_eq!(invoked(&world), ["set", "ping", "remove"]);
+}
+
+#[test]
+fn observer_event_is_not_a_component() {
+    let world = World::new();
+
+    let event = world.entity_from_id(Damage::event_id(&world));
+    assert_eq!(event.id(), Damage::event_id(&world));
+    assert!(!event.has::<flecs::Component>());
+    assert_ne!(Damage::event_id(&world), Ping::event_id(&world));
+}
+
+#[test]
+#[should_panic]
+fn observer_each_event_other_events() {
+    let world = World::new();
+
+    let mut observer = world.observer::<Damage, &mut Position>();
+    observer.add_event::<Ping>();
+    observer.each_event(|_e, damage, p| p.x -= damage.amount);
+}
@@ -0,0 +1,273 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+
+#[derive(Component, Default)]
+struct Steps {
+    count: u32,
+    delta_time: FTime,
+}
+
+fn add_fixed_system(world: &World) {
+    world.set(Steps::default());
+    world
+        .system::<&mut Steps>()
+        .term_at(0)
+        .singleton()
+        .kind::<FixedUpdate>()
+        .each_iter(|it, _, steps| {
+            steps.count += 1;
+            steps.delta_time = it.delta_time();
+        });
+}
+
+fn step_count(world: &World) -> u32 {
+    world.map::<&Steps, _>(|steps| steps.count)
+}
+
+fn assert_time_eq(actual: FTime, expected: FTime) {
+    assert!(
+        (actual - expected).abs() < 1e-6,
+        "time {actual} is not {expected}"
+    );
+}
+
+#[test]
+fn pipeline_fixed_update_accumulator() {
+    let world = World::new();
+    world.set_fixed_timestep(8.0);
+    add_fixed_system(&world);
+
+    world.progress_time(0.0625);
+    assert_eq!(step_count(&world), 0);
+    world.get::<&FixedTime>(|time| {
+        assert_eq!(time.steps(), 0);
+        assert_time_eq(time.alpha(), 0.5);
+    });
+
+    world.progress_time(0.0625);
+    assert_eq!(step_count(&world), 1);
+    world.get::<&FixedTime>(|time| assert_time_eq(time.alpha(), 0.0));
+
+    world.progress_time(0.3125);
+    assert_eq!(step_count(&world), 3);
+    world.get::<&FixedTime>(|time| {
+        assert_eq!(time.steps(), 2);
+        assert_time_eq(time.alpha(), 0.5);
+    });
+    world.get::<&Steps>(|steps| assert_time_eq(steps.delta_time, 0.125));
+}
+
+#[test]
+fn pipeline_fixed_update_max_steps() {
+    let world = World::new();
+    world.set_fixed_timestep(8.0);
+    world.get::<&mut FixedTime>(|time| time.max_steps = 3);
+    add_fixed_system(&world);
+
+    world.progress_time(1.0625);
+    assert_eq!(step_count(&world), 3);
+    world.get::<&FixedTime>(|time| assert_time_eq(time.alpha(), 0.5));
+}
+
+#[test]
+fn pipeline_fixed_update_system_added_later() {
+    let world = World::new();
+    world.set_fixed_timestep(8.0);
+    world.progress_time(0.125);
+
+    // the systems of the phase are matched by a query that is created when the phase first runs
+    add_fixed_system(&world);
+    world.progress_time(0.125);
+    assert_eq!(step_count(&world), 1);
+}
+
+#[test]
+fn pipeline_fixed_update_frame_rate_independent() {
+    let steps_for = |frames: u32, delta_time: f32| {
+        let world = World::new();
+        world.set_fixed_timestep(8.0);
+        add_fixed_system(&world);
+        for _ in 0..frames {
+            world.progress_time(delta_time);
+        }
+        step_count(&world)
+    };
+
+    assert_eq!(steps_for(16, 0.0625), 8);
+    assert_eq!(steps_for(4, 0.25), 8);
+    assert_eq!(steps_for(1, 0.5), 4);
+}
+
+#[test]
+fn pipeline_fixed_update_not_in_pipeline() {
+    let world = World::new();
+    add_fixed_system(&world);
+
+    world.progress_time(1.0);
+    assert_eq!(step_count(&world), 0);
+}
+
+fn schedule_paths(world: &World) -> Vec<Vec<String>> {
+    world
+        .schedule()
+        .stages
+        .into_iter()
+        .map(|stage| {
+            stage
+                .systems
+                .into_iter()
+                .map(|system| system.path)
+                .collect()
+        })
+        .collect()
+}
+
+fn add_ordered_systems(world: &World) {
+    world.entity().set(Position { x: 0, y: 0 });
+
+    world
+        .system_named::<&Position>("Render")
+        .kind::<flecs::pipeline::OnStore>()
+        .each(|_| {});
+    world.system_named::<&mut Position>("Move").each(|_| {});
+    world
+        .system_named::<&Position>("Input")
+        .kind::<flecs::pipeline::PreUpdate>()
+        .each(|_| {});
+    world.system_named::<&Velocity>("Idle").each(|_| {});
+}
+
+#[test]
+fn pipeline_schedule_order() {
+    let world = World::new();
+    add_ordered_systems(&world);
+
+    let schedule = world.schedule();
+    assert_eq!(schedule_paths(&world), [["::Input", "::Move", "::Render"]]);
+    assert_eq!(schedule.sync_points(), 1);
+    assert_eq!(
+        schedule.phases(),
+        [
+            world.component::<flecs::pipeline::PreUpdate>().id(),
+            world.component::<flecs::pipeline::OnUpdate>().id(),
+            world.component::<flecs::pipeline::OnStore>().id(),
+        ]
+    );
+    let input = schedule.systems().next().unwrap();
+    assert_eq!(input.phase_path, "::flecs::pipeline::PreUpdate");
+    assert!(!input.multi_threaded);
+    assert!(!input.immediate);
+}
+
+fn add_systems_with_sync_points(world: &World) {
+    world
+        .entity()
+        .set(Position { x: 0, y: 0 })
+        .set(Velocity { x: 0, y: 0 });
+
+    world
+        .system_named::<&Position>("AddVelocity")
+        .write::<Velocity>()
+        .each(|_| {});
+    world.system_named::<&Position>("Unrelated").each(|_| {});
+    world.system_named::<&Velocity>("ReadVelocity").each(|_| {});
+    world
+        .system_named::<&Position>("Immediate")
+        .immediate(true)
+        .each(|_| {});
+    world.system_named::<&Position>("Last").each(|_| {});
+}
+
+#[test]
+fn pipeline_schedule_sync_points() {
+    let world = World::new();
+    add_systems_with_sync_points(&world);
+
+    assert_eq!(
+        schedule_paths(&world),
+        [
+            vec!["::AddVelocity", "::Unrelated"],
+            vec!["::ReadVelocity"],
+            vec!["::Immediate"],
+            vec!["::Last"],
+        ]
+    );
+    let schedule = world.schedule();
+    assert!(schedule.stages[2].immediate);
+    assert!(!schedule.stages[3].immediate);
+    assert_eq!(schedule.sync_points(), 3);
+}
+
+/// Returns the number of merges flecs does in a frame.
+fn merges_in_frame(world: &World) -> usize {
+    let merge_count =
+        || unsafe { (*flecs_ecs::sys::ecs_get_world_info(world.ptr_mut())).merge_count_total };
+    let before = merge_count();
+    world.progress();
+    (merge_count() - before) as usize
+}
+
+#[test]
+fn pipeline_schedule_sync_points_match_merges() {
+    let world = World::new();
+    add_ordered_systems(&world);
+    assert_eq!(world.schedule().sync_points(), merges_in_frame(&world));
+
+    let world = World::new();
+    add_systems_with_sync_points(&world);
+    assert_eq!(world.schedule().sync_points(), merges_in_frame(&world));
+}
+
+#[test]
+fn pipeline_schedule_custom_pipeline() {
+    let world = World::new();
+    world.entity().set(Position { x: 0, y: 0 });
+
+    #[derive(Component)]
+    struct Tag;
+
+    let pipeline = world
+        .pipeline()
+        .with::<flecs::system::System>()
+        .with::<Tag>()
+        .build();
+    world
+        .system_named::<&Position>("Tagged")
+        .each(|_| {})
+        .add::<Tag>();
+    world.system_named::<&Position>("Untagged").each(|_| {});
+
+    let schedule = pipeline.schedule();
+    assert!(schedule.contains(world.lookup("Tagged")));
+    assert!(!schedule.contains(world.lookup("Untagged")));
+    assert_eq!(world.schedule().systems().count(), 2);
+
+    world.set_pipeline(pipeline.entity());
+    assert_eq!(schedule.sync_points(), merges_in_frame(&world));
+}
+
+#[test]
+fn pipeline_schedule_to_dot() {
+    let world = World::new();
+    world.entity().set(Position { x: 0, y: 0 });
+
+    let b = world.system_named::<&Position>("B").each(|_| {});
+    world
+        .system_named::<&Position>("A")
+        .before_id(b.id())
+        .each(|_| {});
+    world
+        .system_named::<&Position>("C")
+        .immediate(true)
+        .each(|_| {});
+
+    let dot = world.schedule().to_dot();
+    assert!(dot.starts_with("digraph \""));
+    assert!(dot.contains("label=\"::flecs::pipeline::OnUpdate\";"));
+    assert!(dot.contains("\"::A\" -> \"::B\";"));
+    assert!(dot.contains("\"::B\" -> \"merge 0\";"));
+    assert!(dot.contains("\"merge 0\" -> \"::C\";"));
+    assert!(dot.contains("\"::A\" -> \"::B\" [style=dashed];"));
+    assert!(dot.ends_with("}\n"));
+    assert_eq!(world.schedule().sync_points(), merges_in_frame(&world));
+}
@@ -0,0 +1,197 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+
+#[derive(Prefab)]
+struct Seat {
+    mass: Mass,
+}
+
+#[derive(Prefab)]
+struct Cockpit {
+    #[slot]
+    seat: Seat,
+}
+
+#[derive(Prefab)]
+struct Engine;
+
+#[derive(Prefab)]
+struct Spaceship {
+    position: Position,
+    velocity: Velocity,
+    #[slot]
+    engine: Engine,
+    #[slot]
+    cockpit: Cockpit,
+}
+
+fn spaceship() -> Spaceship {
+    Spaceship {
+        position: Position { x: 1, y: 2 },
+        velocity: Velocity { x: 3, y: 4 },
+        engine: Engine,
+        cockpit: Cockpit {
+            seat: Seat {
+                mass: Mass { value: 10 },
+            },
+        },
+    }
+}
+
+#[test]
+fn prefab_derive_hierarchy() {
+    let world = World::new();
+
+    let prefab = world.prefab_from_named("Spaceship", spaceship());
+    assert!(prefab.has_id(flecs::Prefab::ID));
+    assert_eq!(prefab.map::<&Position, _>(|p| (p.x, p.y)), (1, 2));
+    assert_eq!(prefab.map::<&Velocity, _>(|v| (v.x, v.y)), (3, 4));
+
+    let engine = prefab.lookup("engine");
+    assert!(engine.has_id(flecs::Prefab::ID));
+    assert!(engine.has_id((flecs::SlotOf::ID, prefab.id())));
+
+    let seat = prefab.lookup("cockpit::seat");
+    let cockpit = prefab.lookup("cockpit");
+    assert!(seat.has_id((flecs::SlotOf::ID, cockpit.id())));
+    assert_eq!(seat.map::<&Mass, _>(|m| m.value), 10);
+}
+
+#[test]
+fn prefab_derive_instantiate() {
+    let world = World::new();
+
+    let prefab = world.prefab_from_named("Spaceship", spaceship());
+    let inst = prefab.instantiate_named("my_spaceship");
+
+    assert!(inst.entity.has_id((flecs::IsA::ID, prefab.id())));
+    assert_eq!(inst.entity.map::<&Position, _>(|p| (p.x, p.y)), (1, 2));
+    assert_eq!(inst.engine.path().unwrap(), "::my_spaceship::engine");
+    assert_eq!(inst.cockpit.path().unwrap(), "::my_spaceship::cockpit");
+    assert!(!inst.engine.has_id(flecs::Prefab::ID));
+
+    // nested slots are resolved from the slots of the instance
+    assert_eq!(
+        inst.cockpit.seat.path().unwrap(),
+        "::my_spaceship::cockpit::seat"
+    );
+    assert_eq!(inst.cockpit.seat.map::<&Mass, _>(|m| m.value), 10);
+}
+
+#[test]
+fn prefab_derive_existing_instance() {
+    let world = World::new();
+
+    let prefab = world.prefab_from(spaceship());
+    let e = world.entity().is_a_id(*prefab);
+    let inst = prefab.instance(e).unwrap();
+
+    assert_eq!(inst.entity, e);
+    assert!(inst.engine.has_id((flecs::ChildOf::ID, e.id())));
+    assert!(inst
+        .cockpit
+        .seat
+        .has_id((flecs::ChildOf::ID, inst.cockpit.id())));
+    assert_ne!(prefab.instantiate().engine.entity, inst.engine.entity);
+}
+
+#[test]
+fn prefab_derive_not_an_instance() {
+    let world = World::new();
+
+    let prefab = world.prefab_from(spaceship());
+    assert!(prefab.instance(world.entity()).is_none());
+
+    // the slots of the prefab aren't instantiated for an entity that isn't an instance
+    let other = world.prefab_from(spaceship());
+    let e = world.entity().is_a_id(*other);
+    assert!(prefab.instance(e).is_none());
+    assert!(other.instance(e).is_some());
+}
+
+#[derive(Component)]
+#[flecs(inherit)]
+struct Shared {
+    value: i32,
+}
+
+#[derive(Component)]
+#[flecs(dont_inherit)]
+struct Private {
+    value: i32,
+}
+
+#[test]
+fn prefab_on_instantiate_attribute() {
+    let world = World::new();
+
+    let prefab = world
+        .prefab()
+        .set(Shared { value: 1 })
+        .set(Private { value: 2 })
+        .set(Mass { value: 3 });
+    let inst = world.entity().is_a_id(prefab);
+
+    assert!(inst.has::<Shared>());
+    assert!(!inst.owns::<Shared>());
+    assert!(!inst.has::<Private>());
+    assert!(inst.owns::<Mass>());
+}
+
+#[test]
+fn prefab_on_instantiate_component() {
+    let world = World::new();
+
+    world
+        .component::<Position>()
+        .on_instantiate(Instantiate::Inherit);
+    world
+        .component::<Velocity>()
+        .on_instantiate(Instantiate::DontInherit);
+
+    let prefab = world
+        .prefab()
+        .set(Position { x: 1, y: 2 })
+        .set(Velocity { x: 3, y: 4 });
+    let inst = world.entity().is_a_id(prefab);
+
+    assert!(inst.has::<Position>());
+    assert!(!inst.owns::<Position>());
+    assert!(!inst.has::<Velocity>());
+}
+
+#[test]
+fn prefab_get_owned_inherited() {
+    let world = World::new();
+
+    let prefab = world.prefab().set(Shared { value: 1 });
+    let inst = world.entity().is_a_id(prefab);
+
+    let mut value = 0;
+    assert!(!inst.get_owned::<Shared>(|_| {}));
+    assert!(inst.get_inherited::<Shared>(|shared| value = shared.value));
+    assert_eq!(value, 1);
+
+    inst.set(Shared { value: 2 });
+    assert!(inst.get_owned::<Shared>(|shared| value = shared.value));
+    assert_eq!(value, 2);
+    assert!(!inst.get_inherited::<Shared>(|_| {}));
+    assert!(prefab.get_owned::<Shared>(|shared| assert_eq!(shared.value, 1)));
+
+    assert!(!inst.get_owned::<Mass>(|_| {}));
+    assert!(!inst.get_inherited::<Mass>(|_| {}));
+}
+
+#[test]
+#[should_panic]
+#[ignore = "Panic test: panics in C, which isn't captured by rust"]
+fn prefab_get_owned_locks_table() {
+    let world = World::new();
+
+    let inst = world.entity().set(Shared { value: 1 });
+
+    // moves the entity to another table while its value is borrowed
+    inst.get_owned::<Shared>(|_| {
+        inst.add::<Mass>();
+    });
+}
@@ -0,0 +1,135 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+
+#[test]
+fn profiler_records_systems() {
+    let world = World::new();
+    world.entity().set(Position { x: 0, y: 0 });
+    let before = world.system_named::<&Position>("Before").each(|_| {});
+
+    world.enable_profiler(16);
+    let a = world
+        .system_named::<&mut Position>("A")
+        .each(|pos| pos.x += 1);
+    let b = world
+        .system_named::<&Position>("B")
+        .run_if(|_| false)
+        .each(|_| {});
+    world.progress();
+    world.progress();
+
+    let events = world.profiler().unwrap().events();
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|event| event.entity == a.id()
+        && event.kind == ProfileKind::System
+        && event.thread == 0));
+    assert!(events[0].start + events[0].duration <= events[1].start);
+    assert!(!events.iter().any(|event| event.entity == before.id()));
+    assert!(!events.iter().any(|event| event.entity == b.id()));
+}
+
+#[test]
+fn profiler_records_observers() {
+    let world = World::new();
+    world.enable_profiler(16);
+
+    let observer = world
+        .observer_named::<flecs::OnSet, &Position>("OnSetPosition")
+        .each(|_| {});
+    world.entity().set(Position { x: 0, y: 0 });
+    world.entity().set(Position { x: 1, y: 0 });
+
+    let events = world.profiler().unwrap().events();
+    assert_eq!(events.len(), 2);
+    assert!(events
+        .iter()
+        .all(|event| event.entity == observer.id() && event.kind == ProfileKind::Observer));
+}
+
+#[test]
+fn profiler_ring_buffer() {
+    let world = World::new();
+    world.entity().set(Position { x: 0, y: 0 });
+    world.enable_profiler(3);
+    world.system::<&Position>().each(|_| {});
+
+    for _ in 0..5 {
+        world.progress();
+    }
+    let events = world.profiler().unwrap().events();
+    assert_eq!(events.len(), 3);
+    assert!(events.windows(2).all(|pair| pair[0].start < pair[1].start));
+
+    world.enable_profiler(2);
+    assert_eq!(world.profiler().unwrap().capacity(), 2);
+    assert_eq!(world.profiler().unwrap().events()[..], events[1..]);
+
+    world.profiler().unwrap().clear();
+    assert!(world.profiler().unwrap().events().is_empty());
+}
+
+#[test]
+fn profiler_disable() {
+    let world = World::new();
+    world.entity().set(Position { x: 0, y: 0 });
+    world.enable_profiler(16);
+    world.system::<&Position>().each(|_| {});
+
+    world.progress();
+    world.disable_profiler();
+    assert!(!world.profiler().unwrap().is_enabled());
+    world.progress();
+    assert_eq!(world.profiler().unwrap().events().len(), 1);
+
+    world.enable_profiler(16);
+    world.progress();
+    assert_eq!(world.profiler().unwrap().events().len(), 2);
+}
+
+#[test]
+fn profiler_chrome_trace() {
+    let world = World::new();
+    assert_eq!(
+        world.chrome_trace(),
+        "{\"traceEvents\":[],\"displayTimeUnit\":\"ms\"}"
+    );
+
+    world.entity().set(Position { x: 0, y: 0 });
+    world.enable_profiler(16);
+    world.system_named::<&Position>("Move").each(|_| {});
+    let unnamed = world.system::<&Position>().each(|_| {});
+    world.progress();
+
+    let trace = world.chrome_trace();
+    assert!(trace.starts_with(
+        "{\"traceEvents\":[{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":1,\"tid\":0,\"args\":{\"name\":\"main\"}},"
+    ));
+    assert!(trace.contains("{\"name\":\"::Move\",\"cat\":\"system\",\"ph\":\"X\",\"ts\":"));
+    assert!(trace.contains(&format!("{{\"name\":\"#{}\",", unnamed.id())));
+    assert!(trace.ends_with("],\"displayTimeUnit\":\"ms\"}"));
+}
+
+#[test]
+fn profiler_records_ordered_observers() {
+    let world = World::new();
+    world.enable_profiler(16);
+
+    let low = world
+        .observer::<flecs::OnSet, &Position>()
+        .priority(0)
+        .each(|_| {});
+    let high = world
+        .observer::<flecs::OnSet, &Position>()
+        .priority(1)
+        .each(|_| {});
+    world.entity().set(Position { x: 0, y: 0 });
+
+    let order: Vec<Entity> = world
+        .profiler()
+        .unwrap()
+        .events()
+        .iter()
+        .map(|event| event.entity)
+        .collect();
+    assert_eq!(order, [high.id(), low.id()]);
+}
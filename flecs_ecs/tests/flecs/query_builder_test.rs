@@ -2904,6 +2904,25 @@ fn query_builder_term_w_read() {
     assert_eq!(q.term(1).src_id(), 0);
 }
 
+#[test]
+fn query_builder_term_w_filter() {
+    let world = World::new();
+
+    let q = world
+        .query::<()>()
+        .with::<&Position>()
+        .filter()
+        .with::<&mut Velocity>()
+        .filter()
+        .with::<Mass>()
+        .filter()
+        .build();
+
+    assert_eq!(q.term(0).inout(), InOutKind::Filter);
+    assert_eq!(q.term(1).inout(), InOutKind::Filter);
+    assert_eq!(q.term(2).inout(), InOutKind::Filter);
+}
+
 #[test]
 #[ignore = "Iter with stage not implemented"]
 fn query_builder_iter_w_stage() {
@@ -0,0 +1,151 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+
+#[test]
+fn query_dsl_up_traversal_parenthesized() {
+    let world = World::new();
+
+    let parent = world.entity().set(Position { x: 1, y: 2 });
+    let child = world
+        .entity()
+        .child_of_id(parent)
+        .set(Velocity { x: 1, y: 1 });
+    world.entity().set(Velocity { x: 2, y: 2 });
+
+    let query = query!(world, &Velocity, &Position(up(flecs::ChildOf))).build();
+
+    let mut count = 0;
+    query.each_entity(|e, (vel, pos)| {
+        assert_eq!(e, child);
+        assert_eq!(vel.x, 1);
+        assert_eq!(pos.x, 1);
+        count += 1;
+    });
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn query_dsl_this_variable() {
+    let world = World::new();
+
+    world.entity().set(Position { x: 1, y: 2 });
+
+    let mut query = query!(world, &Position($this)).build();
+    assert_eq!(query.count(), 1);
+    assert_eq!(
+        query.to_string(),
+        query!(world, &Position).build().to_string()
+    );
+}
+
+#[test]
+fn query_dsl_or_operator() {
+    let world = World::new();
+
+    world.entity().add::<Likes>();
+    world.entity().add::<Eats>();
+    world.entity().add::<Apples>();
+
+    let mut query = query!(world, Likes || Eats).build();
+    assert_eq!(query.count(), 2);
+
+    let mut query = query!(world, Likes || Eats, !Apples).build();
+    assert_eq!(query.count(), 2);
+}
+
+#[test]
+fn query_dsl_access_annotations() {
+    let world = World::new();
+
+    world
+        .entity()
+        .set(Position { x: 1, y: 2 })
+        .set(Velocity { x: 1, y: 1 })
+        .set(Mass { value: 3 });
+
+    let query = query!(world, [inout] &mut Position, [in] &Velocity, [filter] &Mass).build();
+    assert_eq!(query.term(0).inout(), InOutKind::InOut);
+    assert_eq!(query.term(1).inout(), InOutKind::In);
+    assert_eq!(query.term(2).inout(), InOutKind::Filter);
+
+    let mut count = 0;
+    query.each(|(pos, vel, mass)| {
+        pos.x += vel.x * mass.value;
+        count += 1;
+    });
+    assert_eq!(count, 1);
+    query.each(|(pos, _, _)| assert_eq!(pos.x, 4));
+
+    let mut query = query!(world, &mut Position, &Position(up)).build();
+    assert_eq!(query.count(), 0);
+}
+
+#[test]
+fn query_dsl_observer_multiple_events() {
+    #[derive(Component, Default)]
+    struct Count(u32);
+
+    let world = create_world_with_flags::<Count>();
+
+    observer!(world, flecs::OnAdd | flecs::OnSet, &Position, !Velocity).each_entity(|e, _| {
+        e.world().get::<&mut Count>(|count| count.0 += 1);
+    });
+
+    world.entity().set(Position { x: 1, y: 2 });
+    world.get::<&Count>(|count| assert_eq!(count.0, 2));
+
+    world
+        .entity()
+        .set(Velocity { x: 1, y: 1 })
+        .set(Position { x: 1, y: 2 });
+    world.get::<&Count>(|count| assert_eq!(count.0, 2));
+}
+
+#[test]
+fn query_dsl_observer_yield_existing_named() {
+    #[derive(Component, Default)]
+    struct Count(u32);
+
+    let world = create_world_with_flags::<Count>();
+
+    world.entity().set(Position { x: 1, y: 2 });
+    world.entity().set(Position { x: 3, y: 4 });
+
+    let observer = observer!(
+        "position_observer",
+        world,
+        flecs::OnSet,
+        yield_existing,
+        &Position
+    )
+    .each_entity(|e, _| {
+        e.world().get::<&mut Count>(|count| count.0 += 1);
+    });
+
+    assert_eq!(observer.name(), "position_observer");
+    world.get::<&Count>(|count| assert_eq!(count.0, 2));
+}
+
+#[test]
+fn query_dsl_cascade_relationship() {
+    let world = World::new();
+
+    world.component::<Rel>().add_id(*flecs::Traversable);
+
+    let e0 = world.entity().set(Position { x: 1, y: 2 });
+    let e1 = world.entity().add_first::<Rel>(e0);
+    let e2 = world.entity().add_first::<Rel>(e1);
+    let e3 = world.entity().add_first::<Rel>(e2);
+
+    let query = query!(world, &Position(cascade(Rel))).set_cached().build();
+
+    e3.add::<Foo>();
+    e2.add::<Bar>();
+
+    let mut entities = Vec::new();
+    query.each_entity(|e, pos| {
+        assert_eq!(pos.x, 1);
+        entities.push(e.id());
+    });
+    assert_eq!(entities, [e1.id(), e2.id(), e3.id()]);
+}
@@ -32,3 +32,374 @@ fn query_cached_destruction_lingering_references_panic() {
     query2.run(|_| {});
     drop(query2);
 }
+
+#[test]
+fn query_from_entity() {
+    #[derive(Component)]
+    struct Tag;
+
+    let world = World::new();
+    world.entity().add::<Tag>();
+
+    let query = world.query::<&Tag>().set_cached().build();
+    let mut from_entity = world.try_query_from(query.entity()).unwrap();
+    assert_eq!(from_entity.count(), 1);
+    assert_eq!(from_entity.entity(), query.entity());
+
+    assert!(world.try_query_from(world.entity()).is_none());
+}
+
+#[test]
+fn query_pair_wildcard_yields_data_and_target() {
+    #[derive(Component)]
+    struct Attached {
+        slot: u32,
+    }
+
+    let world = World::new();
+
+    let ship = world.entity();
+    let station = world.entity();
+
+    let turret = world
+        .entity()
+        .set_first::<Attached>(Attached { slot: 1 }, ship)
+        .set_first::<Attached>(Attached { slot: 2 }, station);
+
+    let mut count = 0;
+    world
+        .new_query::<PairWildcard<&mut Attached>>()
+        .each_entity(|e, (attached, target)| {
+            assert_eq!(e, turret);
+            if target == ship {
+                assert_eq!(attached.slot, 1);
+            } else {
+                assert_eq!(target, station);
+                assert_eq!(attached.slot, 2);
+            }
+            attached.slot += 10;
+            count += 1;
+        });
+    assert_eq!(count, 2);
+
+    world
+        .new_query::<PairWildcard<&Attached>>()
+        .each(|(attached, _)| {
+            assert!(attached.slot > 10);
+        });
+}
+
+#[test]
+fn query_pair_target_tag_relationship() {
+    #[derive(Component)]
+    struct Position {
+        x: i32,
+    }
+
+    let world = World::new();
+
+    let parent_a = world.entity();
+    let parent_b = world.entity();
+
+    world.entity().child_of_id(parent_a).set(Position { x: 1 });
+    world.entity().child_of_id(parent_b).set(Position { x: 2 });
+    world.entity().set(Position { x: 3 });
+
+    let mut count = 0;
+    world
+        .new_query::<(&Position, PairTarget<flecs::ChildOf>)>()
+        .each(|(pos, parent)| {
+            match pos.x {
+                1 => assert_eq!(parent, parent_a),
+                2 => assert_eq!(parent, parent_b),
+                _ => panic!("entity without parent matched"),
+            }
+            count += 1;
+        });
+    assert_eq!(count, 2);
+
+    world
+        .new_query::<(&Position, PairTarget<flecs::ChildOf>)>()
+        .run_iter(|_, (pos, parent)| {
+            assert_eq!(pos.len(), 1);
+            assert!(parent == parent_a || parent == parent_b);
+        });
+}
+
+#[test]
+fn query_has_term() {
+    #[derive(Component)]
+    struct Position {
+        x: i32,
+    }
+
+    #[derive(Component)]
+    struct Velocity {
+        x: i32,
+    }
+
+    #[derive(Component)]
+    struct Frozen;
+
+    let world = World::new();
+
+    world.entity().set(Position { x: 1 }).add::<Frozen>();
+    world.entity().set(Position { x: 2 }).set(Velocity { x: 1 });
+    world.entity().set(Position { x: 3 });
+
+    let mut count = 0;
+    world
+        .new_query::<(&Position, Has<Frozen>, Has<Velocity>)>()
+        .each(|(pos, frozen, has_velocity)| {
+            assert_eq!(frozen, pos.x == 1);
+            assert_eq!(has_velocity, pos.x == 2);
+            count += 1;
+        });
+    assert_eq!(count, 3);
+}
+
+#[test]
+fn query_changed_term() {
+    #[derive(Component)]
+    struct Position {
+        x: i32,
+    }
+
+    #[derive(Component)]
+    struct Velocity {
+        x: i32,
+    }
+
+    let world = World::new();
+
+    let e1 = world.entity().set(Position { x: 1 });
+    world.entity().set(Position { x: 2 }).set(Velocity { x: 1 });
+
+    let query = world
+        .query::<(&Position, Changed<Position>)>()
+        .set_cached()
+        .build();
+
+    let mut count = 0;
+    query.each(|_| count += 1);
+    assert_eq!(count, 2);
+
+    count = 0;
+    query.each(|_| count += 1);
+    assert_eq!(count, 0);
+
+    e1.set(Position { x: 10 });
+
+    count = 0;
+    query.each_entity(|e, (pos, ())| {
+        assert_eq!(e, e1);
+        assert_eq!(pos.x, 10);
+        count += 1;
+    });
+    assert_eq!(count, 1);
+
+    world.new_query::<&mut Position>().each(|pos| pos.x += 1);
+
+    count = 0;
+    query.run_iter(|_, (pos, ())| count += pos.len());
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn query_shared_deduplicates_by_terms() {
+    #[derive(Component)]
+    struct Position {
+        x: i32,
+    }
+
+    #[derive(Component)]
+    struct Velocity {
+        x: i32,
+    }
+
+    let world = World::new();
+
+    world.entity().set(Position { x: 1 }).set(Velocity { x: 1 });
+
+    let q1 = world.query_shared::<(&Position, &Velocity)>();
+    let mut q2 = world.query_shared::<(&Position, &Velocity)>();
+    let q3 = world.query_shared::<(&Velocity, &Position)>();
+    let q4 = world.query_shared::<(&mut Position, &Velocity)>();
+
+    assert_eq!(q1.entity(), q2.entity());
+    assert_ne!(q1.entity(), q3.entity());
+    assert_ne!(q1.entity(), q4.entity());
+    assert_eq!(q1.reference_count(), 2);
+    assert_eq!(q2.count(), 1);
+
+    drop(q2);
+    assert_eq!(q1.reference_count(), 1);
+
+    let entity = q1.entity().id();
+    drop(q1);
+
+    // the shared query outlives its handles
+    let q5 = world.query_shared::<(&Position, &Velocity)>();
+    assert_eq!(q5.entity().id(), entity);
+    assert_eq!(q5.reference_count(), 1);
+}
+
+#[test]
+fn query_shared_recreated_after_destruct() {
+    #[derive(Component)]
+    struct Position {
+        x: i32,
+    }
+
+    let world = World::new();
+
+    let q1 = world.query_shared::<&Position>();
+    q1.destruct();
+
+    let mut q2 = world.query_shared::<&Position>();
+    world.entity().set(Position { x: 1 });
+    assert_eq!(q2.count(), 1);
+    assert_eq!(q2.reference_count(), 1);
+}
+
+#[test]
+fn query_shared_destruct_after_clone_dropped() {
+    #[derive(Component)]
+    struct Position {
+        x: i32,
+    }
+
+    let world = World::new();
+
+    let q1 = world.query_shared::<&Position>();
+    let q2 = q1.clone();
+    let q3 = world.query_shared::<&Position>();
+    assert_eq!(q1.reference_count(), 3);
+
+    drop(q2);
+    drop(q3);
+    assert_eq!(q1.reference_count(), 1);
+    q1.destruct();
+}
+
+#[test]
+fn query_changed_term_per_field() {
+    #[derive(Component)]
+    struct Position {
+        x: i32,
+    }
+
+    #[derive(Component)]
+    struct Velocity {
+        x: i32,
+    }
+
+    let world = World::new();
+
+    let e = world.entity().set(Position { x: 1 }).set(Velocity { x: 1 });
+
+    let query = world.new_query::<(&mut Velocity, Changed<Position>)>();
+
+    let mut count = 0;
+    query.each(|(vel, ())| {
+        vel.x += 1;
+        count += 1;
+    });
+    assert_eq!(count, 1);
+
+    // writes to other components, including those of the query itself, are not reported
+    e.set(Velocity { x: 10 });
+    count = 0;
+    query.each(|_| count += 1);
+    assert_eq!(count, 0);
+
+    e.get::<&mut Position>(|pos| pos.x = 2);
+    e.modified::<Position>();
+    count = 0;
+    query.run(|mut it| {
+        while it.next_iter() {
+            count += it.count();
+        }
+    });
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn query_changed_term_system() {
+    #[derive(Component)]
+    struct Position {
+        x: i32,
+    }
+
+    #[derive(Component, Default)]
+    struct Count(i32);
+
+    let world = World::new();
+    world.set(Count::default());
+
+    let e = world.entity().set(Position { x: 1 });
+    world
+        .system::<(&mut Position, Changed<Position>)>()
+        .each_entity(|e, (pos, ())| {
+            pos.x += 1;
+            e.world().get::<&mut Count>(|count| count.0 += 1);
+        });
+
+    world.progress();
+    world.progress();
+    world.get::<&Count>(|count| assert_eq!(count.0, 1));
+    e.get::<&Position>(|pos| assert_eq!(pos.x, 2));
+
+    e.set(Position { x: 10 });
+    world.progress();
+    world.progress();
+    world.get::<&Count>(|count| assert_eq!(count.0, 2));
+    e.get::<&Position>(|pos| assert_eq!(pos.x, 11));
+}
+
+#[test]
+#[should_panic]
+fn query_changed_term_multi_threaded_system() {
+    #[derive(Component)]
+    struct Position {
+        x: i32,
+    }
+
+    let world = World::new();
+    world
+        .system::<(&Position, Changed<Position>)>()
+        .multi_threaded(true)
+        .each(|_| {});
+}
+
+#[test]
+fn query_has_term_get() {
+    #[derive(Component)]
+    struct Position {
+        x: i32,
+    }
+
+    #[derive(Component)]
+    struct Frozen;
+
+    #[derive(Component)]
+    struct Velocity {
+        x: i32,
+    }
+
+    let world = World::new();
+
+    let e = world.entity().set(Position { x: 1 }).add::<Frozen>();
+
+    e.get::<(&Position, Has<Frozen>, Has<Velocity>)>(|(pos, frozen, has_velocity)| {
+        assert_eq!(pos.x, 1);
+        assert!(frozen);
+        assert!(!has_velocity);
+    });
+    assert!(
+        e.try_get::<(Has<Velocity>, Option<&mut Velocity>)>(|(has_velocity, vel)| {
+            assert!(!has_velocity);
+            assert!(vel.is_none());
+        })
+    );
+}
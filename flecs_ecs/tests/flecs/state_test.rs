@@ -0,0 +1,135 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+
+#[repr(C)]
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+pub enum GameState {
+    Loading,
+    Menu,
+    Playing,
+}
+
+#[derive(Component, Default)]
+struct Log(Vec<String>);
+
+fn log(world: &World) -> Vec<String> {
+    world.map::<&mut Log, _>(|log| std::mem::take(&mut log.0))
+}
+
+fn record(mut it: Iter) {
+    while it.next_iter() {}
+    let name = it.system().name();
+    it.world()
+        .get::<&mut Log>(|log| log.0.push(name.to_string()));
+}
+
+fn add_state_systems(world: &World) {
+    for state in [GameState::Loading, GameState::Menu, GameState::Playing] {
+        let name = format!("{state:?}");
+        world
+            .system_named::<()>(&format!("Enter{name}"))
+            .on_enter(state)
+            .run(record);
+        world
+            .system_named::<()>(&format!("Exit{name}"))
+            .on_exit(state)
+            .run(record);
+        world
+            .system_named::<()>(&format!("Update{name}"))
+            .in_state(state)
+            .run(record);
+    }
+}
+
+#[test]
+fn state_enter_exit() {
+    let world = World::new();
+    world.set(Log::default());
+    world.init_state(GameState::Loading);
+    add_state_systems(&world);
+
+    world.progress();
+    assert_eq!(log(&world), ["EnterLoading", "UpdateLoading"]);
+    world.progress();
+    assert_eq!(log(&world), ["UpdateLoading"]);
+
+    world.set_next_state(GameState::Menu);
+    assert_eq!(world.state::<GameState>(), GameState::Loading);
+    world.progress();
+    assert_eq!(world.state::<GameState>(), GameState::Menu);
+    assert!(world.has_enum(GameState::Menu));
+    assert_eq!(log(&world), ["ExitLoading", "EnterMenu", "UpdateMenu"]);
+}
+
+#[test]
+fn state_transition_queue() {
+    let world = World::new();
+    world.set(Log::default());
+    world.init_state(GameState::Loading);
+    add_state_systems(&world);
+    world.progress();
+    log(&world);
+
+    world.set_next_state(GameState::Menu);
+    world.set_next_state(GameState::Menu);
+    world.set_next_state(GameState::Playing);
+    world.progress();
+
+    assert_eq!(
+        log(&world),
+        [
+            "ExitLoading",
+            "EnterMenu",
+            "ExitMenu",
+            "EnterPlaying",
+            "UpdatePlaying"
+        ]
+    );
+}
+
+#[test]
+fn state_on_transition() {
+    let world = World::new();
+    world.set(Log::default());
+    world.init_state(GameState::Loading);
+    add_state_systems(&world);
+
+    world.on_transition::<GameState>(|world, from, to| {
+        world.get::<&mut Log>(|log| log.0.push(format!("{from:?} -> {to:?}")));
+    });
+
+    world.progress();
+    log(&world);
+    world.set_next_state(GameState::Playing);
+    world.progress();
+
+    assert_eq!(
+        log(&world),
+        [
+            "ExitLoading",
+            "Loading -> Playing",
+            "EnterPlaying",
+            "UpdatePlaying"
+        ]
+    );
+}
+
+#[test]
+fn state_phase_names() {
+    let world = World::new();
+    world.init_state(GameState::Loading);
+
+    assert_eq!(
+        world.on_enter(GameState::Menu).path().unwrap(),
+        world.on_enter(GameState::Menu).path().unwrap()
+    );
+    assert!(world
+        .on_exit(GameState::Playing)
+        .path()
+        .unwrap()
+        .ends_with("GameState::OnExit::Playing"));
+    assert_ne!(
+        world.on_enter(GameState::Menu),
+        world.on_exit(GameState::Menu)
+    );
+}
@@ -0,0 +1,292 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+
+#[derive(Component, Default)]
+struct RunOrder(Vec<String>);
+
+#[derive(Component)]
+struct Physics;
+
+#[derive(Component)]
+struct Paused;
+
+#[derive(Component)]
+struct Score(i32);
+
+#[repr(C)]
+#[derive(Component)]
+pub enum GameState {
+    Menu,
+    Playing,
+}
+
+fn record(mut it: Iter) {
+    while it.next_iter() {}
+    let name = it.system().name();
+    it.world()
+        .get::<&mut RunOrder>(|order| order.0.push(name.to_string()));
+}
+
+fn run_order(world: &World) -> Vec<String> {
+    world.set(RunOrder::default());
+    world.progress();
+    world.map::<&RunOrder, _>(|order| order.0.clone())
+}
+
+#[test]
+fn system_before() {
+    let world = World::new();
+
+    let a = world.system_named::<()>("A").run(record);
+    world.system_named::<()>("B").before_id(a.id()).run(record);
+
+    assert_eq!(run_order(&world), ["B", "A"]);
+}
+
+#[test]
+fn system_after() {
+    let world = World::new();
+
+    let a = world.system_named::<()>("A").run(record);
+    let b = world.system_named::<()>("B").run(record);
+    world.system_named::<()>("C").run(record);
+    world
+        .system_named::<()>("D")
+        .after_id(a.id())
+        .before_id(b.id())
+        .run(record);
+
+    assert_eq!(run_order(&world), ["A", "D", "C", "B"]);
+}
+
+#[test]
+fn system_unordered_keep_place() {
+    let world = World::new();
+
+    world.system_named::<()>("U1").run(record);
+    let a = world.system_named::<()>("A").run(record);
+    world.system_named::<()>("U2").run(record);
+    world.system_named::<()>("B").before_id(a.id()).run(record);
+
+    assert_eq!(run_order(&world), ["U1", "B", "U2", "A"]);
+}
+
+#[test]
+fn system_set() {
+    let world = World::new();
+
+    world
+        .system_named::<()>("Render")
+        .after::<Physics>()
+        .run(record);
+    world
+        .system_named::<()>("Collide")
+        .in_set::<Physics>()
+        .run(record);
+    world
+        .system_named::<()>("Move")
+        .in_set::<Physics>()
+        .run(record);
+    world
+        .system_named::<()>("Input")
+        .before::<Physics>()
+        .run(record);
+
+    assert_eq!(run_order(&world), ["Input", "Collide", "Move", "Render"]);
+}
+
+#[test]
+fn system_order_within_phase() {
+    let world = World::new();
+
+    let a = world
+        .system_named::<()>("A")
+        .kind::<flecs::pipeline::PostUpdate>()
+        .run(record);
+    world.system_named::<()>("B").after_id(a.id()).run(record);
+    world
+        .system_named::<()>("C")
+        .kind::<flecs::pipeline::PreUpdate>()
+        .run(record);
+
+    assert_eq!(run_order(&world), ["C", "B", "A"]);
+}
+
+#[test]
+fn system_order_cycle() {
+    let world = World::new();
+
+    world
+        .system_named::<()>("A")
+        .in_set::<Physics>()
+        .run(record);
+    let b = world.system_named::<()>("B").after::<Physics>().run(record);
+    let err = world
+        .system_named::<()>("C")
+        .in_set::<Physics>()
+        .after_id(b.id())
+        .try_build()
+        .err()
+        .unwrap();
+
+    assert_eq!(err.cycle, ["::B", "::C"]);
+    assert_eq!(
+        err.to_string(),
+        "cycle detected in system order: ::B -> ::C -> ::B"
+    );
+    assert!(world.try_lookup("C").is_none());
+    assert_eq!(run_order(&world), ["A", "B"]);
+}
+
+#[test]
+#[should_panic(expected = "cycle detected in system order")]
+fn system_order_cycle_panics() {
+    let world = World::new();
+
+    let a = world.system_named::<()>("A").run(record);
+    let b = world.system_named::<()>("B").after_id(a.id()).run(record);
+    world
+        .system_named::<()>("C")
+        .after_id(b.id())
+        .before_id(a.id())
+        .run(record);
+}
+
+#[test]
+fn system_order_skips_disabled() {
+    let world = World::new();
+
+    let a = world.system_named::<()>("A").run(record);
+    world.system_named::<()>("B").before_id(a.id()).run(record);
+    world.system_named::<()>("C").run(record).disable_self();
+
+    assert_eq!(run_order(&world), ["B", "A"]);
+}
+
+#[test]
+fn system_order_custom_pipeline() {
+    let world = World::new();
+
+    let pipeline = world
+        .pipeline()
+        .with_id(flecs::system::System::ID)
+        .with::<&Physics>()
+        .build();
+    world.set_pipeline(pipeline.entity());
+
+    let a = world.system_named::<()>("A").kind::<Physics>().run(record);
+    world
+        .system_named::<()>("B")
+        .kind::<Physics>()
+        .before_id(a.id())
+        .run(record);
+
+    // the custom pipeline is kept, and runs the systems in its own order
+    assert_eq!(world.get_pipeline(), pipeline.entity());
+    assert_eq!(run_order(&world), ["A", "B"]);
+}
+
+#[test]
+fn system_run_if() {
+    let world = World::new();
+
+    world
+        .system_named::<()>("A")
+        .run_if(|world| !world.has::<Paused>())
+        .run(record);
+    world.system_named::<()>("B").run(record);
+
+    assert_eq!(run_order(&world), ["A", "B"]);
+    world.add::<Paused>();
+    assert_eq!(run_order(&world), ["B"]);
+    world.remove::<Paused>();
+    assert_eq!(run_order(&world), ["A", "B"]);
+}
+
+#[test]
+fn system_run_if_each() {
+    let world = World::new();
+
+    let e = world.entity().set(Position { x: 0, y: 0 });
+    world
+        .system::<&mut Position>()
+        .run_if(|world| !world.has::<Paused>())
+        .each(|pos| pos.x += 1);
+
+    world.progress();
+    world.add::<Paused>();
+    world.progress();
+    world.remove::<Paused>();
+    world.progress();
+
+    e.get::<&Position>(|pos| assert_eq!(pos.x, 2));
+}
+
+#[test]
+#[should_panic(expected = "systems with run conditions can't be multi threaded")]
+fn system_run_if_multi_threaded() {
+    let world = World::new();
+
+    world
+        .system::<&Position>()
+        .run_if_resource_changed::<Score>()
+        .multi_threaded(true)
+        .each(|_| {});
+}
+
+#[test]
+fn system_run_if_all_conditions() {
+    let world = World::new();
+
+    world
+        .system_named::<()>("A")
+        .run_if(|world| !world.has::<Paused>())
+        .run_if_singleton::<Score>(|score| score.0 > 10)
+        .run(record);
+
+    assert!(run_order(&world).is_empty());
+    world.set(Score(5));
+    assert!(run_order(&world).is_empty());
+    world.set(Score(20));
+    assert_eq!(run_order(&world), ["A"]);
+    world.add::<Paused>();
+    assert!(run_order(&world).is_empty());
+}
+
+#[test]
+fn system_run_if_resource_changed() {
+    let world = World::new();
+
+    world
+        .system_named::<()>("A")
+        .run_if_resource_changed::<Score>()
+        .run(record);
+
+    assert!(run_order(&world).is_empty());
+    world.set(Score(1));
+    assert_eq!(run_order(&world), ["A"]);
+    assert!(run_order(&world).is_empty());
+    world.set(Score(2));
+    assert_eq!(run_order(&world), ["A"]);
+    assert!(run_order(&world).is_empty());
+}
+
+#[test]
+fn system_in_state() {
+    let world = World::new();
+
+    world
+        .system_named::<()>("Menu")
+        .in_state(GameState::Menu)
+        .run(record);
+    world
+        .system_named::<()>("Game")
+        .in_state(GameState::Playing)
+        .run(record);
+
+    assert!(run_order(&world).is_empty());
+    world.add_enum(GameState::Menu);
+    assert_eq!(run_order(&world), ["Menu"]);
+    world.add_enum(GameState::Playing);
+    assert_eq!(run_order(&world), ["Game"]);
+}
@@ -0,0 +1,123 @@
+#![allow(dead_code)]
+use crate::common_test::*;
+use flecs_ecs::assert_component_eq;
+use flecs_ecs::testing::*;
+
+#[derive(Component, Debug, PartialEq)]
+struct Health(u32);
+
+fn move_world() -> TestWorld {
+    let world = TestWorld::new().with_delta_time(0.5);
+    world
+        .system::<(&mut Position, &Velocity)>()
+        .each_iter(|it, _, (pos, vel)| {
+            pos.x += vel.x * (it.delta_time() * 2.0) as i32;
+            pos.y += vel.y;
+        });
+    world
+}
+
+#[test]
+fn testing_step_fixed_delta_time() {
+    let world = move_world();
+    let e = world
+        .entity()
+        .set(Position { x: 0, y: 0 })
+        .set(Velocity { x: 1, y: 2 });
+
+    world.step(3).step(1);
+    assert_eq!(world.frame(), 4);
+    assert!((world.delta_time() - 0.5).abs() < FTime::EPSILON);
+    e.get::<&Position>(|pos| assert_eq!((pos.x, pos.y), (4, 8)));
+    assert!((World::delta_time(&world) - 0.5).abs() < FTime::EPSILON);
+}
+
+#[test]
+fn testing_step_quit() {
+    let world = TestWorld::new();
+    world.system::<()>().run(|it| it.world().quit());
+
+    world.step(5);
+    assert_eq!(world.frame(), 1);
+}
+
+#[test]
+fn testing_snapshot() {
+    let world = move_world();
+    world
+        .entity_named("player")
+        .set(Position { x: 1, y: 2 })
+        .set(Velocity { x: 1, y: 0 });
+    let e = world.entity().set(Position { x: 0, y: 0 });
+
+    let snapshot = Snapshot::new()
+        .capture::<Position>(&world)
+        .capture::<Velocity>(&world);
+    assert_eq!(snapshot.len(), 2);
+    assert_eq!(snapshot.get(e, "Position"), Some("Position { x: 0, y: 0 }"));
+    assert_eq!(snapshot.get(e, "Velocity"), None);
+    assert!(snapshot.to_string().ends_with(&format!(
+        "\"#{}\":\n  Position: \"Position {{ x: 0, y: 0 }}\"\n",
+        e.id()
+    )));
+
+    e.destruct();
+    insta::assert_snapshot!(world.snapshot::<Position>().capture::<Velocity>(&world), @r###"
+    "::player":
+      Position: "Position { x: 1, y: 2 }"
+      Velocity: "Velocity { x: 1, y: 0 }"
+    "###);
+}
+
+#[test]
+fn testing_snapshot_diff() {
+    let world = move_world();
+    let player = world
+        .entity_named("player")
+        .set(Position { x: 1, y: 2 })
+        .set(Velocity { x: 1, y: 0 });
+    let idle = world.entity_named("idle").set(Position { x: 0, y: 0 });
+
+    let before = world.snapshot::<Position>();
+    assert!(before.diff(&world.snapshot::<Position>()).is_empty());
+
+    world.step(2);
+    player.set(Health(10));
+    idle.remove::<Position>();
+    world.entity_named("spawned").set(Position { x: 5, y: 5 });
+
+    let after = Snapshot::new()
+        .capture::<Position>(&world)
+        .capture::<Health>(&world);
+    let diff = before.diff(&after);
+    assert_eq!(diff.changes.len(), 4);
+    assert_eq!(
+        diff.to_string(),
+        "+ \"::player\" Health: \"Health(10)\"\n\
+         ~ \"::player\" Position: \"Position { x: 1, y: 2 }\" -> \"Position { x: 3, y: 2 }\"\n\
+         - \"::idle\" Position: \"Position { x: 0, y: 0 }\"\n\
+         + \"::spawned\" Position: \"Position { x: 5, y: 5 }\"\n"
+    );
+}
+
+#[test]
+fn testing_assert_component_eq() {
+    let world = TestWorld::new();
+    let e = world.entity_named("e").set(Health(100));
+    assert_component_eq!(e, Health(100));
+    assert_component_eq!(e, Health(100), "frame {}", world.frame());
+
+    let err = std::panic::catch_unwind(|| assert_component_eq!(e, Health(50))).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<String>().unwrap(),
+        "assertion failed: component Health of entity ::e is Health(100), expected Health(50)"
+    );
+
+    let other = world.entity_named("other");
+    let err =
+        std::panic::catch_unwind(|| assert_component_eq!(other, Health(50), "custom")).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<String>().unwrap(),
+        "assertion failed: entity ::other doesn't have component Health, expected Health(50): custom"
+    );
+}
@@ -4,10 +4,13 @@ use proc_macro::TokenStream as ProcMacroTokenStream;
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned};
 use syn::{
-    bracketed, parenthesized,
+    bracketed,
+    ext::IdentExt,
+    parenthesized,
     parse::{Parse, ParseStream},
     parse_macro_input,
-    token::{Bracket, Comma},
+    punctuated::Punctuated,
+    token::{Bracket, Comma, Paren},
     Data, DeriveInput, Expr, Fields, Ident, LitInt, LitStr, Result, Token, Type,
 };
 
@@ -22,6 +25,19 @@ use syn::{
 /// The `register` attribute can be used to handle `ComponentId` implementation trait over a specific T in a generic component with the world.
 /// This attribute is only supported when the type is generic over a single T.
 ///
+/// The `flecs` attribute adds component traits when the component is registered:
+///
+/// - `#[flecs(override)]`, `#[flecs(inherit)]` or `#[flecs(dont_inherit)]` set what happens to the component when an
+///   entity that owns it is instantiated, see `Component::on_instantiate`.
+/// - `exclusive`, `symmetric`, `transitive`, `reflexive`, `acyclic`, `traversable`, `final`, `pair_is_tag` and
+///   `can_toggle` add the relationship trait with the same name, for example `#[flecs(exclusive, acyclic)]`.
+/// - `with(T)` adds `(With, T)`, `one_of` adds `OneOf` and `one_of(T)` adds `(OneOf, T)`. `Self` can be used as target.
+/// - `on_delete(policy)` and `on_delete_target(policy)` set the cleanup policy, which is `remove`, `delete` or
+///   `panic`, see `Component::on_delete` and `Component::on_delete_target`.
+/// - `module = "game.physics"` sets the path of a module, `import(T)` imports the module `T` before the module.
+///
+/// Since the traits are added when the component is registered, they apply in every world the component is used in.
+///
 /// ## Requirements:
 ///
 /// - Types deriving `ComponentId` should also implement `Clone` and `Default` when the Type needs a `Drop`.
@@ -47,6 +63,12 @@ use syn::{
 ///     value: T,
 /// }
 ///
+/// #[derive(Component)]
+/// #[flecs(inherit)]
+/// struct Mesh {
+///     handle: u32,
+/// }
+///
 /// #[derive(Componfent)]
 /// #[repr(C)]
 /// enum State {
@@ -56,13 +78,226 @@ use syn::{
 ///     Jumping,
 /// }
 /// ```
-#[proc_macro_derive(Component)]
+#[proc_macro_derive(Component, attributes(flecs))]
 pub fn component_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
-    let mut input = parse_macro_input!(input as DeriveInput);
+    let input = parse_macro_input!(input as DeriveInput);
+    impl_component(input).into()
+}
+
+/// `Event` macro for defining types that are emitted as events and observed with a typed payload.
+///
+/// The `Event` trait is implemented for the type, which allows using the type as payload in
+/// `ObserverBuilder::each_event`. Events are not components, each event type is identified by an entity that is
+/// created the first time the event is used in a world.
+///
+/// ## Example:
+///
+#[cfg_attr(doctest, doc = " ````no_test")]
+/// ```ignore
+/// #[derive(Event)]
+/// struct Damage {
+///     amount: u32,
+/// }
+///
+/// world
+///     .observer::<Damage, &mut Health>()
+///     .each_event(|entity, damage, health| {
+///         health.value -= damage.amount;
+///     });
+///
+/// entity.emit(&Damage { amount: 10 });
+/// ```
+#[proc_macro_derive(Event)]
+pub fn event_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    // event types are looked up by their `TypeId`
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!('static));
+    }
+    generics.make_where_clause();
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    let output = quote! {
+        impl #impl_generics flecs_ecs::core::EventId for #name #type_generics #where_clause {
+            fn event_id<'a>(world: impl flecs_ecs::core::IntoWorld<'a>) -> flecs_ecs::core::EntityT {
+                flecs_ecs::core::event::event_entity::<Self>(world)
+            }
+        }
+
+        impl #impl_generics flecs_ecs::core::Event for #name #type_generics #where_clause {}
+    };
+    output.into()
+}
+
+/// `Prefab` macro for describing a prefab hierarchy with a type.
+///
+/// Fields are components that are set on the prefab. Fields marked with `#[slot]` are child prefabs, named after
+/// the field, that are registered as slot of the prefab. Their types must derive `Prefab` as well.
+///
+/// An instance type named after the type with `Instance` appended is generated, with an `entity` field for the
+/// instance and a field with the instance of each slot, resolved from the slots of the instance. Instance types
+/// dereference to their entity.
+///
+/// ## Example:
+///
+#[cfg_attr(doctest, doc = " ````no_test")]
+/// ```ignore
+/// #[derive(Prefab)]
+/// struct Engine {
+///     power: Power,
+/// }
+///
+/// #[derive(Prefab)]
+/// struct Spaceship {
+///     position: Position,
+///     #[slot]
+///     engine: Engine,
+/// }
+///
+/// let spaceship = world.prefab_from_named(
+///     "Spaceship",
+///     Spaceship {
+///         position: Position { x: 0.0, y: 0.0 },
+///         engine: Engine { power: Power(10.0) },
+///     },
+/// );
+///
+/// let inst: SpaceshipInstance = spaceship.instantiate();
+/// inst.engine.get::<&Power>(|power| println!("{}", power.0));
+/// ```
+#[proc_macro_derive(Prefab, attributes(slot))]
+pub fn prefab_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    impl_prefab(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn impl_prefab(input: DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let instance = format_ident!("{}Instance", name);
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "generic types can't derive `Prefab`",
+        ));
+    }
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "only structs can derive `Prefab`",
+        ));
+    };
+
+    let fields = match &data.fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        Fields::Unit => Vec::new(),
+        Fields::Unnamed(fields) => {
+            return Err(syn::Error::new_spanned(
+                fields,
+                "tuple structs can't derive `Prefab`, the fields must be named",
+            ))
+        }
+    };
 
+    let mut components = Vec::new();
+    let mut slots = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        if field.attrs.iter().any(|attr| attr.path().is_ident("slot")) {
+            if ident == "entity" {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "`entity` is reserved for the instance entity and can't be used as slot name",
+                ));
+            }
+            slots.push((ident, &field.ty));
+        } else {
+            components.push(ident);
+        }
+    }
+
+    let (slots, slot_types): (Vec<_>, Vec<_>) = slots.into_iter().unzip();
+    let slot_names = slots.iter().map(|ident| ident.to_string());
+    let slot_names_lookup = slot_names.clone();
+    let instance_doc =
+        format!("Instance of the [`{name}`] prefab, with the instances of its slots.");
+
+    let world = (!slots.is_empty()).then(|| {
+        quote! {
+            let world = flecs_ecs::core::IntoWorld::world(&prefab);
+        }
+    });
+
+    Ok(quote! {
+        #[doc = #instance_doc]
+        #[derive(Clone, Copy)]
+        #vis struct #instance<'a> {
+            /// The instance entity.
+            pub entity: flecs_ecs::core::EntityView<'a>,
+            #(
+                pub #slots: <#slot_types as flecs_ecs::core::Prefab>::Instance<'a>,
+            )*
+        }
+
+        impl<'a> std::ops::Deref for #instance<'a> {
+            type Target = flecs_ecs::core::EntityView<'a>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.entity
+            }
+        }
+
+        impl flecs_ecs::core::Prefab for #name {
+            type Instance<'a> = #instance<'a>;
+
+            fn build(self, prefab: flecs_ecs::core::EntityView<'_>) {
+                #(
+                    prefab.set(self.#components);
+                )*
+                #world
+                #(
+                    let slot = world
+                        .prefab()
+                        .child_of_id(prefab)
+                        .slot_of_id(prefab)
+                        .set_name(#slot_names);
+                    flecs_ecs::core::Prefab::build(self.#slots, slot);
+                )*
+            }
+
+            fn instance<'a>(
+                prefab: flecs_ecs::core::EntityView<'a>,
+                instance: flecs_ecs::core::EntityView<'a>,
+            ) -> Option<Self::Instance<'a>> {
+                Some(#instance {
+                    entity: instance,
+                    #(
+                        #slots: flecs_ecs::core::prefab::slot_instance::<#slot_types>(
+                            prefab,
+                            instance,
+                            #slot_names_lookup,
+                        )?,
+                    )*
+                })
+            }
+        }
+    })
+}
+
+fn impl_component(mut input: DeriveInput) -> TokenStream {
     let has_repr_c = check_repr_c(&input);
     let is_tag;
     let mut generated_impls = vec![];
+    let register_traits = match impl_register_traits(&input) {
+        Ok(register_traits) => register_traits,
+        Err(err) => return err.into_compile_error(),
+    };
 
     match input.data.clone() {
         Data::Struct(data_struct) => {
@@ -73,18 +308,29 @@ pub fn component_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
             };
             is_tag = generate_tag_trait(has_fields);
             generated_impls.push(impl_cached_component_data_struct(
-                &mut input, has_fields, &is_tag,
+                &mut input,
+                has_fields,
+                &is_tag,
+                &register_traits,
             ));
         }
         Data::Enum(_) => {
             is_tag = generate_tag_trait(!has_repr_c);
             if !has_repr_c {
-                generated_impls.push(impl_cached_component_data_struct(&mut input, true, &is_tag));
+                generated_impls.push(impl_cached_component_data_struct(
+                    &mut input,
+                    true,
+                    &is_tag,
+                    &register_traits,
+                ));
             } else {
-                generated_impls.push(impl_cached_component_data_enum(&mut input));
+                generated_impls.push(impl_cached_component_data_enum(
+                    &mut input,
+                    &register_traits,
+                ));
             }
         }
-        _ => return quote! { compile_error!("The type is neither a struct nor an enum!"); }.into(),
+        _ => return quote! { compile_error!("The type is neither a struct nor an enum!"); },
     };
 
     let name = &input.ident;
@@ -102,7 +348,230 @@ pub fn component_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
         #( #generated_impls )*
     };
 
-    output.into()
+    output
+}
+
+/// A trait set with the `#[flecs(...)]` attribute, with an optional target such as `with(Position)`
+/// or value such as `module = "game.physics"`.
+struct FlecsAttribute {
+    ident: Ident,
+    target: Option<Type>,
+    value: Option<LitStr>,
+}
+
+impl Parse for FlecsAttribute {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident = Ident::parse_any(input)?;
+        let target = if input.peek(Paren) {
+            let content;
+            parenthesized!(content in input);
+            Some(content.parse()?)
+        } else {
+            None
+        };
+        let value = if target.is_none() && input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self {
+            ident,
+            target,
+            value,
+        })
+    }
+}
+
+/// Generates the registration of the traits set with `#[flecs(...)]` attributes, such as `#[flecs(exclusive)]`,
+/// and the path and dependencies of modules set with `#[flecs(module = "...", import(...))]`.
+fn impl_register_traits(input: &DeriveInput) -> Result<TokenStream> {
+    let mut traits = Vec::new();
+    let mut on_instantiate = None;
+    let mut module_path = None;
+    let mut module_deps = Vec::new();
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("flecs"))
+    {
+        let attributes =
+            attr.parse_args_with(Punctuated::<FlecsAttribute, Token![,]>::parse_terminated)?;
+
+        for FlecsAttribute {
+            ident,
+            target,
+            value,
+        } in attributes
+        {
+            let name = ident.to_string();
+            if name == "module" {
+                match value {
+                    Some(path) if !path.value().is_empty() => module_path = Some(path),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &ident,
+                            "`module` requires a path, such as `module = \"game.physics\"`",
+                        ))
+                    }
+                }
+                continue;
+            }
+            if name == "import" {
+                match target {
+                    Some(module) => module_deps.push(module),
+                    None => {
+                        return Err(syn::Error::new_spanned(
+                            &ident,
+                            "`import` requires a module, such as `import(TransformModule)`",
+                        ))
+                    }
+                }
+                continue;
+            }
+            if value.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &ident,
+                    format!("`{ident}` doesn't take a value"),
+                ));
+            }
+            let takes_target = matches!(
+                name.as_str(),
+                "with" | "one_of" | "on_delete" | "on_delete_target"
+            );
+            if target.is_some() && !takes_target {
+                return Err(syn::Error::new_spanned(
+                    &ident,
+                    format!("`{ident}` doesn't take a target"),
+                ));
+            }
+
+            let flecs_trait = match name.as_str() {
+                "override" | "inherit" | "dont_inherit" => {
+                    if on_instantiate.replace(ident.clone()).is_some() {
+                        return Err(syn::Error::new_spanned(
+                            &ident,
+                            "only one of `override`, `inherit` and `dont_inherit` can be set",
+                        ));
+                    }
+                    let policy = match name.as_str() {
+                        "override" => quote! { Override },
+                        "inherit" => quote! { Inherit },
+                        _ => quote! { DontInherit },
+                    };
+                    traits.push(quote! {
+                        (
+                            <flecs_ecs::core::flecs::OnInstantiate as flecs_ecs::core::FlecsConstantId>::ID,
+                            flecs_ecs::core::Instantiate::#policy.id(),
+                        )
+                    });
+                    continue;
+                }
+                "on_delete" | "on_delete_target" => {
+                    let policy = match &target {
+                        Some(Type::Path(path)) if path.path.is_ident("remove") => quote! { Remove },
+                        Some(Type::Path(path)) if path.path.is_ident("delete") => quote! { Delete },
+                        Some(Type::Path(path)) if path.path.is_ident("panic") => quote! { Panic },
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                &ident,
+                                format!(
+                                    "`{ident}` requires a policy: `remove`, `delete` or `panic`"
+                                ),
+                            ))
+                        }
+                    };
+                    let cleanup_trait = if name == "on_delete" {
+                        quote! { OnDelete }
+                    } else {
+                        quote! { OnDeleteTarget }
+                    };
+                    traits.push(quote! {
+                        (
+                            <flecs_ecs::core::flecs::#cleanup_trait as flecs_ecs::core::FlecsConstantId>::ID,
+                            flecs_ecs::core::CleanupPolicy::#policy.id(),
+                        )
+                    });
+                    continue;
+                }
+                "exclusive" => quote! { Exclusive },
+                "symmetric" => quote! { Symmetric },
+                "transitive" => quote! { Transitive },
+                "reflexive" => quote! { Reflexive },
+                "acyclic" => quote! { Acyclic },
+                "traversable" => quote! { Traversable },
+                "final" => quote! { Final },
+                "pair_is_tag" => quote! { PairIsTag },
+                "can_toggle" => quote! { CanToggle },
+                "with" => quote! { With },
+                "one_of" => quote! { OneOf },
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &ident,
+                        format!("unknown flecs attribute `{ident}`"),
+                    ))
+                }
+            };
+            let flecs_trait = quote! {
+                <flecs_ecs::core::flecs::#flecs_trait as flecs_ecs::core::FlecsConstantId>::ID
+            };
+
+            match target {
+                // the component is being registered, so its id can't be looked up yet
+                Some(Type::Path(path)) if path.path.is_ident("Self") => {
+                    traits.push(quote! { (#flecs_trait, id) });
+                }
+                Some(target) => traits.push(quote! {
+                    (
+                        #flecs_trait,
+                        <#target as flecs_ecs::core::ComponentId>::id(world),
+                    )
+                }),
+                None if name == "with" => {
+                    return Err(syn::Error::new_spanned(
+                        &ident,
+                        "`with` requires a target, such as `with(Position)`",
+                    ))
+                }
+                None => traits.push(flecs_trait),
+            }
+        }
+    }
+
+    let module_path = module_path.map(|path| {
+        quote! {
+            fn __module_path() -> Option<&'static str> {
+                Some(#path)
+            }
+        }
+    });
+
+    let module_deps = (!module_deps.is_empty()).then(|| {
+        quote! {
+            fn __import_module_deps(world: &flecs_ecs::core::World) {
+                #(
+                    world.import::<#module_deps>();
+                )*
+            }
+        }
+    });
+
+    if traits.is_empty() {
+        return Ok(quote! { #module_path #module_deps });
+    }
+
+    Ok(quote! {
+        fn __register_traits(world: flecs_ecs::core::WorldRef, id: flecs_ecs::core::EntityT) {
+            let entity = world.entity_from_id(id);
+            #(
+                entity.add_id(#traits);
+            )*
+        }
+
+        #module_path
+        #module_deps
+    })
 }
 
 fn generate_tag_trait(has_fields: bool) -> proc_macro2::TokenStream {
@@ -125,6 +594,7 @@ fn impl_cached_component_data_struct(
     ast: &mut syn::DeriveInput, // Name of the structure
     has_fields: bool,
     is_tag: &TokenStream,
+    register_traits: &TokenStream,
 ) -> proc_macro2::TokenStream {
     let is_generic = !ast.generics.params.is_empty();
 
@@ -212,6 +682,8 @@ fn impl_cached_component_data_struct(
             flecs_ecs::core::lifecycle_traits::register_lifecycle_actions::<#name #type_generics>(type_hooks);
         }
 
+        #register_traits
+
         #hook_impl
     };
 
@@ -358,7 +830,10 @@ fn generate_variant_match_arm(
     }
 }
 
-fn impl_cached_component_data_enum(ast: &mut syn::DeriveInput) -> proc_macro2::TokenStream {
+fn impl_cached_component_data_enum(
+    ast: &mut syn::DeriveInput,
+    register_traits: &TokenStream,
+) -> proc_macro2::TokenStream {
     let is_generic = !ast.generics.params.is_empty();
 
     ast.generics.make_where_clause();
@@ -442,6 +917,8 @@ fn impl_cached_component_data_enum(ast: &mut syn::DeriveInput) -> proc_macro2::T
                 flecs_ecs::core::lifecycle_traits::register_lifecycle_actions::<#name>(type_hooks);
             }
 
+            #register_traits
+
             fn __register_default_hooks(type_hooks: &mut flecs_ecs::core::TypeHooksT) {
                 use flecs_ecs::core::component_registration::registration_traits::ComponentInfo;
                 const IMPLS_DEFAULT: bool =  #name::IMPLS_DEFAULT;
@@ -687,6 +1164,7 @@ enum TermIdent {
     Literal(LitStr),
     SelfType,
     SelfVar,
+    This,
     Singleton,
     Wildcard,
     Any,
@@ -703,7 +1181,10 @@ impl Parse for TermIdent {
         } else if input.peek(Token![$]) {
             // Variable
             input.parse::<Token![$]>()?;
-            if input.peek(Ident) {
+            if input.peek(kw::this) {
+                input.parse::<kw::this>()?;
+                Ok(TermIdent::This)
+            } else if input.peek(Ident) {
                 Ok(TermIdent::Local(input.parse::<Ident>()?))
             } else if input.peek(LitStr) {
                 Ok(TermIdent::Variable(input.parse::<LitStr>()?))
@@ -756,11 +1237,17 @@ mod kw {
     syn::custom_keyword!(desc);
     syn::custom_keyword!(up);
 
+    // Variables
+    syn::custom_keyword!(this);
+
     // Access
     syn::custom_keyword!(out);
     syn::custom_keyword!(inout);
     syn::custom_keyword!(filter);
     syn::custom_keyword!(none);
+
+    // Observer options
+    syn::custom_keyword!(yield_existing);
 }
 
 impl Parse for TermOper {
@@ -816,6 +1303,19 @@ impl TermId {
     }
 }
 
+/// Parses the optional relationship of an `up` or `cascade` traversal, either as `up Rel` or `up(Rel)`.
+fn parse_trav_ident(input: ParseStream) -> Result<Option<TermIdent>> {
+    if input.peek(Paren) {
+        let inner;
+        parenthesized!(inner in input);
+        Ok(Some(inner.parse::<TermIdent>()?))
+    } else if input.peek(Ident) || input.peek(Token![$]) {
+        Ok(Some(input.parse::<TermIdent>()?))
+    } else {
+        Ok(None)
+    }
+}
+
 fn peek_trav(input: ParseStream) -> bool {
     input.peek(kw::cascade)
         || input.peek(kw::desc)
@@ -840,10 +1340,7 @@ impl Parse for TermId {
             if input.peek(kw::cascade) {
                 input.parse::<kw::cascade>()?;
                 out.trav_cascade = true;
-
-                if input.peek(Ident) || input.peek(Token![$]) {
-                    out.cascade_ident = Some(input.parse::<TermIdent>()?);
-                }
+                out.cascade_ident = parse_trav_ident(input)?;
             }
             if input.peek(kw::desc) {
                 input.parse::<kw::desc>()?;
@@ -852,10 +1349,7 @@ impl Parse for TermId {
             if input.peek(kw::up) {
                 input.parse::<kw::up>()?;
                 out.trav_up = true;
-
-                if input.peek(Ident) || input.peek(Token![$]) {
-                    out.up_ident = Some(input.parse::<TermIdent>()?);
-                }
+                out.up_ident = parse_trav_ident(input)?;
             }
             if input.peek(Token![self]) {
                 input.parse::<Token![self]>()?;
@@ -894,7 +1388,7 @@ impl Parse for Term {
         let reference = input.parse::<Reference>()?;
         if peek_id(&input) {
             let initial = input.parse::<TermId>()?;
-            if !input.peek(Token![,]) && !input.is_empty() {
+            if !input.peek(Token![,]) && !input.peek(Token![|]) && !input.is_empty() {
                 // Component or pair with explicit source
                 let inner;
                 parenthesized!(inner in input);
@@ -1086,7 +1580,120 @@ fn expand_term_type(term: &Term) -> Option<TokenStream> {
     }
 }
 
-fn expand_dsl(terms: &mut [Term]) -> (TokenStream, Vec<TokenStream>) {
+/// Maximum number of terms in a query, mirrors `FLECS_TERM_COUNT_MAX` of the C library.
+const TERM_COUNT_MAX: usize = if cfg!(feature = "flecs_term_count_64") {
+    64
+} else if cfg!(feature = "flecs_term_count_32") {
+    32
+} else {
+    16
+};
+
+/// Key identifying the component of a term and the entity it is matched on, used to detect aliasing.
+fn term_access_key(term: &Term) -> Option<String> {
+    fn ident_key(ident: &TermIdent) -> String {
+        match ident {
+            TermIdent::Local(ident) => format!("local:{ident}"),
+            TermIdent::Variable(var) => format!("var:{}", var.value()),
+            TermIdent::Literal(lit) => format!("lit:{}", lit.value()),
+            TermIdent::This => "this".to_string(),
+            TermIdent::SelfVar => "self".to_string(),
+            TermIdent::Singleton => "singleton".to_string(),
+            other => expand_type(other)
+                .map(|ty| ty.to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn trav_key(id: &TermId) -> String {
+        format!(
+            "{}{}{}{}{}{}",
+            id.trav_self,
+            id.trav_up,
+            id.up_ident.as_ref().map(ident_key).unwrap_or_default(),
+            id.trav_desc,
+            id.trav_cascade,
+            id.cascade_ident.as_ref().map(ident_key).unwrap_or_default()
+        )
+    }
+
+    let ty = match &term.ty {
+        TermType::Pair(first, second) => format!(
+            "({}, {})",
+            ident_key(first.ident.as_ref()?),
+            ident_key(second.ident.as_ref()?)
+        ),
+        TermType::Component(id) => ident_key(id.ident.as_ref()?),
+    };
+
+    // `$this` is the default source
+    let source = match &term.source.ident {
+        Some(TermIdent::This) | None => String::new(),
+        Some(ident) => ident_key(ident),
+    };
+
+    Some(format!("{ty} @ {source} {}", trav_key(&term.source)))
+}
+
+/// Checks the DSL for errors that would otherwise only be caught at runtime, or not at all.
+///
+/// - the number of terms can't exceed the maximum term count of a query
+/// - a component can't be accessed mutably more than once on the same source, or mutably and immutably
+/// - the access annotation of a term can't contradict its reference kind, such as `[in] &mut T`
+fn validate_dsl(terms: &[Term], iter_count: usize) -> Vec<TokenStream> {
+    let mut errors = Vec::new();
+
+    if terms.len() > TERM_COUNT_MAX {
+        let message = format!(
+            "Query has {} terms, which exceeds the maximum of {TERM_COUNT_MAX}. Enable the `flecs_term_count_32` or `flecs_term_count_64` feature to increase the limit.",
+            terms.len()
+        );
+        errors.push(quote_spanned! { terms[TERM_COUNT_MAX].span => compile_error!(#message); });
+    }
+
+    let mut accessed: Vec<(String, Reference)> = Vec::new();
+    for term in terms.iter().take(iter_count) {
+        let reference = term.reference;
+        match (term.access, reference) {
+            (Access::In, Reference::Mut) => errors.push(quote_spanned! {
+                term.span => compile_error!("Term is marked [in] but accessed as `&mut`, use `&` for read-only access.");
+            }),
+            (Access::Out | Access::InOut, Reference::Ref) => errors.push(quote_spanned! {
+                term.span => compile_error!("Term is marked [out] or [inout] but accessed as `&`, use `&mut` to write to it.");
+            }),
+            (Access::Out, _) => errors.push(quote_spanned! {
+                term.span => compile_error!("[out] is not supported on static terms, their access is derived from `&` and `&mut`.");
+            }),
+            (Access::None, _) => errors.push(quote_spanned! {
+                term.span => compile_error!("Term is marked [none] and can't be accessed, remove the `&` or `&mut` and place it after the static terms.");
+            }),
+            _ => {}
+        }
+
+        let Some(key) = term_access_key(term) else {
+            continue;
+        };
+
+        if let Some((_, previous)) = accessed.iter().find(|(k, _)| *k == key) {
+            let message = match (previous, reference) {
+                (Reference::Mut, Reference::Mut) => {
+                    "Component is accessed as `&mut` more than once on the same source."
+                }
+                (Reference::Mut, _) | (_, Reference::Mut) => {
+                    "Component is accessed both as `&mut` and `&` on the same source."
+                }
+                _ => continue,
+            };
+            errors.push(quote_spanned! { term.span => compile_error!(#message); });
+        } else {
+            accessed.push((key, reference));
+        }
+    }
+
+    errors
+}
+
+fn expand_dsl(terms: &mut [Term]) -> (TokenStream, Vec<TokenStream>, Vec<TokenStream>) {
     let mut iter_terms = Vec::new();
     for t in terms.iter() {
         match expand_term_type(t) {
@@ -1132,6 +1739,7 @@ fn expand_dsl(terms: &mut [Term]) -> (TokenStream, Vec<TokenStream>) {
                             ops.push(quote! { .set_first_name(#var_name) });
                         }
                         TermIdent::SelfVar => ops.push(quote! { .set_first_id(self) }),
+                        TermIdent::This => ops.push(quote! { .set_first_id(*flecs::This_) }),
                         TermIdent::Local(ident) => ops.push(quote! { .set_first_id(#ident) }),
                         TermIdent::Literal(lit) => ops.push(quote! { .set_first_name(#lit) }),
                         TermIdent::Singleton => ops.push(quote_spanned!{ first.span => ; compile_error!("Unexpected singleton identifier.") }),
@@ -1148,6 +1756,7 @@ fn expand_dsl(terms: &mut [Term]) -> (TokenStream, Vec<TokenStream>) {
                             ops.push(quote! { .set_second_name(#var_name) });
                         }
                         TermIdent::SelfVar => ops.push(quote! { .set_second_id(self) }),
+                        TermIdent::This => ops.push(quote! { .set_second_id(*flecs::This_) }),
                         TermIdent::Local(ident) => ops.push(quote! { .set_second_id(#ident) }),
                         TermIdent::Literal(lit) => ops.push(quote! { .set_second_name(#lit) }),
                         TermIdent::Singleton => ops.push(quote_spanned!{ second.span => ; compile_error!("Unexpected singleton identifier.") }),
@@ -1180,6 +1789,7 @@ fn expand_dsl(terms: &mut [Term]) -> (TokenStream, Vec<TokenStream>) {
                             ops.push(quote! { .set_var(#var_name) });
                         }
                         TermIdent::SelfVar => ops.push(quote! { .set_id(self) }),
+                        TermIdent::This => ops.push(quote! { .set_id(*flecs::This_) }),
                         TermIdent::Local(ident) => ops.push(quote! { .set_id(#ident) }),
                         TermIdent::Literal(lit) => ops.push(quote! { .name(#lit) }),
                         TermIdent::Singleton => ops.push(quote_spanned!{ term.span => ; compile_error!("Unexpected singleton identifier.") }),
@@ -1208,6 +1818,7 @@ fn expand_dsl(terms: &mut [Term]) -> (TokenStream, Vec<TokenStream>) {
                         ops.push(quote! { .set_src_name(#var_name) });
                     }
                     TermIdent::SelfVar => ops.push(quote! { .set_src_id(self) }),
+                    TermIdent::This => ops.push(quote! { .set_src_id(*flecs::This_) }),
                     TermIdent::Local(ident) => ops.push(quote! { .set_src_id(#ident) }),
                     TermIdent::Literal(lit) => ops.push(quote! { .set_src_name(#lit) }),
                     TermIdent::Singleton => ops.push(quote! { .singleton() }),
@@ -1241,10 +1852,10 @@ fn expand_dsl(terms: &mut [Term]) -> (TokenStream, Vec<TokenStream>) {
                 ops.push(quote! { .src() #( #id_ops )* });
             }
 
-            // Configure access
+            // Configure access, conflicts with the reference kind of static terms are reported by `validate_dsl`
             if iter_term {
-                if !matches!(t.access, Access::Omitted | Access::Filter) {
-                    ops.push(quote_spanned!{t.span => ; compile_error!("Only [filter] is allowed on static terms.")});
+                if t.access == Access::Filter {
+                    ops.push(quote! { .filter() });
                 }
             } else {
                 match &t.reference {
@@ -1275,7 +1886,8 @@ fn expand_dsl(terms: &mut [Term]) -> (TokenStream, Vec<TokenStream>) {
             }
         })
         .collect::<Vec<_>>();
-    (iter_type, builder_calls)
+    let errors = validate_dsl(terms, iter_terms.len());
+    (iter_type, builder_calls, errors)
 }
 
 #[proc_macro]
@@ -1283,13 +1895,14 @@ pub fn query(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
     let input = parse_macro_input!(input as Builder);
     let mut terms = input.dsl.terms;
 
-    let (iter_type, builder_calls) = expand_dsl(&mut terms);
+    let (iter_type, builder_calls, errors) = expand_dsl(&mut terms);
     let world = input.world;
     let doc = input.dsl.doc;
     let output = match input.name {
         Some(name) => quote! {
             {
                 #doc
+                #( #errors )*
                 #world.query_named::<#iter_type>(#name)
                 #(
                     #builder_calls
@@ -1299,6 +1912,7 @@ pub fn query(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
         None => quote! {
             {
                 #doc
+                #( #errors )*
                 #world.query::<#iter_type>()
                 #(
                     #builder_calls
@@ -1314,7 +1928,7 @@ pub fn system(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
     let input = parse_macro_input!(input as Builder);
     let mut terms = input.dsl.terms;
 
-    let (iter_type, builder_calls) = expand_dsl(&mut terms);
+    let (iter_type, builder_calls, errors) = expand_dsl(&mut terms);
     let world = input.world;
 
     let doc = input.dsl.doc;
@@ -1322,6 +1936,7 @@ pub fn system(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
         Some(name) => quote! {
             {
                 #doc
+                #( #errors )*
                 #world.system_named::<#iter_type>(#name)
                 #(
                     #builder_calls
@@ -1332,6 +1947,7 @@ pub fn system(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
         None => quote! {
             {
                 #doc
+                #( #errors )*
                 #world.system::<#iter_type>()
                 #(
                     #builder_calls
@@ -1345,7 +1961,8 @@ pub fn system(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
 struct Observer {
     name: Option<LitStr>,
     world: Expr,
-    event: Type,
+    events: Vec<Type>,
+    yield_existing: bool,
     dsl: Dsl,
 }
 
@@ -1360,13 +1977,25 @@ impl Parse for Observer {
         };
         let world = input.parse::<Expr>()?;
         input.parse::<Token![,]>()?;
-        let event = input.parse::<Type>()?;
+        let mut events = vec![input.parse::<Type>()?];
+        while input.peek(Token![|]) {
+            input.parse::<Token![|]>()?;
+            events.push(input.parse::<Type>()?);
+        }
         input.parse::<Token![,]>()?;
+        let yield_existing = if input.peek(kw::yield_existing) {
+            input.parse::<kw::yield_existing>()?;
+            input.parse::<Token![,]>()?;
+            true
+        } else {
+            false
+        };
         let dsl = input.parse::<Dsl>()?;
         Ok(Observer {
             name,
             world,
-            event,
+            events,
+            yield_existing,
             dsl,
         })
     }
@@ -1377,16 +2006,25 @@ pub fn observer(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
     let input = parse_macro_input!(input as Observer);
     let mut terms = input.dsl.terms;
 
-    let (iter_type, builder_calls) = expand_dsl(&mut terms);
-    let event_type = input.event;
+    let (iter_type, builder_calls, errors) = expand_dsl(&mut terms);
+    let event_type = &input.events[0];
+    let extra_events = &input.events[1..];
     let world = input.world;
+    let yield_existing = input
+        .yield_existing
+        .then(|| quote! { .yield_existing(true) });
 
     let doc = input.dsl.doc;
     let output = match input.name {
         Some(name) => quote! {
             {
                 #doc
+                #( #errors )*
                 #world.observer_named::<#event_type, #iter_type>(#name)
+                #(
+                    .add_event::<#extra_events>()
+                )*
+                #yield_existing
                 #(
                     #builder_calls
                 )*
@@ -1395,7 +2033,12 @@ pub fn observer(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
         None => quote! {
             {
                 #doc
+                #( #errors )*
                 #world.observer::<#event_type, #iter_type>()
+                #(
+                    .add_event::<#extra_events>()
+                )*
+                #yield_existing
                 #(
                     #builder_calls
                 )*